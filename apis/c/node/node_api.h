@@ -1,13 +1,108 @@
 #ifndef DORA_NODE_API_H
 #define DORA_NODE_API_H
+#include <stdbool.h>
 #include <stddef.h>
 #include <stdint.h>
 
+#define DORA_MAX_NDIM 8
+
+#ifndef ARROW_C_DATA_INTERFACE
+#define ARROW_C_DATA_INTERFACE
+
+#define ARROW_FLAG_DICTIONARY_ORDERED 1
+#define ARROW_FLAG_NULLABLE 2
+#define ARROW_FLAG_MAP_KEYS_SORTED 4
+
+struct ArrowSchema {
+  const char *format;
+  const char *name;
+  const char *metadata;
+  int64_t flags;
+  int64_t n_children;
+  struct ArrowSchema **children;
+  struct ArrowSchema *dictionary;
+  void (*release)(struct ArrowSchema *);
+  void *private_data;
+};
+
+struct ArrowArray {
+  int64_t length;
+  int64_t null_count;
+  int64_t offset;
+  int64_t n_buffers;
+  int64_t n_children;
+  const void **buffers;
+  struct ArrowArray **children;
+  struct ArrowArray *dictionary;
+  void (*release)(struct ArrowArray *);
+  void *private_data;
+};
+
+#endif // ARROW_C_DATA_INTERFACE
+
 void *init_dora_context_from_env();
 void free_dora_context(void *dora_context);
+int dora_reconnect(void *dora_context);
+int dora_enable_auto_reconnect(void *dora_context, bool buffer_outputs,
+                               size_t max_buffer_bytes);
+int dora_connection_status(void *dora_context, bool *out_connected,
+                           size_t *out_buffered_bytes);
+void dora_set_user_data(void *dora_context, void *user_data);
+typedef void (*DoraOutputHook)(const char *id_ptr, size_t id_len,
+                               const void *data_ptr, size_t data_len,
+                               void *user_data);
+void dora_register_output_hook(void *dora_context, DoraOutputHook hook,
+                               void *user_data);
+typedef uint64_t (*DoraClockSource)(void);
+void dora_set_clock_source(void *dora_context, DoraClockSource now_fn);
+int dora_set_session_id(void *dora_context, char *id_ptr, size_t id_len);
+typedef ptrdiff_t (*DoraInputCodec)(const uint8_t *in_ptr, size_t in_len,
+                                    uint8_t *out_ptr, size_t out_cap);
+int dora_register_input_codec(void *dora_context, char *id_ptr, size_t id_len,
+                              DoraInputCodec codec);
+void *dora_get_user_data(void *dora_context);
+void dora_node_uptime_nanos(void *dora_context, uint64_t *out_nanos);
+int dora_save_checkpoint(void *dora_context, uint8_t *data_ptr, size_t data_len,
+                         char *path_ptr, size_t path_len);
+int dora_load_checkpoint(void *dora_context, char *path_ptr, size_t path_len,
+                         uint8_t **out_ptr, size_t *out_len);
+void free_dora_checkpoint(uint8_t *ptr, size_t len);
+int dora_set_realtime_priority(void *dora_context, int priority);
+int dora_rotate_log(void *dora_context);
+int dora_barrier(void *dora_context, char *name_ptr, size_t name_len,
+                 size_t expected_count, uint64_t timeout_ms);
+
+void *init_dora_context_standalone(char *config_json_ptr, size_t config_json_len);
+int dora_standalone_push_input_f32(void *dora_context, char *id_ptr, size_t id_len,
+                                   float *data_ptr, size_t data_len);
+int dora_standalone_finish(void *dora_context);
 
 void *dora_next_event(void *dora_context);
+int dora_await_stop(void *dora_context);
+enum DoraLifecycleEvent {
+    DoraLifecycleEvent_DataflowStarted = 0,
+    DoraLifecycleEvent_Reload = 1,
+    DoraLifecycleEvent_DataflowStopping = 2,
+};
+int dora_next_lifecycle_event(void *dora_context, uint64_t timeout_ms);
+typedef void (*DoraEventCallback)(void *dora_context, const void *dora_event,
+                                  void *user_data);
+int dora_start_background_loop(void *dora_context, DoraEventCallback on_event,
+                               void *user_data);
+void dora_stop_background_loop(void *dora_context);
+int dora_pause_inputs(void *dora_context);
+int dora_resume_inputs(void *dora_context);
+int dora_drain_inputs(void *dora_context, void **out_events_ptr, size_t cap,
+                      size_t *out_count);
+int dora_drop_oldest_event(void *dora_context);
 void free_dora_event(void *dora_event);
+void dora_set_event_queue_policy(void *dora_context, size_t max_events,
+                                 bool drop_oldest);
+uint64_t dora_dropped_events(void *dora_context);
+int dora_set_link_policy(void *dora_context, bool reliable);
+void dora_machine_id(void *dora_context, char **out_ptr, size_t *out_len);
+const void *dora_install_stop_token(void *dora_context);
+bool dora_stop_token_is_set(const void *stop_token);
 
 enum DoraEventType {
     DoraEventType_Stop,
@@ -19,21 +114,226 @@ enum DoraEventType {
 enum DoraEventType read_dora_event_type(void *dora_event);
 
 void read_dora_input_id(void *dora_event, char **out_ptr, size_t *out_len);
-void read_dora_input_data_u8(void *dora_event, uint8_t **out_ptr,
+int read_dora_input_data_type_name(void *dora_event, char **out_ptr,
+                                   size_t *out_len);
+int read_dora_input_image(void *dora_event, void **out_ptr, size_t *out_width,
+                          size_t *out_height, size_t *out_channels, int *out_dtype);
+int read_dora_input_as_numpy_desc(void *dora_event, void **out_data_ptr,
+                                  size_t *out_shape_ptr, size_t *out_ndim,
+                                  char *out_dtype_char, ptrdiff_t *out_strides_ptr);
+int read_dora_input_shm_region(void *dora_event, const uint8_t **out_ptr,
+                               size_t *out_len, bool *out_is_shm);
+size_t read_dora_input_alignment(void *dora_event);
+int dora_input_is_zero_copy(void *dora_event);
+int read_dora_input_capacity(void *dora_event, size_t *out_capacity);
+int read_dora_input_validity(void *dora_event, uint8_t *out_validity_ptr, size_t len);
+int read_dora_event_input_status(void *dora_event, char **out_id_ptr, size_t *out_id_len);
+void read_dora_input_field_name(void *dora_event, char **out_ptr,
+                                size_t *out_len);
+int read_dora_input_age_nanos(void *dora_event, void *dora_context,
+                              uint64_t *out_nanos);
+int read_dora_input_ingress_nanos(void *dora_event, uint64_t *out_nanos);
+int read_dora_input_sequence(void *dora_event, uint64_t *out_seq);
+int read_dora_input_is_dropped(void *dora_event);
+int read_dora_input_verify_checksum(void *dora_event);
+int read_dora_input_is_first(void *dora_event);
+int read_dora_input_type_changed(void *dora_event);
+int dora_reset_input_first(void *dora_context, char *id_ptr, size_t id_len);
+int read_dora_input_data_u8(void *dora_event, uint8_t **out_ptr,
+                            size_t *out_len);
+int read_dora_input_data_i32(void *dora_event, int **out_ptr, size_t *out_len);
+int read_dora_input_data_f32(void *dora_event, float **out_ptr,
+                             size_t *out_len);
+int read_dora_input_data_f32_reshaped(void *dora_event,
+                                      const size_t *requested_dims_ptr,
+                                      size_t requested_ndim, float **out_ptr);
+int read_dora_input_data_u64(void *dora_event, uint64_t **out_ptr,
                              size_t *out_len);
-void read_dora_input_data_i32(void *dora_event, int **out_ptr, size_t *out_len);
-void read_dora_input_data_f32(void *dora_event, float **out_ptr,
-                              size_t *out_len);
-void read_dora_input_data_u64(void *dora_event, uint64_t **out_ptr,
-                              size_t *out_len);
+ptrdiff_t read_dora_input_binary_count(void *dora_event);
+int read_dora_input_data_binary(void *dora_event, size_t index,
+                                uint8_t **out_ptr, size_t *out_len);
+int read_dora_input_scalar_f32(void *dora_event, float *out_value);
+int read_dora_input_scalar_i32(void *dora_event, int32_t *out_value);
+int read_dora_input_scalar_f64(void *dora_event, double *out_value);
+int read_dora_input_scalar_u64(void *dora_event, uint64_t *out_value);
+void *dora_scalar_filter_new(size_t window);
+void dora_scalar_filter_push(void *filter, double value);
+void dora_scalar_filter_push_f32(void *filter, float value);
+int dora_scalar_filter_mean(const void *filter, double *out_value);
+int dora_scalar_filter_min(const void *filter, double *out_value);
+int dora_scalar_filter_max(const void *filter, double *out_value);
+void free_dora_scalar_filter(void *filter);
+int read_dora_input_list_offsets(void *dora_event, int32_t **out_ptr,
+                                 size_t *out_len);
+int read_dora_input_list_values_f32(void *dora_event, float **out_ptr,
+                                    size_t *out_len);
+int read_dora_input_dictionary(void *dora_event, int32_t **out_keys_ptr,
+                               size_t *out_keys_len, void **out_values_handle);
+int read_dora_input_ree(void *dora_event, int32_t **out_run_ends_ptr,
+                        size_t *out_run_count, void **out_values_handle);
+ptrdiff_t read_dora_input_map_len(void *dora_event);
+int read_dora_input_map_entry(void *dora_event, size_t index,
+                              uint8_t **out_key_ptr, size_t *out_key_len,
+                              uint8_t **out_val_ptr, size_t *out_val_len);
+int read_dora_input_detections(void *dora_event, float **out_boxes_ptr,
+                               float **out_scores_ptr, void **out_labels_handle,
+                               size_t *out_count);
+int read_dora_input_union_variant(void *dora_event, size_t index,
+                                  int8_t *out_type_id, size_t *out_value_offset);
+void *read_dora_input_union_child(void *dora_event, int8_t type_id,
+                                  char **out_name_ptr, size_t *out_name_len);
+size_t read_dora_array_len(void *array);
+int read_dora_array_string(void *array, size_t index, char **out_ptr,
+                           size_t *out_len);
+int read_dora_input_schema_metadata(void *dora_event, char *key_ptr,
+                                    size_t key_len, char **out_ptr,
+                                    size_t *out_len);
+int read_dora_input_record_batch(void *dora_event, void **out_batch_handle);
+size_t read_dora_record_batch_num_columns(void *batch);
+void *read_dora_record_batch_column(void *batch, size_t index,
+                                    char **out_name_ptr, size_t *out_name_len);
+void free_dora_record_batch(void *batch);
+int dora_send_record_batch(void *dora_context, char *id_ptr, size_t id_len,
+                           char *schema_json_ptr, size_t schema_json_len,
+                           struct ArrowArray *columns_ptr, size_t column_count);
+int dora_downcast_f64_to_f32(void *dora_event, float *out_buf, size_t cap,
+                             size_t *out_written);
+
+enum DoraDataType {
+    DoraDataType_Null,
+    DoraDataType_UInt8,
+    DoraDataType_Int32,
+    DoraDataType_Float32,
+    DoraDataType_UInt64,
+    DoraDataType_Float64,
+    DoraDataType_Unknown,
+};
+void *dora_clone_event_reader(void *dora_context);
+void *dora_reader_next_event(void *reader);
+void free_dora_event_reader(void *reader);
+void *dora_window_reader_new(void *dora_context, size_t window_size);
+int dora_window_get(void *reader, size_t offset, const void **out_event);
+int dora_window_advance(void *reader);
+void free_dora_window_reader(void *reader);
+int dora_peek_input_data_type(void *dora_context);
+int dora_input_declared_type(void *dora_context, char *id_ptr, size_t id_len);
+int dora_output_declared_type(void *dora_context, char *id_ptr, size_t id_len);
+int dora_get_config(void *dora_context, char *key_ptr, size_t key_len,
+                    char **out_ptr, size_t *out_len);
+int dora_set_config(void *dora_context, char *key_ptr, size_t key_len,
+                    char *value_ptr, size_t value_len);
+int dora_require_config(void *dora_context, char **keys_ptr,
+                        size_t *key_lens_ptr, size_t count);
+int dora_get_topology(void *dora_context, char **out_json_ptr,
+                      size_t *out_json_len);
+int dora_output_subscriber_count(void *dora_context, char *id_ptr, size_t id_len);
+ptrdiff_t dora_output_queue_capacity(void *dora_context, char *id_ptr,
+                                     size_t id_len);
+int dora_collect_latest(void *dora_context, uint64_t timeout_ms,
+                        void **out_handle);
+int dora_snapshot_get(void *snapshot, char *id_ptr, size_t id_len,
+                      void **out_data_ptr, size_t *out_len,
+                      enum DoraDataType *out_dtype);
+void free_dora_snapshot(void *snapshot);
+int dora_latest_as_struct(void *dora_context, uint64_t timeout_ms,
+                          void **out_handle);
+size_t read_dora_struct_num_fields(void *handle);
+void *read_dora_struct_field(void *handle, size_t index, char **out_name_ptr,
+                             size_t *out_name_len);
+int dora_send_output_struct(void *dora_context, char *id_ptr, size_t id_len,
+                            void *handle);
+void free_dora_struct(void *handle);
+int dora_enable_input_history(void *dora_context, char *id_ptr, size_t id_len,
+                              size_t capacity);
+int dora_get_history(void *dora_context, char *id_ptr, size_t id_len,
+                     size_t index, void **out_data_ptr, size_t *out_len,
+                     enum DoraDataType *out_dtype);
+int dora_input_to_parquet(void *dora_event, char *path_ptr, size_t path_len);
+int dora_materialize_input(void *dora_event, void **out_ptr, size_t *out_len,
+                           enum DoraDataType *out_dtype);
+void dora_free_buffer(void *ptr, size_t len, enum DoraDataType dtype);
+
+enum DoraStatus {
+    DoraStatus_Ok = 0,
+    DoraStatus_Error = -1,
+    DoraStatus_InvalidUtf8Id = -2,
+    DoraStatus_RateLimited = -3,
+    DoraStatus_ShmFull = -4,
+    DoraStatus_WindowFull = -5,
+    DoraStatus_Unchanged = -6,
+    DoraStatus_Timeout = -7,
+    DoraStatus_OutputDisabled = -8,
+};
+void dora_last_error(char **out_ptr, size_t *out_len);
 
 int dora_send_output_u8(void *dora_context, char *id_ptr, size_t id_len,
                         uint8_t *data_ptr, size_t data_len);
+int dora_send_output_u8_checked(void *dora_context, char *id_ptr,
+                                size_t id_len, uint8_t *data_ptr,
+                                size_t data_len);
+int dora_set_shutdown_output(void *dora_context, char *id_ptr, size_t id_len,
+                             uint8_t *data_ptr, size_t data_len);
 int dora_send_output_i32(void *dora_context, char *id_ptr, size_t id_len,
                          int *data_ptr, size_t data_len);
 int dora_send_output_f32(void *dora_context, char *id_ptr, size_t id_len,
                          float *data_ptr, size_t data_len);
+int dora_send_output_f32_to(void *dora_context, char *id_ptr, size_t id_len,
+                            float *data_ptr, size_t data_len,
+                            char **targets_ptr, size_t *target_lens_ptr,
+                            size_t target_count);
+int dora_send_detections(void *dora_context, char *id_ptr, size_t id_len,
+                         float *boxes_ptr, float *scores_ptr, char **labels_ptr,
+                         size_t *label_lens_ptr, size_t count);
+int dora_send_output_f32_nullable(void *dora_context, char *id_ptr, size_t id_len,
+                                  float *data_ptr, uint8_t *validity_ptr,
+                                  size_t len);
+void *dora_output_accumulator_new(void *dora_context, char *id_ptr, size_t id_len,
+                                  int dtype);
+void dora_output_accumulator_push_f32(void *accumulator, float *data_ptr,
+                                      size_t data_len);
+int dora_output_accumulator_flush(void *accumulator);
+void free_dora_output_accumulator(void *accumulator);
+int dora_send_output_f32_with_schema_meta(void *dora_context, char *id_ptr,
+                                          size_t id_len, float *data_ptr,
+                                          size_t data_len, const char **keys,
+                                          const char **vals, size_t count);
+int dora_send_output_f32_on_change(void *dora_context, char *id_ptr,
+                                   size_t id_len, float *data_ptr,
+                                   size_t data_len);
 int dora_send_output_u64(void *dora_context, char *id_ptr, size_t id_len,
                          uint64_t *data_ptr, size_t data_len);
+int dora_send_output_dropped(void *dora_context, char *id_ptr, size_t id_len);
+int dora_send_arrow_file(void *dora_context, char *id_ptr, size_t id_len,
+                         char *path_ptr, size_t path_len);
+
+int dora_ack_input(void *dora_context, void *dora_event);
+int dora_send_output_f32_sync(void *dora_context, char *id_ptr, size_t id_len,
+                              float *data_ptr, size_t data_len,
+                              uint64_t timeout_ms);
+int dora_send_reply_f32(void *dora_context, void *dora_event, float *data_ptr,
+                        size_t data_len);
+int dora_set_output_window(void *dora_context, char *id_ptr, size_t id_len,
+                           char *ack_input_ptr, size_t ack_input_len,
+                           uint64_t window);
+int dora_measure_rtt(void *dora_context, char *downstream_id_ptr,
+                     size_t downstream_id_len, uint64_t *out_nanos,
+                     uint64_t timeout_ms);
+int dora_echo_probe(void *dora_context, void *dora_event);
+int dora_output_would_block(void *dora_context, size_t bytes);
+int dora_set_output_rate_limit(void *dora_context, char *id_ptr, size_t id_len,
+                               double hz);
+int dora_set_active_outputs(void *dora_context, char **ids_ptr,
+                            size_t *id_lens_ptr, size_t count);
+
+int dora_input_jitter_stats(void *dora_context, char *id_ptr, size_t id_len,
+                            uint64_t *out_mean_ns, uint64_t *out_max_ns,
+                            uint64_t *out_std_ns);
+int dora_output_stats(void *dora_context, char *id_ptr, size_t id_len,
+                      uint64_t *out_messages, uint64_t *out_bytes);
+int dora_emit_metric(void *dora_context, char *name_ptr, size_t name_len,
+                     double value);
+
+int dora_begin_tick(void *dora_context);
+int dora_end_tick(void *dora_context);
 
 #endif
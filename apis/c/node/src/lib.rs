@@ -1,368 +1,8037 @@
+//! C API for dora nodes, declared in the `node_api.h` header.
+//!
+//! # Safety
+//!
+//! The exported functions are called from C, so the compiler can't check their
+//! arguments. Unless the `## Safety` section of a function states otherwise, the
+//! following requirements apply to all of them:
+//!
+//! - A `context` argument must be a dora context created through
+//!   [`init_dora_context_from_env`] or [`init_dora_context_standalone`]. The context
+//!   must be still valid, i.e., not freed yet, and must not be used by multiple
+//!   threads at the same time.
+//! - An `event` argument must be a dora event received through [`dora_next_event`].
+//!   The event must be still valid, i.e., not freed yet.
+//! - Pointer and length arguments, e.g. `id_ptr` and `id_len` or `data_ptr` and
+//!   `data_len`, must be the start pointer and the number of elements of an array
+//!   that is valid for reads. IDs, names, keys, and paths are UTF8-encoded strings.
+//! - Output arguments, e.g. `out_ptr` and `out_len`, must be valid for writes.
+//! - Pointers that are written to output arguments point into the memory of the
+//!   given event or context, unless documented otherwise. They must not be used
+//!   after freeing the event or context.
 #![deny(unsafe_op_in_unsafe_fn)]
 use arrow_array::UInt8Array;
-use arrow_array::{Array, Float32Array, Int32Array, UInt64Array};
-use dora_node_api::{arrow::array::AsArray, DoraNode, Event, EventStream};
+use arrow_array::{Array, Float32Array, Int32Array, NullArray, UInt64Array};
+use dora_node_api::{
+    arrow::{
+        array::{
+            make_array, ArrayData, ArrayRef, AsArray, FixedSizeListArray, Float32Builder,
+            ListArray, MapArray, RunArray, StringArray, StructArray, UnionArray,
+        },
+        buffer::OffsetBuffer,
+        compute::{cast, concat},
+        datatypes::{
+            ArrowPrimitiveType, DataType, Field, Float32Type, Float64Type, Int32Type, Schema,
+            SchemaRef, UInt64Type, UInt8Type,
+        },
+        ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema},
+        ipc::reader::FileReader,
+        record_batch::RecordBatch,
+    },
+    arrow_utils::{copy_array_into_sample, required_data_size},
+    dora_core::{
+        config::{DataId, Input, InputMapping, NodeId},
+        descriptor::{CoreNodeKind, NodeKind},
+        message::{ArrowTypeInfo, TARGET_PARAMETER_PREFIX},
+    },
+    uhlc, ArrowData, DoraNode, Event, EventStream, MappedInputData, Metadata, MetadataParameters,
+    Parameter, RecvTimeoutError, SharedMemoryFull,
+};
 use eyre::Context;
-use std::sync::Arc;
-use std::{ffi::c_void, ptr, slice};
+use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc, Mutex,
+};
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    ffi::{c_char, c_void, CStr},
+    fs::File,
+    io::{Read, Seek},
+    path::{Path, PathBuf},
+    ptr, slice,
+    time::{Duration, Instant},
+};
 pub const HEADER_NODE_API: &str = include_str!("../node_api.h");
 
+/// Name of the custom metadata parameter that stores the arrow field name of a message.
+pub const FIELD_NAME_PARAMETER: &str = "field_name";
+/// Name of the custom metadata parameter that marks a message as dropped frame.
+pub const DROPPED_PARAMETER: &str = "dropped";
+/// Prefix of the custom metadata parameters that store arrow schema metadata.
+///
+/// Dora transfers arrays without their schema, so each schema metadata entry
+/// `key: value` is stored as a custom parameter `schema_metadata.<key>` instead.
+pub const SCHEMA_METADATA_PREFIX: &str = "schema_metadata.";
+/// Name of the custom metadata parameter that marks a message as latency probe, see
+/// [`dora_measure_rtt`].
+pub const RTT_PROBE_PARAMETER: &str = "rtt_probe";
+/// Name of the custom metadata parameter that correlates a request with its reply,
+/// see [`dora_send_output_f32_sync`].
+pub const CORRELATION_PARAMETER: &str = "correlation_id";
+/// Name of the custom metadata parameter that stores the CRC-32 checksum of a message.
+pub const CHECKSUM_PARAMETER: &str = "crc32";
+/// Name of the custom metadata parameter that stores the shape of an image as
+/// `[height, width]` or `[height, width, channels]` list, see [`read_dora_input_image`].
+pub const SHAPE_PARAMETER: &str = "shape";
+/// Name of the custom metadata parameter that marks a message as compressed.
+///
+/// Supported values are `lz4` (LZ4 block with the uncompressed size prepended as
+/// little-endian `u32`) and `zstd` (Zstandard frame). Compressed `UInt8` inputs are
+/// decompressed by [`read_dora_input_data_u8`].
+pub const COMPRESSION_PARAMETER: &str = "compression";
+/// Name of the custom metadata parameter that stores the session ID of a message,
+/// see [`dora_set_session_id`].
+pub const SESSION_PARAMETER: &str = "session_id";
+/// Name of the custom metadata parameter that stores the per-output sequence number,
+/// see [`read_dora_input_sequence`].
+///
+/// Note that this changes the message format of nodes using the C API: all their
+/// outputs carry this additional parameter. Nodes using the other node APIs don't
+/// set it. The name is prefixed with `dora.` to avoid clashes with user parameters.
+/// If the sender already set a parameter with this name, it is kept.
+pub const SEQUENCE_PARAMETER: &str = "dora.sequence";
+/// Maximum number of dimensions reported by [`read_dora_input_as_numpy_desc`].
+pub const DORA_MAX_NDIM: usize = 8;
+
+/// Name of the struct field that stores the bounding boxes of detections, see
+/// [`dora_send_detections`].
+pub const DETECTION_BBOX_FIELD: &str = "bbox";
+/// Name of the struct field that stores the confidence scores of detections.
+pub const DETECTION_SCORE_FIELD: &str = "score";
+/// Name of the struct field that stores the class labels of detections.
+pub const DETECTION_LABEL_FIELD: &str = "label";
+
 struct DoraContext {
-    node: &'static mut DoraNode,
-    events: EventStream,
+    node: NodeHandle,
+    events: Events,
+    /// Outputs buffered between [`dora_begin_tick`] and [`dora_end_tick`].
+    tick: Option<Vec<(DataId, MetadataParameters, ArrayRef)>>,
+    /// Input IDs that were already received, see [`read_dora_input_is_first`].
+    seen_inputs: HashSet<DataId>,
+    /// Data type of the last input per input ID, see [`read_dora_input_type_changed`].
+    input_types: HashMap<DataId, DataType>,
+    /// Statistics about the sent messages per output, see [`dora_output_stats`].
+    output_stats: HashMap<DataId, OutputStats>,
+    /// Inter-arrival times per input, see [`dora_input_jitter_stats`].
+    arrival_stats: HashMap<DataId, ArrivalStats>,
+    /// Events that were already received from the event stream, but not handed out yet.
+    buffered_events: VecDeque<Event>,
+    /// Limits the number of buffered events, see [`dora_set_event_queue_policy`].
+    queue_policy: Option<QueuePolicy>,
+    /// Number of input events that were dropped because of the `queue_policy` or
+    /// the link policy.
+    dropped_events: u64,
+    /// Whether buffered inputs are replaced by newer data of the same input, see
+    /// [`dora_set_link_policy`].
+    latest_wins: bool,
+    /// Whether a `Stop` event was handed out, see [`dora_next_event`].
+    stopped: bool,
+    /// Output that is sent when the node stops, see [`dora_set_shutdown_output`].
+    shutdown_output: Option<(DataId, ArrayRef)>,
+    /// Whether event delivery is paused, see [`dora_pause_inputs`].
+    paused: bool,
+    /// Set when a `Stop` event is received, see [`dora_install_stop_token`].
+    stop_token: Option<Arc<AtomicBool>>,
+    /// Thread that forwards the events of the event stream, see [`Self::stop_token`].
+    forwarder: Option<Forwarder>,
+    /// Outputs that sends are allowed on, see [`dora_set_active_outputs`]. All
+    /// outputs are active if `None`.
+    active_outputs: Option<HashSet<DataId>>,
+    /// Maximum send rates per output, see [`dora_set_output_rate_limit`].
+    rate_limits: HashMap<DataId, RateLimit>,
+    /// Flow control windows per output, see [`dora_set_output_window`].
+    windows: HashMap<DataId, FlowWindow>,
+    /// Number of latency probes sent through [`dora_measure_rtt`].
+    rtt_probes: u64,
+    /// Number of requests sent through [`dora_send_output_f32_sync`].
+    sync_requests: u64,
+    /// Sequence number of the next message per output, see [`SEQUENCE_PARAMETER`].
+    sequence_numbers: HashMap<DataId, u64>,
+    /// Last sent payload per output, see [`dora_send_output_f32_on_change`].
+    last_payloads: HashMap<DataId, Vec<u8>>,
+    /// Opaque pointer set through [`dora_set_user_data`], never dereferenced.
+    user_data: *mut c_void,
+    /// Time at which the context was created, see [`dora_node_uptime_nanos`].
+    started: Instant,
+    /// Configuration values of the node, see [`dora_get_config`].
+    config: BTreeMap<String, String>,
+    /// JSON description of the dataflow graph, see [`dora_get_topology`].
+    topology: Option<String>,
+    /// Latest data of each input, see [`dora_latest_as_struct`].
+    held_inputs: BTreeMap<DataId, ArrayRef>,
+    /// Recently received inputs per input ID, see [`dora_enable_input_history`].
+    input_history: HashMap<DataId, InputHistory>,
+    /// Callbacks that are invoked before each send, see [`dora_register_output_hook`].
+    output_hooks: Vec<(DoraOutputHook, *mut c_void)>,
+    /// Decoders of custom input encodings, see [`dora_register_input_codec`].
+    input_codecs: HashMap<DataId, DoraInputCodec>,
+    /// Reconnection state, see [`dora_enable_auto_reconnect`].
+    auto_reconnect: Option<AutoReconnect>,
+    /// User clock for output timestamps, see [`dora_set_clock_source`].
+    clock_source: Option<DoraClockSource>,
+    /// Time of the last timestamp taken from the `clock_source`.
+    last_clock_time: Option<uhlc::NTP64>,
+    /// Attached to the metadata of all outputs, see [`dora_set_session_id`].
+    session_id: Option<String>,
+    /// Exporter of the metrics emitted through [`dora_emit_metric`], created on first use.
+    #[cfg(feature = "metrics")]
+    metrics: Option<NodeMetrics>,
+}
+
+impl DoraContext {
+    fn new(node: NodeHandle, events: Events) -> Self {
+        Self {
+            config: node.config(),
+            topology: None,
+            node,
+            events,
+            tick: None,
+            seen_inputs: HashSet::new(),
+            input_types: HashMap::new(),
+            output_stats: HashMap::new(),
+            arrival_stats: HashMap::new(),
+            buffered_events: VecDeque::new(),
+            queue_policy: None,
+            dropped_events: 0,
+            latest_wins: false,
+            stopped: false,
+            shutdown_output: None,
+            paused: false,
+            stop_token: None,
+            forwarder: None,
+            active_outputs: None,
+            rate_limits: HashMap::new(),
+            windows: HashMap::new(),
+            rtt_probes: 0,
+            sync_requests: 0,
+            sequence_numbers: HashMap::new(),
+            last_payloads: HashMap::new(),
+            user_data: ptr::null_mut(),
+            started: Instant::now(),
+            held_inputs: BTreeMap::new(),
+            input_history: HashMap::new(),
+            output_hooks: Vec::new(),
+            input_codecs: HashMap::new(),
+            auto_reconnect: None,
+            clock_source: None,
+            last_clock_time: None,
+            session_id: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    /// Sends the given output, or buffers it if a tick is in progress.
+    fn send_output(
+        &mut self,
+        output_id: DataId,
+        parameters: MetadataParameters,
+        data: ArrayRef,
+    ) -> eyre::Result<()> {
+        if let Some(active) = &self.active_outputs {
+            if !active.contains(&output_id) {
+                return Err(OutputDisabled(output_id).into());
+            }
+        }
+        if self.windows.contains_key(&output_id) {
+            self.receive_acks();
+            let sent = self.output_stats.get(&output_id).map_or(0, |s| s.messages);
+            if let Some(window) = self.windows.get(&output_id) {
+                let sent = sent.saturating_sub(window.sent_before_reconnect);
+                if sent.saturating_sub(window.acked) >= window.size {
+                    return Err(WindowFull(output_id).into());
+                }
+            }
+        }
+        let now = Instant::now();
+        if let Some(limit) = self.rate_limits.get(&output_id) {
+            if let Some(last_sent) = limit.last_sent {
+                if now.duration_since(last_sent) < limit.min_interval {
+                    return Err(RateLimited(output_id).into());
+                }
+            }
+        }
+        self.run_output_hooks(&output_id, &data);
+        if let Some(pending) = &mut self.tick {
+            if !self.node.has_output(&output_id) {
+                eyre::bail!("unknown output {output_id}");
+            }
+            if let Some(limit) = self.rate_limits.get_mut(&output_id) {
+                limit.last_sent = Some(now);
+            }
+            pending.push((output_id, parameters, data));
+            return Ok(());
+        }
+        let timestamp = self.new_timestamp();
+        self.send_array(output_id.clone(), parameters, data, timestamp)
+            .wrap_err("failed to send output")?;
+        // failed sends don't count towards the rate limit
+        if let Some(limit) = self.rate_limits.get_mut(&output_id) {
+            limit.last_sent = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Invokes the callbacks registered through [`dora_register_output_hook`].
+    fn run_output_hooks(&self, output_id: &DataId, data: &ArrayRef) {
+        if self.output_hooks.is_empty() {
+            return;
+        }
+        let (data_ptr, data_len) = with_value_bytes(data, |bytes| (bytes.as_ptr(), bytes.len()))
+            .unwrap_or((ptr::null(), 0));
+        let id = output_id.as_str();
+        for (hook, user_data) in &self.output_hooks {
+            unsafe { hook(id.as_ptr(), id.len(), data_ptr.cast(), data_len, *user_data) };
+        }
+    }
+
+    /// Marks the event stream as stopped after a `Stop` event was received.
+    fn handle_stop(&mut self) {
+        self.stopped = true;
+        self.send_shutdown_output();
+    }
+
+    /// Sends the output registered through [`dora_set_shutdown_output`], if it was
+    /// not sent yet.
+    ///
+    /// The output bypasses ticks, rate limits, and flow control windows.
+    fn send_shutdown_output(&mut self) {
+        let Some((output_id, data)) = self.shutdown_output.take() else {
+            return;
+        };
+        self.run_output_hooks(&output_id, &data);
+        let timestamp = self.new_timestamp();
+        if let Err(err) = self.send_array(output_id, Default::default(), data, timestamp) {
+            tracing::error!("failed to send shutdown output: {err:?}");
+        }
+    }
+
+    /// Returns the timestamp for the next output message.
+    ///
+    /// Uses the clock registered through [`dora_set_clock_source`] if there is one,
+    /// otherwise the hybrid logical clock of the node.
+    fn new_timestamp(&mut self) -> uhlc::Timestamp {
+        let Some(now_fn) = self.clock_source else {
+            return self.node.clock().new_timestamp();
+        };
+        let mut time = uhlc::NTP64::from(Duration::from_nanos(unsafe { now_fn() }));
+        if let Some(last) = self.last_clock_time {
+            // keep the timestamps of the node strictly increasing
+            if time <= last {
+                time = uhlc::NTP64(last.0 + 1);
+            }
+        }
+        self.last_clock_time = Some(time);
+        uhlc::Timestamp::new(time, *self.node.clock().get_id())
+    }
+
+    /// Returns the next event, either from the buffer or from the event stream.
+    ///
+    /// Returns `None` after a `Stop` event was returned.
+    fn next_event(&mut self) -> Option<Event> {
+        if self.stopped {
+            return None;
+        }
+        if self.paused {
+            return self.next_stop_event();
+        }
+        self.buffer_available_events();
+        loop {
+            let event = match self.buffered_events.pop_front() {
+                Some(event) => event,
+                None => match self.events.recv() {
+                    Some(event) => event,
+                    None if self.reconnect_after_outage() => continue,
+                    None => return None,
+                },
+            };
+            if !self.handle_ack(&event) {
+                if matches!(event, Event::Stop) {
+                    self.handle_stop();
+                }
+                break Some(event);
+            }
+        }
+    }
+
+    /// Like [`Self::next_event`], but waits at most `timeout` for the next event.
+    ///
+    /// Returns [`RecvTimeoutError::Disconnected`] if no more events will be handed
+    /// out, e.g. because a `Stop` event was already returned.
+    fn next_event_timeout(&mut self, timeout: Duration) -> Result<Event, RecvTimeoutError> {
+        if self.stopped {
+            return Err(RecvTimeoutError::Disconnected);
+        }
+        self.buffer_available_events();
+        let start = Instant::now();
+        loop {
+            let buffered = if self.paused {
+                // only the `Stop` event is handed out while paused
+                let stop = self
+                    .buffered_events
+                    .iter()
+                    .position(|e| matches!(e, Event::Stop));
+                stop.and_then(|index| self.buffered_events.remove(index))
+            } else {
+                self.buffered_events.pop_front()
+            };
+            let event = match buffered {
+                Some(event) => event,
+                None => {
+                    let remaining = timeout.saturating_sub(start.elapsed());
+                    let event = match self.events.try_recv_timeout(remaining) {
+                        Ok(event) => event,
+                        Err(RecvTimeoutError::Disconnected) if self.reconnect_after_outage() => {
+                            continue
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    if self.paused && !matches!(event, Event::Stop) {
+                        if !self.handle_ack(&event) {
+                            self.buffer_event(event);
+                        }
+                        continue;
+                    }
+                    event
+                }
+            };
+            if !self.handle_ack(&event) {
+                if matches!(event, Event::Stop) {
+                    self.handle_stop();
+                }
+                break Ok(event);
+            }
+        }
+    }
+
+    /// Buffers all events until a `Stop` event arrives, which is returned.
+    ///
+    /// Returns `None` if the event stream is closed before.
+    fn next_stop_event(&mut self) -> Option<Event> {
+        if let Some(index) = self
+            .buffered_events
+            .iter()
+            .position(|e| matches!(e, Event::Stop))
+        {
+            self.handle_stop();
+            return self.buffered_events.remove(index);
+        }
+        loop {
+            let event = match self.events.recv() {
+                Some(event) => event,
+                None if self.reconnect_after_outage() => continue,
+                None => return None,
+            };
+            if self.handle_ack(&event) {
+                continue;
+            }
+            if matches!(event, Event::Stop) {
+                self.handle_stop();
+                break Some(event);
+            }
+            self.buffer_event(event);
+        }
+    }
+
+    /// Sends a latency probe to the given downstream node and waits up to `timeout`
+    /// for its echo.
+    ///
+    /// Acknowledgements that arrive in the meantime are processed, other events are
+    /// kept in the event buffer.
+    fn measure_rtt(&mut self, downstream: &str, timeout: Duration) -> eyre::Result<Duration> {
+        let probe = self.rtt_probes;
+        self.rtt_probes += 1;
+        let ping = DataId::from(format!("ping_{downstream}"));
+        let pong = DataId::from(format!("pong_{downstream}"));
+        if !self.node.has_input(&pong) {
+            eyre::bail!("unknown input {pong}");
+        }
+        let mut parameters = MetadataParameters::default();
+        parameters.custom.insert(
+            RTT_PROBE_PARAMETER.to_owned(),
+            Parameter::Integer(probe as i64),
+        );
+
+        let start = Instant::now();
+        let timestamp = self.new_timestamp();
+        self.send_array(
+            ping.clone(),
+            parameters,
+            Arc::new(NullArray::new(0)),
+            timestamp,
+        )
+        .wrap_err("failed to send latency probe")?;
+        loop {
+            let event = timeout
+                .checked_sub(start.elapsed())
+                .and_then(|remaining| self.events.recv_timeout(remaining))
+                .ok_or(ReplyTimeout(ping.clone()))?;
+            if self.handle_ack(&event) {
+                continue;
+            }
+            if let Event::Input { id, metadata, .. } = &event {
+                let echoed = metadata.parameters.custom.get(RTT_PROBE_PARAMETER);
+                if id == &pong && echoed == Some(&Parameter::Integer(probe as i64)) {
+                    return Ok(start.elapsed());
+                }
+            }
+            self.buffer_event(event);
+        }
+    }
+
+    /// Sends the given request and waits for the correlated reply.
+    ///
+    /// The reply is put at the front of the event buffer. Acknowledgements that
+    /// arrive in the meantime are processed, other events are kept in the event
+    /// buffer behind the reply.
+    fn send_request(
+        &mut self,
+        output_id: DataId,
+        data: ArrayRef,
+        timeout: Duration,
+    ) -> eyre::Result<()> {
+        if self.tick.is_some() {
+            eyre::bail!("requests cannot be sent between `dora_begin_tick` and `dora_end_tick`");
+        }
+        let reply_id = DataId::from(format!("reply_{output_id}"));
+        if !self.node.has_input(&reply_id) {
+            eyre::bail!("unknown input {reply_id}");
+        }
+        let correlation = Parameter::Integer(self.sync_requests as i64);
+        self.sync_requests += 1;
+        let mut parameters = MetadataParameters::default();
+        parameters
+            .custom
+            .insert(CORRELATION_PARAMETER.to_owned(), correlation.clone());
+        self.send_output(output_id.clone(), parameters, data)?;
+
+        let start = Instant::now();
+        loop {
+            let event = timeout
+                .checked_sub(start.elapsed())
+                .and_then(|remaining| self.events.recv_timeout(remaining))
+                .ok_or(ReplyTimeout(output_id.clone()))?;
+            if self.handle_ack(&event) {
+                continue;
+            }
+            if let Event::Input { id, metadata, .. } = &event {
+                let correlated = metadata.parameters.custom.get(CORRELATION_PARAMETER);
+                if id == &reply_id && correlated == Some(&correlation) {
+                    self.buffered_events.push_front(event);
+                    return Ok(());
+                }
+            }
+            self.buffer_event(event);
+        }
+    }
+
+    /// Moves all available events into the event buffer and processes the
+    /// acknowledgements among them.
+    fn receive_acks(&mut self) {
+        for event in std::mem::take(&mut self.buffered_events) {
+            if !self.handle_ack(&event) {
+                self.buffered_events.push_back(event);
+            }
+        }
+        while let Some(event) = self.events.try_recv() {
+            self.buffer_event(event);
+        }
+    }
+
+    /// Updates the flow control window if the given event is an acknowledgement
+    /// sent through [`dora_ack_input`].
+    ///
+    /// Returns `true` if the event was an acknowledgement.
+    fn handle_ack(&mut self, event: &Event) -> bool {
+        let Event::Input { id, data, .. } = event else {
+            return false;
+        };
+        let Some(window) = self.windows.values_mut().find(|w| &w.ack_input == id) else {
+            return false;
+        };
+        if let Some(acked) = data.as_primitive_opt::<UInt64Type>() {
+            if let Some(sequence) = acked.values().iter().max() {
+                window.acked = window.acked.max(sequence + 1);
+            }
+        }
+        true
+    }
+
+    /// Returns the next event if it is an input event that is immediately available.
+    ///
+    /// Returns `None` if no event is available or if the next event is not an input.
+    /// In the latter case, the event stays in the buffer.
+    fn try_next_input(&mut self) -> Option<Event> {
+        if self.stopped || self.paused {
+            return None;
+        }
+        loop {
+            if !matches!(self.peek_event()?, Event::Input { .. }) {
+                return None;
+            }
+            let event = self.buffered_events.pop_front()?;
+            if !self.handle_ack(&event) {
+                return Some(event);
+            }
+        }
+    }
+
+    /// Wraps the given event for passing it to C.
+    fn wrap_event(&mut self, event: Event) -> *mut c_void {
+        let event = self.decode_input(event);
+        let (is_first, type_changed) = match &event {
+            Event::Input { id, data, .. } => {
+                if let Some(history) = self.input_history.get_mut(id) {
+                    history.record(data.0.clone());
+                }
+                self.arrival_stats
+                    .entry(id.clone())
+                    .or_default()
+                    .record(Instant::now());
+                let previous_type = self
+                    .input_types
+                    .insert(id.clone(), data.data_type().clone());
+                let type_changed = previous_type.is_some_and(|t| &t != data.data_type());
+                (self.seen_inputs.insert(id.clone()), type_changed)
+            }
+            _ => (false, false),
+        };
+        let event = DoraEvent {
+            event,
+            ingress: self.node.clock().new_timestamp(),
+            is_first,
+            type_changed,
+            decompressed: OnceCell::new(),
+            type_name: OnceCell::new(),
+        };
+        Box::into_raw(Box::new(event)).cast()
+    }
+
+    /// Runs the input codec that was registered for the given input event, if any.
+    ///
+    /// Inputs that fail to decode are replaced by an error event.
+    fn decode_input(&self, event: Event) -> Event {
+        let Event::Input {
+            id,
+            mut metadata,
+            data,
+        } = event
+        else {
+            return event;
+        };
+        let Some(codec) = self.input_codecs.get(&id) else {
+            return Event::Input { id, metadata, data };
+        };
+        match run_codec(*codec, &data) {
+            Ok(decoded) => {
+                metadata.type_info = ArrowTypeInfo::byte_array(decoded.len());
+                Event::Input {
+                    id,
+                    metadata,
+                    data: ArrowData(Arc::new(UInt8Array::from(decoded))),
+                }
+            }
+            Err(err) => Event::Error(format!("failed to decode input `{id}`: {err:?}")),
+        }
+    }
+
+    /// Returns the next event without removing it, if one is immediately available.
+    fn peek_event(&mut self) -> Option<&Event> {
+        if self.buffered_events.is_empty() {
+            let event = self.events.try_recv()?;
+            self.buffered_events.push_back(event);
+        }
+        self.buffered_events.front()
+    }
+
+    /// Moves all events that are immediately available into the event buffer,
+    /// applying the queue and link policies.
+    ///
+    /// Does nothing if neither a queue policy nor the latest-wins link policy is set.
+    fn buffer_available_events(&mut self) {
+        if self.queue_policy.is_none() && !self.latest_wins {
+            return;
+        }
+        while let Some(event) = self.events.try_recv() {
+            self.buffer_event(event);
+        }
+    }
+
+    fn buffer_event(&mut self, event: Event) {
+        // acknowledgements only update the flow control windows, so they are not
+        // buffered and can neither be dropped nor take the place of inputs
+        if self.handle_ack(&event) {
+            return;
+        }
+        // only inputs are dropped, all other events (e.g. `Stop` and `InputClosed`)
+        // are always kept
+        if let Event::Input { id, .. } = &event {
+            if self.latest_wins {
+                let superseded = self
+                    .buffered_events
+                    .iter()
+                    .position(|e| matches!(e, Event::Input { id: other, .. } if other == id));
+                if let Some(index) = superseded {
+                    self.buffered_events.remove(index);
+                    self.dropped_events += 1;
+                }
+            }
+        }
+        if let (Event::Input { .. }, Some(policy)) = (&event, self.queue_policy) {
+            let buffered_inputs = self
+                .buffered_events
+                .iter()
+                .filter(|e| matches!(e, Event::Input { .. }))
+                .count();
+            if buffered_inputs >= policy.max_events {
+                self.dropped_events += 1;
+                if !policy.drop_oldest {
+                    return;
+                }
+                let oldest = self
+                    .buffered_events
+                    .iter()
+                    .position(|e| matches!(e, Event::Input { .. }));
+                if let Some(index) = oldest {
+                    self.buffered_events.remove(index);
+                }
+            }
+        }
+        self.buffered_events.push_back(event);
+    }
+
+    /// Drains all available events and returns the latest data of each input.
+    ///
+    /// Waits up to `timeout` for an input if none is available yet. Events that are
+    /// not inputs are kept in the event buffer, acknowledgements are processed.
+    ///
+    /// While inputs are paused, no inputs are returned. Instead, the events that
+    /// arrive within `timeout` are buffered until inputs are resumed.
+    fn collect_latest(&mut self, timeout: Duration) -> HashMap<DataId, ArrayRef> {
+        if self.paused {
+            if let Some(event) = self.events.recv_timeout(timeout) {
+                self.buffer_event(event);
+            }
+            while let Some(event) = self.events.try_recv() {
+                self.buffer_event(event);
+            }
+            return HashMap::new();
+        }
+        let mut pending = std::mem::take(&mut self.buffered_events);
+        if !pending.iter().any(|e| matches!(e, Event::Input { .. })) {
+            if let Some(event) = self.events.recv_timeout(timeout) {
+                pending.push_back(event);
+            }
+        }
+        while let Some(event) = self.events.try_recv() {
+            pending.push_back(event);
+        }
+
+        let mut latest = HashMap::new();
+        for event in pending {
+            if self.handle_ack(&event) {
+                continue;
+            }
+            match event {
+                Event::Input { id, data, .. } => {
+                    latest.insert(id, data.0.clone());
+                }
+                other => self.buffered_events.push_back(other),
+            }
+        }
+        latest
+    }
+
+    /// Returns the stop token of this context, installing it first if necessary.
+    ///
+    /// Installing the stop token moves the event stream to a background thread,
+    /// which sets the token when it receives a `Stop` event and forwards all events
+    /// to this context.
+    fn stop_token(&mut self) -> Arc<AtomicBool> {
+        if let Some(token) = &self.stop_token {
+            return token.clone();
+        }
+        let token = Arc::new(AtomicBool::new(false));
+        self.forward_events(token.clone());
+        self.stop_token = Some(token.clone());
+        token
+    }
+
+    /// Moves the event stream to a background thread that sets the given token
+    /// when a `Stop` event is received.
+    fn forward_events(&mut self, token: Arc<AtomicBool>) {
+        if matches!(self.events.source, EventSource::Stream(_)) {
+            let (tx, rx) = mpsc::channel();
+            if let EventSource::Stream(mut stream) =
+                std::mem::replace(&mut self.events.source, EventSource::Forwarded(rx))
+            {
+                let stop = Arc::new(AtomicBool::new(false));
+                let stop_flag = stop.clone();
+                let thread = std::thread::spawn(move || {
+                    while !stop_flag.load(Ordering::Acquire) {
+                        let event = match stream.try_recv_timeout(FORWARD_POLL_INTERVAL) {
+                            Ok(event) => event,
+                            Err(RecvTimeoutError::Timeout) => continue,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        };
+                        if matches!(event, Event::Stop) {
+                            token.store(true, Ordering::Release);
+                        }
+                        if tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                });
+                self.forwarder = Some(Forwarder {
+                    stop,
+                    thread: Some(thread),
+                });
+            }
+        }
+    }
+
+    /// Sets the scheduling priority of the runtime threads, see
+    /// [`dora_set_realtime_priority`].
+    fn set_realtime_priority(&mut self, priority: i32) -> eyre::Result<()> {
+        let NodeHandle::Dora(node) = &self.node else {
+            return Ok(());
+        };
+        let EventSource::Stream(stream) = &self.events.source else {
+            eyre::bail!(
+                "cannot set priority after the event stream was moved to a separate thread"
+            );
+        };
+        stream
+            .set_realtime_priority(priority)
+            .wrap_err("failed to set priority of event stream thread")?;
+        node.set_realtime_priority(priority)
+    }
+
+    /// Replaces the daemon connection by a new one, see [`dora_reconnect`].
+    fn reconnect(&mut self) -> eyre::Result<()> {
+        if !matches!(self.node, NodeHandle::Dora(_)) {
+            eyre::bail!("standalone contexts cannot reconnect");
+        }
+        let (node, events) =
+            DoraNode::reconnect_from_env().wrap_err("failed to reconnect to dora daemon")?;
+        // the old event stream might still reference the old node, so drop it first,
+        // including the forwarding thread that owns it
+        self.forwarder = None;
+        self.events.source = EventSource::Stream(events);
+        self.node = NodeHandle::Dora(Box::new(node));
+        self.sequence_numbers.clear();
+        if let Some(token) = self.stop_token.clone() {
+            self.forward_events(token);
+        }
+        for (output_id, window) in &mut self.windows {
+            window.acked = 0;
+            window.sent_before_reconnect =
+                self.output_stats.get(output_id).map_or(0, |s| s.messages);
+        }
+        if let Some(auto_reconnect) = &mut self.auto_reconnect {
+            auto_reconnect.connected = true;
+            self.flush_buffered_outputs();
+        }
+        Ok(())
+    }
+
+    /// Reconnects after the event stream was closed without a `Stop` event, if
+    /// automatic reconnection is enabled.
+    ///
+    /// Retries until [`RECONNECT_TIMEOUT`] has passed. Returns whether the
+    /// connection was re-established.
+    fn reconnect_after_outage(&mut self) -> bool {
+        if self.auto_reconnect.is_none() {
+            return false;
+        }
+        // standalone contexts never reconnect, so retrying is pointless
+        let retry = matches!(self.node, NodeHandle::Dora(_));
+        let start = Instant::now();
+        loop {
+            match self.reconnect() {
+                Ok(()) => return true,
+                Err(err) if !retry || start.elapsed() >= RECONNECT_TIMEOUT => {
+                    tracing::warn!("giving up to reconnect to dora daemon: {err:?}");
+                    if let Some(auto_reconnect) = &mut self.auto_reconnect {
+                        auto_reconnect.connected = false;
+                    }
+                    return false;
+                }
+                Err(_) => std::thread::sleep(RECONNECT_INTERVAL),
+            }
+        }
+    }
+
+    /// Sends the outputs that were buffered while the daemon connection was down.
+    ///
+    /// Stops at the first failed send, which marks the connection as down again.
+    fn flush_buffered_outputs(&mut self) {
+        while let Some(output) = self
+            .auto_reconnect
+            .as_mut()
+            .and_then(|auto_reconnect| auto_reconnect.buffered.pop_front())
+        {
+            let (output_id, parameters, data, timestamp) = output.clone();
+            if let Err(err) = self.send_array_now(output_id, parameters, data, timestamp) {
+                tracing::warn!("failed to send buffered output: {err:?}");
+                if let Some(auto_reconnect) = &mut self.auto_reconnect {
+                    auto_reconnect.buffered.push_front(output);
+                    auto_reconnect.connected = false;
+                }
+                return;
+            }
+            if let Some(auto_reconnect) = &mut self.auto_reconnect {
+                auto_reconnect.buffered_bytes -= array_size(&output.2);
+            }
+        }
+    }
+
+    /// Sends the given array, or buffers it while the daemon connection is down if
+    /// automatic reconnection is enabled.
+    fn send_array(
+        &mut self,
+        output_id: DataId,
+        mut parameters: MetadataParameters,
+        data: ArrayRef,
+        timestamp: uhlc::Timestamp,
+    ) -> eyre::Result<()> {
+        if let Some(session_id) = &self.session_id {
+            parameters.custom.insert(
+                SESSION_PARAMETER.to_owned(),
+                Parameter::String(session_id.clone()),
+            );
+        }
+        let Some(auto_reconnect) = &self.auto_reconnect else {
+            return self.send_array_now(output_id, parameters, data, timestamp);
+        };
+        if auto_reconnect.connected {
+            let result = self.send_array_now(
+                output_id.clone(),
+                parameters.clone(),
+                data.clone(),
+                timestamp,
+            );
+            match result {
+                // errors of unknown outputs are not caused by the connection
+                Err(err) if self.node.has_output(&output_id) => {
+                    tracing::warn!("lost connection to dora daemon: {err:?}");
+                    self.try_reconnect();
+                }
+                other => return other,
+            }
+        } else if auto_reconnect
+            .last_attempt
+            .map_or(true, |last| last.elapsed() >= RECONNECT_INTERVAL)
+        {
+            self.try_reconnect();
+        }
+        match &mut self.auto_reconnect {
+            Some(auto_reconnect) if !auto_reconnect.connected => {
+                auto_reconnect.buffer(output_id, parameters, data, timestamp)
+            }
+            _ => self.send_array_now(output_id, parameters, data, timestamp),
+        }
+    }
+
+    /// Tries to reconnect to the daemon once and updates the connection state of the
+    /// automatic reconnection accordingly.
+    fn try_reconnect(&mut self) {
+        if let Some(auto_reconnect) = &mut self.auto_reconnect {
+            auto_reconnect.connected = false;
+            auto_reconnect.last_attempt = Some(Instant::now());
+        }
+        if let Err(err) = self.reconnect() {
+            tracing::debug!("failed to reconnect to dora daemon: {err:?}");
+        }
+    }
+
+    /// Sends the given array on the given output and updates the output statistics.
+    ///
+    /// Stamps the message with the next sequence number of the output, which is only
+    /// advanced if the send succeeds.
+    fn send_array_now(
+        &mut self,
+        output_id: DataId,
+        mut parameters: MetadataParameters,
+        data: ArrayRef,
+        timestamp: uhlc::Timestamp,
+    ) -> eyre::Result<()> {
+        let bytes = array_size(&data);
+        let sequence = self.sequence_numbers.get(&output_id).copied().unwrap_or(0);
+        parameters
+            .custom
+            .entry(SEQUENCE_PARAMETER.to_owned())
+            .or_insert(Parameter::Integer(sequence as i64));
+        self.node
+            .send_output_with_timestamp(output_id.clone(), parameters, data, timestamp)?;
+
+        self.sequence_numbers
+            .insert(output_id.clone(), sequence + 1);
+        let stats = self.output_stats.entry(output_id).or_default();
+        stats.messages += 1;
+        stats.bytes += bytes as u64;
+        Ok(())
+    }
+}
+
+/// The events of a [`DoraContext`], shared with the readers created through
+/// [`dora_clone_event_reader`].
+struct Events {
+    source: EventSource,
+    /// Events that were received by a cloned reader, but not by the context yet.
+    pending: VecDeque<Event>,
+    /// Event queues of the cloned readers, `None` if the reader was freed.
+    readers: Vec<Option<VecDeque<Event>>>,
+    /// Lifecycle events that were not returned yet, see
+    /// [`dora_next_lifecycle_event`].
+    lifecycle: VecDeque<DoraLifecycleEvent>,
+}
+
+impl Events {
+    fn new(source: EventSource) -> Self {
+        Self {
+            source,
+            pending: VecDeque::new(),
+            readers: Vec::new(),
+            // the node API only returns after all nodes of the dataflow were started
+            lifecycle: VecDeque::from([DoraLifecycleEvent::DataflowStarted]),
+        }
+    }
+
+    fn recv(&mut self) -> Option<Event> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        let event = self.source.recv()?;
+        self.observe(&event);
+        self.tee(&event, None);
+        Some(event)
+    }
+
+    fn try_recv(&mut self) -> Option<Event> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        let event = self.source.try_recv()?;
+        self.observe(&event);
+        self.tee(&event, None);
+        Some(event)
+    }
+
+    fn recv_timeout(&mut self, timeout: Duration) -> Option<Event> {
+        self.try_recv_timeout(timeout).ok()
+    }
+
+    fn try_recv_timeout(&mut self, timeout: Duration) -> Result<Event, RecvTimeoutError> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(event);
+        }
+        let event = self.source.try_recv_timeout(timeout)?;
+        self.observe(&event);
+        self.tee(&event, None);
+        Ok(event)
+    }
+
+    /// Receives the next event for the cloned reader with the given index.
+    fn recv_for_reader(&mut self, index: usize) -> Option<Event> {
+        if let Some(event) = self.readers.get_mut(index)?.as_mut()?.pop_front() {
+            return Some(event);
+        }
+        let event = self.source.recv()?;
+        self.observe(&event);
+        self.pending.push_back(clone_event(&event));
+        self.tee(&event, Some(index));
+        Some(event)
+    }
+
+    /// Records the lifecycle event that the given received event represents, if any.
+    fn observe(&mut self, event: &Event) {
+        let lifecycle = match event {
+            Event::Stop => DoraLifecycleEvent::DataflowStopping,
+            Event::Reload { .. } => DoraLifecycleEvent::Reload,
+            _ => return,
+        };
+        self.lifecycle.push_back(lifecycle);
+    }
+
+    /// Pushes a copy of the given event to the queues of all cloned readers,
+    /// except for the reader with the given index.
+    fn tee(&mut self, event: &Event, except: Option<usize>) {
+        for (i, queue) in self.readers.iter_mut().enumerate() {
+            if let Some(queue) = queue.as_mut().filter(|_| Some(i) != except) {
+                queue.push_back(clone_event(event));
+            }
+        }
+    }
+}
+
+/// Copies the given event. The data of inputs is shared, not copied.
+fn clone_event(event: &Event) -> Event {
+    match event {
+        Event::Stop => Event::Stop,
+        Event::Reload { operator_id } => Event::Reload {
+            operator_id: operator_id.clone(),
+        },
+        Event::Input { id, metadata, data } => Event::Input {
+            id: id.clone(),
+            metadata: metadata.clone(),
+            data: ArrowData(data.0.clone()),
+        },
+        Event::InputClosed { id } => Event::InputClosed { id: id.clone() },
+        Event::Error(err) => Event::Error(err.clone()),
+        other => Event::Error(format!("unsupported event {other:?}")),
+    }
+}
+
+/// The source of the events of a [`DoraContext`].
+enum EventSource {
+    /// Events are received directly from the event stream.
+    Stream(EventStream),
+    /// Events are forwarded by a background thread, see [`DoraContext::stop_token`].
+    Forwarded(mpsc::Receiver<Event>),
+}
+
+impl EventSource {
+    fn recv(&mut self) -> Option<Event> {
+        match self {
+            EventSource::Stream(stream) => stream.recv(),
+            EventSource::Forwarded(receiver) => receiver.recv().ok(),
+        }
+    }
+
+    fn try_recv(&mut self) -> Option<Event> {
+        match self {
+            EventSource::Stream(stream) => stream.try_recv().ok(),
+            EventSource::Forwarded(receiver) => receiver.try_recv().ok(),
+        }
+    }
+
+    fn try_recv_timeout(&mut self, timeout: Duration) -> Result<Event, RecvTimeoutError> {
+        match self {
+            EventSource::Stream(stream) => stream.try_recv_timeout(timeout),
+            EventSource::Forwarded(receiver) => {
+                receiver.recv_timeout(timeout).map_err(|err| match err {
+                    mpsc::RecvTimeoutError::Timeout => RecvTimeoutError::Timeout,
+                    mpsc::RecvTimeoutError::Disconnected => RecvTimeoutError::Disconnected,
+                })
+            }
+        }
+    }
+}
+
+/// Interval in which the event forwarding thread checks whether it should stop.
+const FORWARD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The thread that forwards the events of the event stream when a stop token is
+/// installed, see [`DoraContext::stop_token`].
+///
+/// Dropping it stops the thread and waits until the thread dropped the event stream.
+struct Forwarder {
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drop for Forwarder {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                tracing::error!("event forwarding thread panicked");
+            }
+        }
+    }
+}
+
+/// The node that a [`DoraContext`] sends its outputs through.
+enum NodeHandle {
+    /// A node connected to a dora daemon.
+    Dora(Box<DoraNode>),
+    /// A node without daemon connection, see [`init_dora_context_standalone`].
+    Standalone(StandaloneNode),
+}
+
+/// A node that runs without dora daemon, e.g. for testing.
+struct StandaloneNode {
+    id: NodeId,
+    inputs: BTreeSet<DataId>,
+    outputs: BTreeSet<DataId>,
+    types: BTreeMap<DataId, String>,
+    config: BTreeMap<String, serde_yaml::Value>,
+    clock: uhlc::HLC,
+    /// Feeds the event source of the context, `None` after
+    /// [`dora_standalone_finish`].
+    events: Option<mpsc::Sender<Event>>,
+}
+
+/// Configuration of a standalone node, see [`init_dora_context_standalone`].
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StandaloneConfig {
+    node_id: NodeId,
+    #[serde(default)]
+    inputs: BTreeSet<DataId>,
+    #[serde(default)]
+    outputs: BTreeSet<DataId>,
+    #[serde(default)]
+    types: BTreeMap<DataId, String>,
+    #[serde(default)]
+    config: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl NodeHandle {
+    fn has_input(&self, input_id: &DataId) -> bool {
+        match self {
+            NodeHandle::Dora(node) => node.node_config().inputs.contains_key(input_id),
+            NodeHandle::Standalone(node) => node.inputs.contains(input_id),
+        }
+    }
+
+    fn has_output(&self, output_id: &DataId) -> bool {
+        match self {
+            NodeHandle::Dora(node) => node.node_config().outputs.contains(output_id),
+            NodeHandle::Standalone(node) => node.outputs.contains(output_id),
+        }
+    }
+
+    fn declared_type(&self, id: &DataId) -> Option<&str> {
+        let types = match self {
+            NodeHandle::Dora(node) => &node.node_config().types,
+            NodeHandle::Standalone(node) => &node.types,
+        };
+        types.get(id).map(String::as_str)
+    }
+
+    /// Returns the custom configuration values from the dataflow YAML as strings.
+    fn config(&self) -> BTreeMap<String, String> {
+        let config = match self {
+            NodeHandle::Dora(node) => &node.node_config().config,
+            NodeHandle::Standalone(node) => &node.config,
+        };
+        config
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_yaml::Value::Null => String::new(),
+                    serde_yaml::Value::Bool(value) => value.to_string(),
+                    serde_yaml::Value::Number(value) => value.to_string(),
+                    serde_yaml::Value::String(value) => value.clone(),
+                    other => serde_yaml::to_string(other)
+                        .unwrap_or_default()
+                        .trim_end()
+                        .to_owned(),
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    fn clock(&self) -> &uhlc::HLC {
+        match self {
+            NodeHandle::Dora(node) => node.clock(),
+            NodeHandle::Standalone(node) => &node.clock,
+        }
+    }
+
+    fn id(&self) -> &NodeId {
+        match self {
+            NodeHandle::Dora(node) => node.id(),
+            NodeHandle::Standalone(node) => &node.id,
+        }
+    }
+
+    fn machine_id(&self) -> &str {
+        match self {
+            NodeHandle::Dora(node) => node.machine_id(),
+            NodeHandle::Standalone(_) => "",
+        }
+    }
+
+    /// Returns the number of nodes that have at least one input mapped to the given
+    /// output, according to the dataflow descriptor.
+    fn subscriber_count(&self, output_id: &DataId) -> usize {
+        let nodes: BTreeSet<_> = self
+            .subscribed_inputs(output_id)
+            .into_iter()
+            .map(|(node_id, _)| node_id)
+            .collect();
+        nodes.len()
+    }
+
+    /// Returns the smallest queue size of the inputs that are mapped to the given
+    /// output, according to the dataflow descriptor.
+    ///
+    /// Returns `None` if no input is mapped to the output.
+    fn output_queue_capacity(&self, output_id: &DataId) -> Option<usize> {
+        self.subscribed_inputs(output_id)
+            .into_iter()
+            // the daemon uses a queue size of 10 for inputs without `queue_size`
+            .map(|(_, input)| input.queue_size.unwrap_or(10))
+            .min()
+    }
+
+    /// Returns all inputs of the dataflow that are mapped to the given output,
+    /// together with the ID of the node that they belong to.
+    fn subscribed_inputs(&self, output_id: &DataId) -> Vec<(&NodeId, &Input)> {
+        let NodeHandle::Dora(node) = self else {
+            return Vec::new();
+        };
+        let is_subscribed = |input: &&Input| match &input.mapping {
+            InputMapping::User(mapping) => {
+                &mapping.source == node.id() && &mapping.output == output_id
+            }
+            InputMapping::Timer { .. } => false,
+        };
+        let mut inputs = Vec::new();
+        for n in &node.dataflow_descriptor().nodes {
+            let input_maps: Vec<_> = match &n.kind {
+                NodeKind::Runtime(runtime) => runtime
+                    .operators
+                    .iter()
+                    .map(|op| &op.config.inputs)
+                    .collect(),
+                NodeKind::Custom(custom) => vec![&custom.run_config.inputs],
+                NodeKind::Operator(op) => vec![&op.config.inputs],
+            };
+            for input_map in input_maps {
+                inputs.extend(input_map.values().filter(is_subscribed).map(|i| (&n.id, i)));
+            }
+        }
+        inputs
+    }
+
+    /// Describes the nodes of the dataflow and the connections between them.
+    fn topology(&self) -> eyre::Result<Topology> {
+        let NodeHandle::Dora(node) = self else {
+            eyre::bail!("standalone nodes are not part of a dataflow");
+        };
+        let mut topology = Topology::default();
+        for n in node
+            .dataflow_descriptor()
+            .resolve_aliases_and_set_defaults()
+        {
+            // inputs and outputs of operators are prefixed with the operator ID
+            let (inputs, outputs): (Vec<_>, Vec<_>) = match &n.kind {
+                CoreNodeKind::Custom(custom) => (
+                    custom
+                        .run_config
+                        .inputs
+                        .iter()
+                        .map(|(id, input)| (id.to_string(), input))
+                        .collect(),
+                    custom
+                        .run_config
+                        .outputs
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                ),
+                CoreNodeKind::Runtime(runtime) => (
+                    runtime
+                        .operators
+                        .iter()
+                        .flat_map(|op| {
+                            op.config
+                                .inputs
+                                .iter()
+                                .map(move |(id, input)| (format!("{}/{id}", op.id), input))
+                        })
+                        .collect(),
+                    runtime
+                        .operators
+                        .iter()
+                        .flat_map(|op| {
+                            op.config
+                                .outputs
+                                .iter()
+                                .map(move |id| format!("{}/{id}", op.id))
+                        })
+                        .collect(),
+                ),
+            };
+            for (input_id, input) in &inputs {
+                let source = input.mapping.source().to_string();
+                let mapping = input.mapping.to_string();
+                topology.edges.push(TopologyEdge {
+                    output: mapping
+                        .strip_prefix(&format!("{source}/"))
+                        .unwrap_or(&mapping)
+                        .to_owned(),
+                    source,
+                    target: n.id.to_string(),
+                    input: input_id.clone(),
+                });
+            }
+            topology.nodes.push(TopologyNode {
+                id: n.id.to_string(),
+                inputs: inputs.into_iter().map(|(id, _)| id).collect(),
+                outputs,
+            });
+        }
+        Ok(topology)
+    }
+
+    fn reserve_shared_memory(&mut self, data_len: usize) -> eyre::Result<()> {
+        match self {
+            NodeHandle::Dora(node) => node.reserve_shared_memory(data_len),
+            NodeHandle::Standalone(_) => Ok(()),
+        }
+    }
+
+    fn send_output_with_timestamp(
+        &mut self,
+        output_id: DataId,
+        parameters: MetadataParameters,
+        data: ArrayRef,
+        timestamp: uhlc::Timestamp,
+    ) -> eyre::Result<()> {
+        match self {
+            NodeHandle::Dora(node) => {
+                node.send_output_with_timestamp(output_id, parameters, data, timestamp)
+            }
+            NodeHandle::Standalone(node) => {
+                if !node.outputs.contains(&output_id) {
+                    eyre::bail!("unknown output");
+                }
+                tracing::debug!("standalone node sent {} values on {output_id}", data.len());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A second reader of the events of a [`DoraContext`], see
+/// [`dora_clone_event_reader`].
+struct DoraEventReader {
+    context: *mut DoraContext,
+    index: usize,
+}
+
+/// A reader with bounded lookahead over the events of a [`DoraContext`], see
+/// [`dora_window_reader_new`].
+struct DoraWindowReader {
+    context: *mut DoraContext,
+    window_size: usize,
+    /// The current event, followed by the received lookahead events.
+    window: VecDeque<Box<DoraEvent>>,
+}
+
+/// An output array that is built incrementally, see
+/// [`dora_output_accumulator_new`].
+struct DoraOutputAccumulator {
+    context: *mut DoraContext,
+    output_id: DataId,
+    builder: Float32Builder,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct QueuePolicy {
+    max_events: usize,
+    drop_oldest: bool,
+}
+
+/// Description of a dataflow graph, see [`dora_get_topology`].
+#[derive(Debug, Default, serde::Serialize)]
+struct Topology {
+    nodes: Vec<TopologyNode>,
+    edges: Vec<TopologyEdge>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct TopologyNode {
+    id: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+/// Connection from the output of a node to the input of another node.
+#[derive(Debug, serde::Serialize)]
+struct TopologyEdge {
+    source: String,
+    output: String,
+    target: String,
+    input: String,
+}
+
+#[derive(Debug)]
+struct InputHistory {
+    capacity: usize,
+    /// The recorded input data, oldest first.
+    entries: VecDeque<ArrayRef>,
+}
+
+impl InputHistory {
+    fn record(&mut self, data: ArrayRef) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(data);
+    }
+}
+
+/// Exports the metrics emitted through [`dora_emit_metric`] through OpenTelemetry.
+#[cfg(feature = "metrics")]
+struct NodeMetrics {
+    /// The exporter runs on this runtime since C nodes have no async runtime.
+    _runtime: tokio::runtime::Runtime,
+    _provider: opentelemetry_sdk::metrics::SdkMeterProvider,
+    _gauge: opentelemetry::metrics::ObservableGauge<f64>,
+    /// Latest value of each metric, read by the exporter on every export.
+    values: Arc<Mutex<BTreeMap<String, f64>>>,
+}
+
+#[cfg(feature = "metrics")]
+impl NodeMetrics {
+    fn new(node_id: &NodeId) -> eyre::Result<Self> {
+        use opentelemetry::{metrics::MeterProvider, KeyValue};
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .wrap_err("failed to create metrics runtime")?;
+        let provider = {
+            let _guard = runtime.enter();
+            dora_metrics::init_metrics().wrap_err("failed to create metrics exporter")?
+        };
+        let values = Arc::new(Mutex::new(BTreeMap::<String, f64>::new()));
+        let observed = values.clone();
+        let gauge = provider
+            .meter(node_id.to_string())
+            .f64_observable_gauge("dora.node.metric")
+            .with_description("Metrics emitted by the node through `dora_emit_metric`")
+            .with_callback(move |observer| {
+                let values = observed.lock().unwrap_or_else(|err| err.into_inner());
+                for (name, value) in values.iter() {
+                    observer.observe(*value, &[KeyValue::new("name", name.clone())]);
+                }
+            })
+            .init();
+        Ok(Self {
+            _runtime: runtime,
+            _provider: provider,
+            _gauge: gauge,
+            values,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct RateLimit {
+    min_interval: Duration,
+    last_sent: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct FlowWindow {
+    /// The input on which the acknowledgements for the output are received.
+    ack_input: DataId,
+    /// Maximum number of unacknowledged messages.
+    size: u64,
+    /// Number of acknowledged messages, i.e. the highest acknowledged sequence
+    /// number plus one.
+    acked: u64,
+    /// Number of messages sent before the last [`dora_reconnect`] call.
+    ///
+    /// Sequence numbers restart at zero after reconnecting, so these messages are
+    /// not counted towards the window anymore.
+    sent_before_reconnect: u64,
+}
+
+/// Interval between reconnection attempts, see [`dora_enable_auto_reconnect`].
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(500);
+/// Time after which [`dora_next_event`] stops trying to reconnect.
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// State of the automatic reconnection, see [`dora_enable_auto_reconnect`].
+#[derive(Debug)]
+struct AutoReconnect {
+    /// Whether outputs are buffered while the connection is down.
+    buffer_outputs: bool,
+    /// Maximum total size of the buffered outputs.
+    max_buffer_bytes: usize,
+    buffered: VecDeque<(DataId, MetadataParameters, ArrayRef, uhlc::Timestamp)>,
+    /// Total size of the `buffered` outputs.
+    buffered_bytes: usize,
+    connected: bool,
+    /// Time of the last reconnection attempt of a send.
+    last_attempt: Option<Instant>,
+}
+
+impl AutoReconnect {
+    /// Buffers the given output until the connection is re-established.
+    fn buffer(
+        &mut self,
+        output_id: DataId,
+        parameters: MetadataParameters,
+        data: ArrayRef,
+        timestamp: uhlc::Timestamp,
+    ) -> eyre::Result<()> {
+        if !self.buffer_outputs {
+            eyre::bail!("not connected to dora daemon, dropping output {output_id}");
+        }
+        let bytes = array_size(&data);
+        if self.buffered_bytes + bytes > self.max_buffer_bytes {
+            eyre::bail!(
+                "not connected to dora daemon and output buffer is full ({} of {} bytes \
+                used), dropping output {output_id}",
+                self.buffered_bytes,
+                self.max_buffer_bytes
+            );
+        }
+        self.buffered
+            .push_back((output_id, parameters, data, timestamp));
+        self.buffered_bytes += bytes;
+        Ok(())
+    }
+}
+
+/// Returns the size of the buffers of the given array in bytes.
+fn array_size(data: &ArrayRef) -> usize {
+    data.to_data().buffers().iter().map(|b| b.len()).sum()
+}
+
+/// Error returned when a send is rejected because its [`FlowWindow`] is full.
+#[derive(Debug)]
+struct WindowFull(DataId);
+
+impl std::fmt::Display for WindowFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "too many unacknowledged messages on output {}", self.0)
+    }
+}
+
+impl std::error::Error for WindowFull {}
+
+/// Error returned when a send is dropped because of a [`RateLimit`].
+#[derive(Debug)]
+struct RateLimited(DataId);
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "output {} was dropped because of its rate limit", self.0)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Error returned when a send is skipped because the output is not active, see
+/// [`dora_set_active_outputs`].
+#[derive(Debug)]
+struct OutputDisabled(DataId);
+
+impl std::fmt::Display for OutputDisabled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "output {} is not active", self.0)
+    }
+}
+
+impl std::error::Error for OutputDisabled {}
+
+/// Error returned when no reply to a request arrives in time, see
+/// [`dora_send_output_f32_sync`] and [`dora_measure_rtt`].
+#[derive(Debug)]
+struct ReplyTimeout(DataId);
+
+impl std::fmt::Display for ReplyTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no reply to request on output {} in time", self.0)
+    }
+}
+
+impl std::error::Error for ReplyTimeout {}
+
+/// Error returned when a barrier is not released in time, see [`dora_barrier`].
+#[derive(Debug)]
+struct BarrierTimeout(String);
+
+impl std::fmt::Display for BarrierTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "barrier `{}` was not released in time", self.0)
+    }
+}
+
+impl std::error::Error for BarrierTimeout {}
+
+#[derive(Debug, Default)]
+struct OutputStats {
+    messages: u64,
+    bytes: u64,
+}
+
+/// Running statistics of the times between consecutive inputs of an input ID.
+#[derive(Debug, Default)]
+struct ArrivalStats {
+    last_arrival: Option<Instant>,
+    /// Number of measured intervals.
+    count: u64,
+    /// Mean interval in nanoseconds.
+    mean: f64,
+    /// Sum of the squared deviations from the mean, see Welford's algorithm.
+    m2: f64,
+    max: Duration,
+}
+
+impl ArrivalStats {
+    fn record(&mut self, now: Instant) {
+        if let Some(last) = self.last_arrival.replace(now) {
+            let interval = now.duration_since(last);
+            let nanos = interval.as_nanos() as f64;
+            self.count += 1;
+            let delta = nanos - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (nanos - self.mean);
+            self.max = self.max.max(interval);
+        }
+    }
+
+    fn std_dev(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64).sqrt()
+        }
+    }
+}
+
+/// An event handed out through [`dora_next_event`], together with additional
+/// bookkeeping data.
+struct DoraEvent {
+    event: Event,
+    /// Time at which the event was handed out, see [`read_dora_input_ingress_nanos`].
+    ingress: uhlc::Timestamp,
+    /// Whether this is the first input with this ID since the start of the node
+    /// or since the last [`dora_reset_input_first`] call.
+    is_first: bool,
+    /// Whether the data type differs from the previous input with this ID.
+    type_changed: bool,
+    /// The decompressed input data, see [`COMPRESSION_PARAMETER`].
+    decompressed: OnceCell<Vec<u8>>,
+    /// The formatted data type of the input, see [`read_dora_input_data_type_name`].
+    type_name: OnceCell<String>,
+}
+
+/// A snapshot of the latest data of each input, see [`dora_collect_latest`].
+struct DoraSnapshot {
+    inputs: HashMap<DataId, ArrayRef>,
+}
+
+/// Data types of the arrays handed out by the dora API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoraDataType {
+    Null,
+    UInt8,
+    Int32,
+    Float32,
+    UInt64,
+    Float64,
+    /// A data type that is not supported by the C API.
+    Unknown,
+}
+
+impl From<&DataType> for DoraDataType {
+    fn from(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Null => DoraDataType::Null,
+            DataType::UInt8 => DoraDataType::UInt8,
+            DataType::Int32 => DoraDataType::Int32,
+            DataType::Float32 => DoraDataType::Float32,
+            DataType::UInt64 => DoraDataType::UInt64,
+            DataType::Float64 => DoraDataType::Float64,
+            _ => DoraDataType::Unknown,
+        }
+    }
+}
+
+impl DoraDataType {
+    /// Returns the size of a single value in bytes, or `None` for [`DoraDataType::Unknown`].
+    fn byte_width(self) -> Option<usize> {
+        match self {
+            DoraDataType::Null => Some(0),
+            DoraDataType::UInt8 => Some(1),
+            DoraDataType::Int32 | DoraDataType::Float32 => Some(4),
+            DoraDataType::UInt64 | DoraDataType::Float64 => Some(8),
+            DoraDataType::Unknown => None,
+        }
+    }
+
+    /// Parses a data type name as used in the `types` field of the dataflow YAML.
+    fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "null" => DoraDataType::Null,
+            "uint8" | "u8" => DoraDataType::UInt8,
+            "int32" | "i32" => DoraDataType::Int32,
+            "float32" | "f32" => DoraDataType::Float32,
+            "uint64" | "u64" => DoraDataType::UInt64,
+            "float64" | "f64" => DoraDataType::Float64,
+            _ => DoraDataType::Unknown,
+        }
+    }
+}
+
+/// Initializes a dora context from the environment variables that were set by
+/// the dora-coordinator.
+///
+/// Returns a pointer to the dora context on success. This pointer can be
+/// used to call dora API functions that expect a `context` argument. Any
+/// other use is prohibited. To free the dora context when it is no longer
+/// needed, use the [`free_dora_context`] function.
+///
+/// On error, a null pointer is returned.
+#[no_mangle]
+pub extern "C" fn init_dora_context_from_env() -> *mut c_void {
+    let context = || {
+        let (node, events) = DoraNode::init_from_env()?;
+        Result::<_, eyre::Report>::Ok(DoraContext::new(
+            NodeHandle::Dora(Box::new(node)),
+            Events::new(EventSource::Stream(events)),
+        ))
+    };
+    let context = match context().context("failed to initialize node") {
+        Ok(n) => n,
+        Err(err) => {
+            let err: eyre::Error = err;
+            tracing::error!("{err:?}");
+            return ptr::null_mut();
+        }
+    };
+
+    into_raw_context(context)
+}
+
+/// Initializes a dora context from the given JSON configuration, without a running
+/// dora coordinator or daemon.
+///
+/// This standalone mode allows testing the logic of C nodes without starting a
+/// dataflow. The configuration is a JSON object of the following form:
+///
+/// ```json
+/// { "node_id": "my-node", "inputs": ["tick", "image"], "outputs": ["result"] }
+/// ```
+///
+/// The optional `config` object sets the values read through [`dora_get_config`].
+///
+/// Inputs are injected through [`dora_standalone_push_input_f32`] and received
+/// through [`dora_next_event`] as usual. [`dora_standalone_finish`] sends a `Stop`
+/// event and closes the event stream afterwards.
+///
+/// Standalone mode has the following limitations:
+///
+/// - Outputs are not delivered anywhere. They are only counted, which can be checked
+///   through [`dora_output_stats`].
+/// - The machine ID is always empty.
+/// - Shared memory is never used, so [`dora_output_would_block`] always returns `0`.
+///
+/// Returns a pointer to the dora context on success, which must be freed through
+/// [`free_dora_context`]. On error, a null pointer is returned.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn init_dora_context_standalone(
+    config_json_ptr: *const u8,
+    config_json_len: usize,
+) -> *mut c_void {
+    let config = unsafe { slice::from_raw_parts(config_json_ptr, config_json_len) };
+    let config: StandaloneConfig = match serde_json::from_slice(config) {
+        Ok(config) => config,
+        Err(err) => {
+            tracing::error!("invalid standalone node config: {err}");
+            set_last_error(format!("invalid standalone node config: {err}"));
+            return ptr::null_mut();
+        }
+    };
+    tracing::info!("initializing node {} in standalone mode", config.node_id);
+
+    let (tx, rx) = mpsc::channel();
+    let node = StandaloneNode {
+        id: config.node_id,
+        inputs: config.inputs,
+        outputs: config.outputs,
+        types: config.types,
+        config: config.config,
+        clock: uhlc::HLC::default(),
+        events: Some(tx),
+    };
+    let context = DoraContext::new(
+        NodeHandle::Standalone(node),
+        Events::new(EventSource::Forwarded(rx)),
+    );
+    into_raw_context(context)
+}
+
+/// Injects a `Float32` input into the given standalone context.
+///
+/// The input is returned by a subsequent [`dora_next_event`] call.
+///
+/// Returns a [`DoraStatus`] code. Fails if the context was not created through
+/// [`init_dora_context_standalone`], if the input ID is not part of its
+/// configuration, or if [`dora_standalone_finish`] was called already.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_standalone_push_input_f32(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|input_id| {
+        let NodeHandle::Standalone(node) = &context.node else {
+            eyre::bail!("context is not a standalone context");
+        };
+        if !node.inputs.contains(&input_id) {
+            eyre::bail!("unknown input {input_id}");
+        }
+        let array = data.to_arrow();
+        let array_data = array.to_data();
+        let mut buffer = vec![0; required_data_size(&array_data)];
+        let type_info = copy_array_into_sample(&mut buffer, &array_data);
+        let event = Event::Input {
+            id: input_id,
+            metadata: Metadata::new(node.clock.new_timestamp(), type_info),
+            data: ArrowData(array),
+        };
+        node.events
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("standalone event stream was finished already"))?
+            .send(event)
+            .map_err(|_| eyre::eyre!("standalone event stream is closed"))
+    });
+    send_status(result)
+}
+
+/// Finishes the event stream of the given standalone context.
+///
+/// Sends a `Stop` event and closes the event stream afterwards, so that
+/// [`dora_next_event`] returns a null pointer once all events were received. Also
+/// sets the stop token of the context, if one was installed through
+/// [`dora_install_stop_token`].
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `context` argument
+/// must be a dora context created through [`init_dora_context_standalone`].
+#[no_mangle]
+pub unsafe extern "C" fn dora_standalone_finish(context: *mut c_void) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = match &mut context.node {
+        NodeHandle::Standalone(node) => {
+            if let Some(events) = node.events.take() {
+                let _ = events.send(Event::Stop);
+            }
+            if let Some(token) = &context.stop_token {
+                token.store(true, Ordering::Release);
+            }
+            Ok(())
+        }
+        NodeHandle::Dora(_) => Err(eyre::eyre!("context is not a standalone context")),
+    };
+    send_status(result)
+}
+
+/// Callback that is invoked before an output is sent, see
+/// [`dora_register_output_hook`].
+///
+/// The arguments are the UTF8-encoded output ID, the raw bytes of the output values,
+/// and the user-data pointer given on registration.
+pub type DoraOutputHook = unsafe extern "C" fn(
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const c_void,
+    data_len: usize,
+    user_data: *mut c_void,
+);
+
+/// Registers a callback that is invoked before each output of the node is sent.
+///
+/// This allows handling cross-cutting concerns, such as logging or auditing every
+/// output, in a single place instead of at every send site. The hook is invoked by
+/// all `dora_send_output_*` functions (and the other send functions, such as
+/// [`dora_send_detections`]) on the calling thread, right before the message is
+/// sent. Sends that are rejected by a rate limit or flow control window don't invoke
+/// the hook. Within a tick (see [`dora_begin_tick`]), the hook is invoked when the
+/// output is buffered, not when the tick is sent.
+///
+/// Multiple hooks can be registered. They are invoked in the order of registration.
+///
+/// The `data_ptr` and `data_len` arguments of the hook describe the raw bytes of
+/// the output values. For outputs that don't consist of fixed-width values (e.g.
+/// strings), a null pointer and length `0` are passed. The hook may only inspect the
+/// data: it must not modify or free it, and it must not keep the pointers after
+/// returning. The hook must not call any dora function on the same context.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `hook` must be safe
+/// to call with the described arguments and the given `user_data` for as long as
+/// the context exists.
+#[no_mangle]
+pub unsafe extern "C" fn dora_register_output_hook(
+    context: *mut c_void,
+    hook: DoraOutputHook,
+    user_data: *mut c_void,
+) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return;
+        }
+    };
+    context.output_hooks.push((hook, user_data));
+}
+
+/// Clock function that returns the current time in nanoseconds, see
+/// [`dora_set_clock_source`].
+pub type DoraClockSource = unsafe extern "C" fn() -> u64;
+
+/// Registers a clock that dora uses to timestamp the outputs of the node.
+///
+/// By default, outputs are timestamped through the hybrid logical clock (HLC) of
+/// the node, which is based on the system wall clock. Nodes that must align their
+/// data to an external clock, such as a PTP hardware clock, can register a function
+/// that returns the current time of that clock in nanoseconds since the Unix epoch.
+/// The function is called on the sending thread for every output, including the
+/// outputs of a tick. Passing a null pointer restores the default clock.
+///
+/// Timestamps of the registered clock bypass the logical clock of the node:
+///
+/// - The timestamps of the node's outputs are strictly increasing. If the clock
+///   returns a time that is not after the previous timestamp, the timestamp is
+///   advanced by the smallest possible step instead.
+/// - The timestamps are not ordered with respect to the timestamps of other nodes.
+///   Receivers that compare them to their own clock (e.g. through
+///   [`read_dora_input_age_nanos`]) only get meaningful results if both clocks are
+///   synchronized, as is the case with PTP.
+/// - The HLC of the node is not advanced by these timestamps. Functions that read
+///   the HLC, such as [`read_dora_input_age_nanos`], keep using the wall clock.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `now_fn` must be
+/// null or safe to call for as long as the context exists.
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_clock_source(
+    context: *mut c_void,
+    now_fn: Option<DoraClockSource>,
+) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return;
+        }
+    };
+    context.clock_source = now_fn;
+}
+
+/// Sets a session ID that is attached to the metadata of all following outputs.
+///
+/// The ID is stored as [`SESSION_PARAMETER`] string parameter, so that recorders
+/// and analysis tools can group the data of a run (e.g. an experiment) across
+/// nodes without specifying the ID on every send. It applies to all outputs of
+/// the node, including the outputs of a tick and the shutdown output. Passing an
+/// empty ID removes the session ID again.
+///
+/// Returns a [`DoraStatus`] code, [`DoraStatus::InvalidUtf8Id`] if the ID is not
+/// valid UTF-8.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_session_id(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let id = unsafe { slice::from_raw_parts(id_ptr, id_len) };
+    match std::str::from_utf8(id).wrap_err("session ID is not valid UTF-8") {
+        Ok(id) => {
+            context.session_id = Some(id.to_owned()).filter(|id| !id.is_empty());
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Callback that decodes a custom input encoding, see [`dora_register_input_codec`].
+///
+/// The arguments are the encoded input bytes and a buffer of `out_cap` bytes for the
+/// decoded bytes. Returns the length of the decoded data or a negative value on
+/// error.
+pub type DoraInputCodec = unsafe extern "C" fn(
+    in_ptr: *const u8,
+    in_len: usize,
+    out_ptr: *mut u8,
+    out_cap: usize,
+) -> isize;
+
+/// Registers a decoder for an input that uses an application-specific encoding.
+///
+/// The codec is run for every event of the given input before it is returned by
+/// [`dora_next_event`] (and by [`dora_drain_inputs`] and [`dora_window_get`]), so
+/// that [`read_dora_input_data_u8`] returns the decoded bytes. The rest of the node
+/// can thus work with decoded data uniformly. Registering a codec for an input that
+/// already has one replaces the previous codec. Events of readers created through
+/// [`dora_clone_event_reader`] are not decoded.
+///
+/// The codec is called with the encoded bytes of the input, which must be a `UInt8`
+/// array, and an output buffer that is owned by dora. The codec must write the
+/// decoded bytes to the output buffer and return their length. It must not keep
+/// any of the pointers after returning. The capacity is negotiated as follows:
+///
+/// - The first call passes a buffer of at least the input length (minimum 64 bytes).
+/// - If the decoded data doesn't fit, the codec returns the required length without
+///   writing anything. It is then called a second time with a buffer of exactly
+///   this length. If the data still doesn't fit, decoding fails.
+/// - A negative return value indicates a decoding error.
+///
+/// If decoding fails, the input is replaced by an `Error` event that names the
+/// input.
+///
+/// Returns a [`DoraStatus`] code. Fails if the ID is not valid UTF-8 or if the node
+/// has no such input.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `codec` must be
+/// safe to call with the described arguments for as long as the context exists.
+#[no_mangle]
+pub unsafe extern "C" fn dora_register_input_codec(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    codec: DoraInputCodec,
+) -> isize {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let input_id = unsafe { read_id(id_ptr, id_len) }?;
+        if !context.node.has_input(&input_id) {
+            eyre::bail!("unknown input {input_id}");
+        }
+        context.input_codecs.insert(input_id, codec);
+        Ok(())
+    });
+    send_status(result)
+}
+
+/// Decodes the given input data through the given codec, following the capacity
+/// negotiation described in [`dora_register_input_codec`].
+fn run_codec(codec: DoraInputCodec, data: &ArrayRef) -> eyre::Result<Vec<u8>> {
+    let input = data
+        .as_primitive_opt::<UInt8Type>()
+        .ok_or_else(|| eyre::eyre!("input has data type {}, expected UInt8", data.data_type()))?
+        .values();
+    let mut output = vec![0; input.len().max(64)];
+    for _ in 0..2 {
+        let result = unsafe {
+            codec(
+                input.as_ptr(),
+                input.len(),
+                output.as_mut_ptr(),
+                output.len(),
+            )
+        };
+        let len = usize::try_from(result)
+            .map_err(|_| eyre::eyre!("codec failed with error code {result}"))?;
+        if len <= output.len() {
+            output.truncate(len);
+            return Ok(output);
+        }
+        output.resize(len, 0);
+    }
+    eyre::bail!("decoded data does not fit into the requested buffer size")
+}
+
+/// Attaches an opaque user-data pointer to the given context.
+///
+/// This allows C code to associate its own application state with a dora context,
+/// without relying on global variables. The pointer can be read back through
+/// [`dora_get_user_data`]. It replaces any previously set pointer.
+///
+/// The pointer is never dereferenced or freed by dora. The caller is responsible
+/// for keeping the pointed-to data valid and for freeing it, e.g. after calling
+/// [`free_dora_context`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_user_data(context: *mut c_void, user_data: *mut c_void) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return;
+        }
+    };
+    context.user_data = user_data;
+}
+
+/// Returns the user-data pointer that was attached through [`dora_set_user_data`].
+///
+/// Returns a null pointer if no user data was set.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_get_user_data(context: *mut c_void) -> *mut c_void {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return ptr::null_mut();
+        }
+    };
+    context.user_data
+}
+
+/// Reads out the time since the given context was created, in nanoseconds.
+///
+/// The time is measured from the successful [`init_dora_context_from_env`] call,
+/// using a monotonic clock. It is not reset by [`dora_reconnect`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_node_uptime_nanos(context: *mut c_void, out_nanos: *mut u64) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return;
+        }
+    };
+    let uptime = context.started.elapsed();
+    unsafe { *out_nanos = uptime.as_nanos().try_into().unwrap_or(u64::MAX) };
+}
+
+/// Asks the dora daemon to rotate the log files of this node.
+///
+/// The daemon copies the current log output of the node into an archive file next
+/// to the original log file, with the current time as suffix (e.g.
+/// `out/<dataflow>/log_<node>.20240301T100000.123Z.txt`). The original log file is
+/// truncated afterwards, so `dora logs` only shows the output that is written after
+/// the rotation. The node keeps running and keeps writing to its stdout and stderr
+/// as before.
+///
+/// Returns `0` on success. Returns `-1` and sets the last error if the daemon failed
+/// to rotate the log files or if the context is a standalone context, which has no
+/// log files.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_rotate_log(context: *mut c_void) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = match &mut context.node {
+        NodeHandle::Dora(node) => node.rotate_log(),
+        NodeHandle::Standalone(_) => Err(eyre::eyre!("standalone contexts have no log files")),
+    };
+    send_status(result)
+}
+
+/// Blocks until `expected_count` nodes of the dataflow reached the barrier with the
+/// given name.
+///
+/// Can be used to synchronize phases across nodes, e.g. to wait until all nodes
+/// finished their initialization before the first input is processed. The calling
+/// node counts as one of the `expected_count` nodes. Each node counts only once, so
+/// `expected_count` must not be larger than the number of nodes that call this
+/// function with the same name. The barrier is coordinated through the dora
+/// coordinator, so nodes on different machines can wait on the same barrier. Once
+/// the barrier is released, the name can be reused for the next round.
+///
+/// Events that arrive while waiting are kept and returned by the following
+/// [`dora_next_event`] calls.
+///
+/// Returns [`DoraStatus::Timeout`] if the barrier is not released within
+/// `timeout_ms` milliseconds. In this case, the node no longer counts as waiting on
+/// the barrier. Returns [`DoraStatus::Error`] and sets the last error if the barrier
+/// request fails or if the context is a standalone context, which has no other
+/// nodes.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_barrier(
+    context: *mut c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    expected_count: usize,
+    timeout_ms: u64,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = std::str::from_utf8(unsafe { slice::from_raw_parts(name_ptr, name_len) })
+        .wrap_err("barrier name is not valid UTF-8")
+        .and_then(|name| match &mut context.node {
+            NodeHandle::Dora(node) => {
+                let timeout = Duration::from_millis(timeout_ms);
+                if node.barrier(name, expected_count, timeout)? {
+                    Ok(())
+                } else {
+                    Err(BarrierTimeout(name.to_owned()).into())
+                }
+            }
+            NodeHandle::Standalone(_) => {
+                Err(eyre::eyre!("standalone contexts cannot wait on barriers"))
+            }
+        });
+    send_status(result)
+}
+
+/// Runs the internal threads of the dora runtime with the given realtime priority.
+///
+/// This affects the threads that receive events and drop notifications from the
+/// dora daemon, not the calling thread. On Linux and other Unix platforms, the
+/// threads are switched to the `SCHED_FIFO` scheduling policy with the given
+/// priority (`1` to `99` on Linux), so that they are not preempted by normal
+/// threads. A `priority` of `0` switches back to the default `SCHED_OTHER` policy.
+///
+/// Realtime priorities require elevated privileges. On Linux, the process needs the
+/// `CAP_SYS_NICE` capability or an `RLIMIT_RTPRIO` limit of at least `priority`
+/// (e.g. set through `/etc/security/limits.conf`). Other platforms, such as
+/// Windows, are not supported.
+///
+/// Must be called before [`dora_install_stop_token`], which moves the event stream
+/// to a separate thread. The priority is not preserved by [`dora_reconnect`], so it
+/// needs to be set again after reconnecting. Standalone contexts have no runtime
+/// threads, so this function does nothing for them.
+///
+/// Returns `0` on success. Returns `-1` and sets the last error if the platform is
+/// not supported, the privileges are missing, or the priority is out of range.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_realtime_priority(context: *mut c_void, priority: i32) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    send_status(context.set_realtime_priority(priority))
+}
+
+/// Stores the given data as checkpoint with the given name.
+///
+/// Checkpoints allow nodes to persist their application state, so that they can
+/// resume through [`dora_load_checkpoint`] after a restart. The data is treated as
+/// opaque blob. It is written to `out/checkpoints/<node_id>/<name>`, relative to the
+/// working directory of the node, i.e. the directory of the dataflow file. Thus,
+/// checkpoints are kept across dataflow runs, separately for each node of the
+/// dataflow. Existing checkpoints with the same name are replaced atomically, so a
+/// crash while saving never leaves a partially written checkpoint behind.
+///
+/// The name must be a relative path without `..` components, e.g. `state.bin` or
+/// `model/weights`.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_save_checkpoint(
+    context: *mut c_void,
+    data_ptr: *const u8,
+    data_len: usize,
+    path_ptr: *const u8,
+    path_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { checkpoint_path(context, path_ptr, path_len) }.and_then(|path| {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        std::fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("failed to create checkpoint directory {dir:?}"))?;
+        // write to a temporary file first and rename it afterwards, which replaces the
+        // previous checkpoint atomically
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, data)
+            .wrap_err_with(|| format!("failed to write checkpoint {tmp_path:?}"))?;
+        std::fs::rename(&tmp_path, &path)
+            .wrap_err_with(|| format!("failed to replace checkpoint {path:?}"))
+    });
+    send_status(result)
+}
+
+/// Loads the checkpoint with the given name, see [`dora_save_checkpoint`].
+///
+/// Writes the start pointer and length of the checkpoint data to `out_ptr` and
+/// `out_len`. The data must be freed through [`free_dora_checkpoint`].
+///
+/// Returns `0` on success. Returns `1` and writes a null pointer and length `0` if
+/// no checkpoint with the given name exists, e.g. on the first start of the node.
+/// Returns a negative [`DoraStatus`] code on other errors.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_load_checkpoint(
+    context: *mut c_void,
+    path_ptr: *const u8,
+    path_len: usize,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> isize {
+    unsafe {
+        *out_ptr = ptr::null_mut();
+        *out_len = 0;
+    }
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let path = match unsafe { checkpoint_path(context, path_ptr, path_len) } {
+        Ok(path) => path,
+        Err(err) => return send_status(Err(err)),
+    };
+    match std::fs::read(&path) {
+        Ok(data) => {
+            let data = Box::leak(data.into_boxed_slice());
+            unsafe {
+                *out_ptr = data.as_mut_ptr();
+                *out_len = data.len();
+            }
+            0
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => 1,
+        Err(err) => {
+            let err =
+                eyre::Report::new(err).wrap_err(format!("failed to read checkpoint {path:?}"));
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Frees the checkpoint data returned by [`dora_load_checkpoint`].
+///
+/// ## Safety
+///
+/// The `ptr` and `len` arguments must be the values written by a successful
+/// [`dora_load_checkpoint`] call. The data must be freed exactly once.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_checkpoint(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        let _: Box<[u8]> = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)) };
+    }
+}
+
+/// Returns the path of the checkpoint with the given name.
+///
+/// ## Safety
+///
+/// The `path_ptr` and `path_len` fields must be the start pointer and length of an
+/// UTF8-encoded string.
+unsafe fn checkpoint_path(
+    context: &DoraContext,
+    path_ptr: *const u8,
+    path_len: usize,
+) -> eyre::Result<PathBuf> {
+    let name = unsafe { slice::from_raw_parts(path_ptr, path_len) };
+    let name = Path::new(std::str::from_utf8(name).wrap_err("checkpoint name is not valid UTF-8")?);
+    let is_valid = name
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if name.as_os_str().is_empty() || !is_valid {
+        eyre::bail!("invalid checkpoint name {name:?}: expected a relative path without `..`");
+    }
+    Ok(Path::new("out")
+        .join("checkpoints")
+        .join(context.node.id().to_string())
+        .join(name))
+}
+
+/// Frees the given dora context.
+///
+/// As a best-effort safety net, dora keeps track of the contexts that were not freed
+/// yet. Freeing a context a second time is ignored with an error. All functions
+/// that take a context (e.g. the `dora_send_output_*` functions and
+/// [`dora_next_event`]) return an error value instead of accessing a freed context.
+/// This detects late callbacks after shutdown in most cases, but not all: if the
+/// memory of the freed context is reused for a new context, the old pointer is
+/// treated as valid again. So using a freed context is still not allowed.
+///
+/// ## Safety
+///
+/// Only pointers created through [`init_dora_context_from_env`] are allowed
+/// as arguments. Each context pointer must be freed exactly once. After
+/// freeing, the pointer must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_context(context: *mut c_void) {
+    let registered = LIVE_CONTEXTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(&(context as usize));
+    if !registered {
+        let err = "ignoring `free_dora_context` call for an invalid or already freed context";
+        tracing::error!("{err}");
+        set_last_error(err.to_owned());
+        return;
+    }
+    // the loop thread accesses the context, so it must finish first
+    stop_background_loop(context);
+    let mut context: Box<DoraContext> = unsafe { Box::from_raw(context.cast()) };
+    if let Some(pending) = &context.tick {
+        if !pending.is_empty() {
+            tracing::warn!(
+                "discarding {} outputs of unfinished tick (missing `dora_end_tick` call)",
+                pending.len()
+            );
+        }
+    }
+    context.send_shutdown_output();
+    let DoraContext {
+        node,
+        events,
+        forwarder,
+        ..
+    } = *context;
+    // the event stream might still reference the node, so drop it first
+    drop(forwarder);
+    drop(events);
+    drop(node);
+}
+
+/// Waits for the next incoming event for the node.
+///
+/// Returns a pointer to the event on success. This pointer must not be used
+/// directly. Instead, use the `read_dora_event_*` functions to read out the
+/// type and payload of the event. When the event is not needed anymore, use
+/// [`free_dora_event`] to free it again.
+///
+/// Returns a null pointer when all event streams were closed. This means that
+/// no more event will be available. Nodes typically react by stopping.
+///
+/// The `Stop` event is always the last event: once it was returned, all subsequent
+/// calls return a null pointer, even if further events (e.g. `InputClosed`) arrive
+/// afterwards. See [`dora_await_stop`] for waiting for the `Stop` event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_next_event(context: *mut c_void) -> *mut c_void {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return ptr::null_mut();
+        }
+    };
+    match context.next_event() {
+        Some(event) => context.wrap_event(event),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Blocks until dora requests the node to stop.
+///
+/// Receives and discards all events until the `Stop` event arrives. This allows
+/// nodes that do their work outside of the event loop (e.g. on other threads) to
+/// wait for a clean shutdown. Subsequent [`dora_next_event`] calls return a null
+/// pointer.
+///
+/// Returns `0` when the `Stop` event was received, or immediately if it was already
+/// returned by [`dora_next_event`]. Returns `-1` if the event stream was closed
+/// without a `Stop` event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_await_stop(context: *mut c_void) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    while context.next_event().is_some() {}
+    if context.stopped {
+        DoraStatus::Ok as isize
+    } else {
+        set_last_error("event stream closed without a `Stop` event".into());
+        DoraStatus::Error as isize
+    }
+}
+
+/// Callback that handles the events of a background loop, see
+/// [`dora_start_background_loop`].
+///
+/// The arguments are the dora context, the event, and the user-data pointer given
+/// on start.
+pub type DoraEventCallback =
+    unsafe extern "C" fn(context: *mut c_void, event: *const c_void, user_data: *mut c_void);
+
+/// Interval in which the background loop checks whether it should stop.
+const BACKGROUND_LOOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A receive loop started through [`dora_start_background_loop`].
+struct BackgroundLoop {
+    stop: Arc<AtomicBool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+/// Running background loops by context address.
+///
+/// Kept outside of the context because the loop thread has exclusive access to
+/// the context while it runs.
+static BACKGROUND_LOOPS: Mutex<BTreeMap<usize, BackgroundLoop>> = Mutex::new(BTreeMap::new());
+
+/// Pointers that are moved to the thread of a background loop.
+struct BackgroundLoopArgs {
+    context: *mut c_void,
+    on_event: DoraEventCallback,
+    user_data: *mut c_void,
+}
+
+// Safety: the caller of `dora_start_background_loop` guarantees that the context
+// and the user data may be used from the loop thread.
+unsafe impl Send for BackgroundLoopArgs {}
+
+/// Starts a thread that receives the events of the node and passes them to the
+/// given callback.
+///
+/// This is an alternative to calling [`dora_next_event`] in a loop, for nodes whose
+/// main thread does unrelated work. The thread receives events like
+/// [`dora_next_event`] and invokes `on_event` for each of them, with the context,
+/// the event, and the given `user_data` pointer. The event can be read through the
+/// `read_dora_*` functions. It is only valid until the callback returns and must
+/// not be freed by the callback. The `Stop` event is passed to the callback too.
+/// The loop ends after the `Stop` event, when the event stream is closed, or when
+/// [`dora_stop_background_loop`] is called.
+///
+/// Thread safety:
+///
+/// - The callback is always invoked on the loop thread, one event at a time. It may
+///   use the context for all dora functions, e.g. to send outputs. It must not call
+///   [`dora_stop_background_loop`] or [`free_dora_context`].
+/// - The context is not thread-safe. While the loop runs, the context must not be
+///   used from any other thread, except for [`dora_stop_background_loop`] and
+///   [`free_dora_context`] (which stops the loop first). This includes sending
+///   outputs: other threads must pass their data to the callback instead, e.g.
+///   through a queue that the callback drains, or send it after stopping the loop.
+/// - The `user_data` pointer is passed to the callback as is. Data that is shared
+///   with other threads through it must be synchronized by the caller.
+///
+/// Only one background loop can run per context. Returns [`DoraStatus::Ok`] if the
+/// loop was started, or [`DoraStatus::Error`] and sets the last error if a loop is
+/// already running for the context.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `on_event` callback
+/// must be safe to call from another thread with the described arguments and the
+/// given `user_data` until the loop is stopped.
+#[no_mangle]
+pub unsafe extern "C" fn dora_start_background_loop(
+    context: *mut c_void,
+    on_event: DoraEventCallback,
+    user_data: *mut c_void,
+) -> isize {
+    if let Err(err) = unsafe { live_context(context) } {
+        return send_status(Err(err));
+    }
+    let mut loops = BACKGROUND_LOOPS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let key = context as usize;
+    if loops.contains_key(&key) {
+        let err = eyre::eyre!("a background loop is already running for this context");
+        return send_status(Err(err));
+    }
+    let args = BackgroundLoopArgs {
+        context,
+        on_event,
+        user_data,
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+    let thread = std::thread::spawn(move || unsafe { run_background_loop(args, &stop_flag) });
+    loops.insert(key, BackgroundLoop { stop, thread });
+    DoraStatus::Ok as isize
+}
+
+/// Stops the background loop of the given context and waits until its thread
+/// finished.
+///
+/// If the callback is currently handling an event, this function waits until the
+/// callback returns. Events that were not passed to the callback yet stay in the
+/// context and are returned by the following [`dora_next_event`] calls. Afterwards,
+/// the context can be used from the calling thread again.
+///
+/// Does nothing if no background loop was started for the context. Stopping a loop
+/// that already ended (e.g. after the `Stop` event) is fine.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). This function must not
+/// be called from the callback of the loop.
+#[no_mangle]
+pub unsafe extern "C" fn dora_stop_background_loop(context: *mut c_void) {
+    stop_background_loop(context);
+}
+
+/// Stops the background loop of the given context, if any.
+fn stop_background_loop(context: *mut c_void) {
+    let background_loop = BACKGROUND_LOOPS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .remove(&(context as usize));
+    if let Some(BackgroundLoop { stop, thread }) = background_loop {
+        stop.store(true, Ordering::Release);
+        if thread.join().is_err() {
+            tracing::error!("background loop panicked");
+        }
+    }
+}
+
+/// Receives events and passes them to the callback until `stop` is set or no more
+/// events arrive.
+///
+/// ## Safety
+///
+/// The context must be valid and must not be accessed by other threads until this
+/// function returns.
+unsafe fn run_background_loop(args: BackgroundLoopArgs, stop: &AtomicBool) {
+    let context: &mut DoraContext = unsafe { &mut *args.context.cast() };
+    while !stop.load(Ordering::Acquire) {
+        let event = match context.next_event_timeout(BACKGROUND_LOOP_POLL_INTERVAL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        let event = context.wrap_event(event);
+        unsafe { (args.on_event)(args.context, event, args.user_data) };
+        unsafe { free_dora_event(event) };
+    }
+}
+
+/// State transitions of the whole dataflow, see [`dora_next_lifecycle_event`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoraLifecycleEvent {
+    /// All nodes of the dataflow were spawned and the dataflow starts running.
+    DataflowStarted = 0,
+    /// A reload of the node was requested, e.g. through `dora reload`.
+    Reload = 1,
+    /// The dataflow is stopping, e.g. because of `dora stop` or because all nodes
+    /// finished.
+    DataflowStopping = 2,
+}
+
+/// Waits for the next state transition of the dataflow.
+///
+/// Returns the [`DoraLifecycleEvent`] as integer. This gives nodes that manage
+/// external resources a single place to react to dataflow-level transitions. The
+/// transitions are derived from the notifications that the coordinator already
+/// sends to all nodes through the daemon:
+///
+/// - [`DoraLifecycleEvent::DataflowStarted`] is always the first lifecycle event.
+///   The coordinator starts the dataflow once all nodes on all machines are ready,
+///   and the context is only created after this point.
+/// - [`DoraLifecycleEvent::Reload`] is reported for each `Reload` event.
+/// - [`DoraLifecycleEvent::DataflowStopping`] is reported for the `Stop` event.
+///
+/// Lifecycle events are reported in addition to the regular events: the `Stop` and
+/// `Reload` events are still returned by [`dora_next_event`], independent of
+/// whether they were returned by this function already. Events that this function
+/// receives while waiting are kept for [`dora_next_event`].
+///
+/// Returns `-1` if no lifecycle event arrives within `timeout_ms` milliseconds or if
+/// the event stream is closed. A `timeout_ms` of `0` only returns already received
+/// lifecycle events.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_next_lifecycle_event(context: *mut c_void, timeout_ms: u64) -> i32 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)) as i32,
+    };
+    let timeout = Duration::from_millis(timeout_ms);
+    let start = Instant::now();
+    loop {
+        if let Some(event) = context.events.lifecycle.pop_front() {
+            return event as i32;
+        }
+        let Some(remaining) = timeout.checked_sub(start.elapsed()) else {
+            return -1;
+        };
+        match context.events.recv_timeout(remaining) {
+            Some(event) => context.buffer_event(event),
+            None => return -1,
+        }
+    }
+}
+
+/// Pauses the delivery of events through [`dora_next_event`].
+///
+/// While paused, incoming events are buffered instead of being handed out. Only the
+/// `Stop` event is still delivered, so [`dora_next_event`] blocks until dora
+/// requests the node to stop, and [`dora_drain_inputs`] returns no events. This
+/// allows the node to perform a blocking operation, such as a recalibration,
+/// without losing the inputs that arrive in the meantime. Buffered inputs are
+/// subject to the policies set through [`dora_set_event_queue_policy`] and
+/// [`dora_set_link_policy`]. Inputs that are still buffered when the `Stop` event
+/// is delivered are discarded.
+///
+/// Returns a [`DoraStatus`] code. Fails if the context was already freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_pause_inputs(context: *mut c_void) -> isize {
+    match unsafe { live_context(context) } {
+        Ok(context) => {
+            context.paused = true;
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Resumes the delivery of events that was paused through [`dora_pause_inputs`].
+///
+/// The buffered events are handed out by the following [`dora_next_event`] calls,
+/// in the order in which they arrived.
+///
+/// Returns a [`DoraStatus`] code. Fails if the context was already freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_resume_inputs(context: *mut c_void) -> isize {
+    match unsafe { live_context(context) } {
+        Ok(context) => {
+            context.paused = false;
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Collects up to `cap` input events that are immediately available.
+///
+/// Writes pointers to the collected events to the `out_events_ptr` array and their
+/// number to `out_count`. This function does not block: it stops at the first
+/// event that is not available yet. It also stops before the first event that is
+/// not an input event (e.g. `Stop` or `InputClosed`), so that the event order is
+/// kept. Such events are returned by the next [`dora_next_event`] call.
+///
+/// Each returned event must be freed through [`free_dora_event`] separately.
+///
+/// Returns `0` on success, which includes the case that no input was available.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_events_ptr`
+/// argument must point to an array of at least `cap` pointers.
+#[no_mangle]
+pub unsafe extern "C" fn dora_drain_inputs(
+    context: *mut c_void,
+    out_events_ptr: *mut *mut c_void,
+    cap: usize,
+    out_count: *mut usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            unsafe { *out_count = 0 };
+            return send_status(Err(err));
+        }
+    };
+    context.buffer_available_events();
+    let mut count = 0;
+    while count < cap {
+        let Some(event) = context.try_next_input() else {
+            break;
+        };
+        unsafe { *out_events_ptr.add(count) = context.wrap_event(event) };
+        count += 1;
+    }
+    unsafe { *out_count = count };
+    0
+}
+
+/// Discards the oldest event that is available for [`dora_next_event`].
+///
+/// In contrast to [`dora_drain_inputs`], only a single event is removed. This allows
+/// real-time nodes that fall behind to shed exactly as many events as needed to
+/// catch up, e.g. a single frame. All events that are immediately available are
+/// considered, also while event delivery is paused through [`dora_pause_inputs`].
+/// The `Stop` event is never discarded.
+///
+/// Returns the `DoraEventType` of the discarded event as integer, or `-1` if no
+/// event was available or if the oldest event is the `Stop` event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_drop_oldest_event(context: *mut c_void) -> i32 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)) as i32,
+    };
+    context.receive_acks();
+    match context.buffered_events.pop_front() {
+        Some(Event::Stop) => {
+            context.buffered_events.push_front(Event::Stop);
+            -1
+        }
+        Some(event) => event_type(&event) as i32,
+        None => -1,
+    }
+}
+
+/// Creates a second reader for the events of the given context.
+///
+/// The reader receives a copy of every event that arrives after this call, e.g. to
+/// record all inputs while the context processes them. Events are read through
+/// [`dora_reader_next_event`] and the context's [`dora_next_event`] independently.
+/// Input data is not copied: both events share the same memory, which is released
+/// once both events are freed. Thus, every event must be freed through
+/// [`free_dora_event`] separately for each reader.
+///
+/// Events that are not read by one of the readers are queued in memory, so all
+/// readers should be read regularly. Reading from a reader might block until the
+/// next event arrives, even if events are queued for the other reader.
+///
+/// The returned reader must be freed through [`free_dora_event_reader`] before the
+/// context is freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_clone_event_reader(context: *mut c_void) -> *mut c_void {
+    let context_ptr: *mut DoraContext = context.cast();
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return ptr::null_mut();
+        }
+    };
+    context.events.readers.push(Some(VecDeque::new()));
+    let reader = DoraEventReader {
+        context: context_ptr,
+        index: context.events.readers.len() - 1,
+    };
+    Box::into_raw(Box::new(reader)).cast()
+}
+
+/// Waits for the next event of the given reader.
+///
+/// Works like [`dora_next_event`], but for readers created through
+/// [`dora_clone_event_reader`]. The returned events are independent of the events
+/// returned by [`dora_next_event`]. Queue policies and acknowledgements only apply
+/// to the context's own events.
+///
+/// ## Safety
+///
+/// The `reader` argument must be a reader created through
+/// [`dora_clone_event_reader`] that was not freed yet. Its context must be still
+/// valid, i.e., not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_reader_next_event(reader: *mut c_void) -> *mut c_void {
+    let reader: &DoraEventReader = unsafe { &*reader.cast() };
+    let context = match unsafe { live_context(reader.context.cast()) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return ptr::null_mut();
+        }
+    };
+    match context.events.recv_for_reader(reader.index) {
+        Some(event) => {
+            let event = DoraEvent {
+                event,
+                ingress: context.node.clock().new_timestamp(),
+                is_first: false,
+                type_changed: false,
+                decompressed: OnceCell::new(),
+                type_name: OnceCell::new(),
+            };
+            Box::into_raw(Box::new(event)).cast()
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees the given event reader.
+///
+/// Events that were read from the reader stay valid until they are freed.
+///
+/// ## Safety
+///
+/// Only pointers created through [`dora_clone_event_reader`] are allowed as
+/// arguments. Each reader must be freed exactly once and before its context is
+/// freed. After freeing, the pointer must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_event_reader(reader: *mut c_void) {
+    let reader: Box<DoraEventReader> = unsafe { Box::from_raw(reader.cast()) };
+    // the reader's queue was already released if the context was freed
+    if let Ok(context) = unsafe { live_context(reader.context.cast()) } {
+        context.events.readers[reader.index] = None;
+    }
+}
+
+/// Creates a reader that gives access to the current event and the next
+/// `window_size - 1` events.
+///
+/// Events are accessed through [`dora_window_get`] without consuming them. Only
+/// [`dora_window_advance`] moves the window forward by one event. This is useful
+/// for filters that need some context around the current sample, e.g. for
+/// smoothing.
+///
+/// The reader receives its events through the context, like [`dora_next_event`], so
+/// the two should not be used at the same time. Queue and link policies apply as
+/// usual.
+///
+/// Returns a null pointer if `window_size` is `0`. The returned reader must be
+/// freed through [`free_dora_window_reader`] before the context is freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_window_reader_new(
+    context: *mut c_void,
+    window_size: usize,
+) -> *mut c_void {
+    if let Err(err) = unsafe { live_context(context) } {
+        set_last_error(format!("{err:?}"));
+        return ptr::null_mut();
+    }
+    if window_size == 0 {
+        set_last_error("window size must be at least 1".into());
+        return ptr::null_mut();
+    }
+    let reader = DoraWindowReader {
+        context: context.cast(),
+        window_size,
+        window: VecDeque::with_capacity(window_size),
+    };
+    Box::into_raw(Box::new(reader)).cast()
+}
+
+/// Returns the event at the given offset of the window without consuming it.
+///
+/// Offset `0` is the current event, offset `1` the event after it, and so on, up to
+/// `window_size - 1`. Blocks until enough events were received. Writes the event
+/// to `out_event`, which can be passed to the `read_dora_event_*` and
+/// `read_dora_input_*` functions.
+///
+/// The event is owned by the reader and must not be freed through
+/// [`free_dora_event`]. It stays valid until the window is advanced past it.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer if the offset is
+/// outside the window or if the event stream ended before the requested event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `reader` argument
+/// must be a reader created through [`dora_window_reader_new`] that was not freed
+/// yet. Its context must be still valid, i.e., not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_window_get(
+    reader: *mut c_void,
+    offset: usize,
+    out_event: *mut *const c_void,
+) -> isize {
+    let reader: &mut DoraWindowReader = unsafe { &mut *reader.cast() };
+    let mut event = || {
+        if offset >= reader.window_size {
+            eyre::bail!(
+                "offset {offset} is outside of the window of size {}",
+                reader.window_size
+            );
+        }
+        let context = unsafe { live_context(reader.context.cast()) }?;
+        while reader.window.len() <= offset {
+            let event = context
+                .next_event()
+                .ok_or_else(|| eyre::eyre!("event stream ended"))?;
+            let event = context.wrap_event(event);
+            reader
+                .window
+                .push_back(unsafe { Box::from_raw(event.cast()) });
+        }
+        let event: *const DoraEvent = &*reader.window[offset];
+        Ok(event)
+    };
+    match event() {
+        Ok(event) => {
+            unsafe { *out_event = event.cast() };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe { *out_event = ptr::null() };
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Moves the window forward by one event.
+///
+/// Frees the current event, so that the event at offset `1` becomes the current
+/// event. Pointers to the freed event must not be used anymore. If the current
+/// event was not received through [`dora_window_get`] yet, this function waits for
+/// it and drops it.
+///
+/// Returns `0` on success. Returns `-1` if the event stream ended.
+///
+/// ## Safety
+///
+/// The `reader` argument must be a reader created through
+/// [`dora_window_reader_new`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_window_advance(reader: *mut c_void) -> isize {
+    let reader: &mut DoraWindowReader = unsafe { &mut *reader.cast() };
+    if reader.window.pop_front().is_none() {
+        let context = match unsafe { live_context(reader.context.cast()) } {
+            Ok(context) => context,
+            Err(err) => return send_status(Err(err)),
+        };
+        if context.next_event().is_none() {
+            set_last_error("event stream ended".into());
+            return DoraStatus::Error as isize;
+        }
+    }
+    DoraStatus::Ok as isize
+}
+
+/// Frees the given window reader and all events in its window.
+///
+/// ## Safety
+///
+/// Only pointers created through [`dora_window_reader_new`] are allowed as
+/// arguments. Each reader must be freed exactly once. After freeing, the pointer
+/// and all events read from it must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_window_reader(reader: *mut c_void) {
+    let _: Box<DoraWindowReader> = unsafe { Box::from_raw(reader.cast()) };
+}
+
+/// Returns the data type of the next event, without consuming it.
+///
+/// Returns the [`DoraDataType`] of the next input as integer if the next event is an
+/// input event. Returns `-1` if the next event is not an input event or if no event
+/// is immediately available. This function does not block. The peeked event is
+/// returned by the next [`dora_next_event`] call.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_peek_input_data_type(context: *mut c_void) -> i32 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)) as i32,
+    };
+    match context.peek_event() {
+        Some(Event::Input { data, .. }) => DoraDataType::from(data.data_type()) as i32,
+        _ => -1,
+    }
+}
+
+/// Re-establishes the connection to the dora daemon, e.g. after a daemon restart.
+///
+/// The new connection is set up from the same environment variables that were
+/// used by [`init_dora_context_from_env`]. On success, subsequent sends and
+/// [`dora_next_event`] calls use the new connection.
+///
+/// The following state of the context is preserved:
+///
+/// - the node configuration, including the output IDs and declared types
+/// - the output statistics of [`dora_output_stats`] and the dropped event count
+/// - rate limits, flow control windows, and the input queue policy
+/// - events that were already received, but not returned by [`dora_next_event`] yet
+/// - outputs buffered in an active tick, as well as the installed stop token
+///
+/// Outputs that were buffered during an outage (see [`dora_enable_auto_reconnect`])
+/// are sent right after reconnecting.
+///
+/// The following state is reset:
+///
+/// - the sequence numbers of the outputs restart at zero
+/// - the acknowledgements of flow control windows, i.e. messages sent before the
+///   reconnect no longer count towards the window
+/// - events that were not received from the old connection are lost
+///
+/// Returns a [`DoraStatus`] code. On error, the old connection is kept. Reconnecting
+/// is refused while a background loop is running for the context (see
+/// [`dora_start_background_loop`]), since the loop thread uses the connection. Stop
+/// the loop first, or use [`dora_enable_auto_reconnect`], which reconnects on the
+/// loop thread.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). Pointers to events or
+/// readers of the context stay valid.
+#[no_mangle]
+pub unsafe extern "C" fn dora_reconnect(context: *mut c_void) -> isize {
+    // the loop thread has exclusive access to the context, so check this first
+    let loop_running = BACKGROUND_LOOPS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .contains_key(&(context as usize));
+    if loop_running {
+        let err = eyre::eyre!("cannot reconnect while a background loop is running");
+        return send_status(Err(err));
+    }
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    send_status(context.reconnect())
+}
+
+/// Enables the automatic reconnection to the dora daemon, e.g. for daemon restarts.
+///
+/// When a send fails while the automatic reconnection is enabled, the context
+/// reconnects like [`dora_reconnect`] and retries the send. If the daemon is not
+/// reachable, the connection is considered down:
+///
+/// - If `buffer_outputs` is `true`, subsequent outputs are buffered in memory and
+///   the send functions return [`DoraStatus::Ok`]. If `buffer_outputs` is `false`,
+///   the outputs are dropped and the send functions return [`DoraStatus::Error`].
+/// - Every send retries to reconnect, at most every 500 milliseconds. Once
+///   reconnected, the buffered outputs are sent in their original order and with
+///   their original timestamps, before the new output.
+/// - When the buffer is full, i.e. when an output would exceed `max_buffer_bytes`
+///   (measured as the size of the arrow buffers of the outputs), the output is
+///   dropped and the send function returns [`DoraStatus::Error`]. Outputs that are
+///   already buffered are kept, so the oldest data is sent first after
+///   reconnecting.
+///
+/// When the event stream is closed without a `Stop` event, [`dora_next_event`]
+/// and the background loop of [`dora_start_background_loop`] retry to reconnect for
+/// up to 10 seconds before they report the end of the stream. Events resume transparently after reconnecting,
+/// but events that the daemon sent while the node was disconnected are lost. See
+/// [`dora_reconnect`] for the state that is reset by reconnecting, e.g. the
+/// sequence numbers. Use [`dora_connection_status`] to query the current
+/// connection state.
+///
+/// Calling this function again updates the settings. Returns a [`DoraStatus`] code.
+/// Fails for standalone contexts, which have no daemon connection.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_enable_auto_reconnect(
+    context: *mut c_void,
+    buffer_outputs: bool,
+    max_buffer_bytes: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    if let NodeHandle::Standalone(_) = context.node {
+        return send_status(Err(eyre::eyre!("standalone contexts cannot reconnect")));
+    }
+    match &mut context.auto_reconnect {
+        Some(auto_reconnect) => {
+            auto_reconnect.buffer_outputs = buffer_outputs;
+            auto_reconnect.max_buffer_bytes = max_buffer_bytes;
+        }
+        None => {
+            context.auto_reconnect = Some(AutoReconnect {
+                buffer_outputs,
+                max_buffer_bytes,
+                buffered: VecDeque::new(),
+                buffered_bytes: 0,
+                connected: true,
+                last_attempt: None,
+            });
+        }
+    }
+    DoraStatus::Ok as isize
+}
+
+/// Reports the state of the daemon connection, see [`dora_enable_auto_reconnect`].
+///
+/// Writes whether the node is currently connected to the daemon to
+/// `out_connected` and the total size of the outputs that wait for the connection
+/// to be re-established to `out_buffered_bytes`. Without automatic reconnection,
+/// the node is always reported as connected with no buffered outputs, since
+/// connection losses are only detected by automatic reconnection.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_connection_status(
+    context: *mut c_void,
+    out_connected: *mut bool,
+    out_buffered_bytes: *mut usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let (connected, buffered_bytes) = match &context.auto_reconnect {
+        Some(auto_reconnect) => (auto_reconnect.connected, auto_reconnect.buffered_bytes),
+        None => (true, 0),
+    };
+    unsafe {
+        *out_connected = connected;
+        *out_buffered_bytes = buffered_bytes;
+    }
+    DoraStatus::Ok as isize
+}
+
+/// Returns the declared data type of the given input.
+///
+/// Data types are declared through the `types` field of the node in the dataflow
+/// YAML, e.g. `types: { image: uint8 }`. They allow allocating buffers before the
+/// first input arrives. Note that dora does not enforce the declared types.
+///
+/// Returns the [`DoraDataType`] as integer. Returns a negative [`DoraStatus`] code
+/// if the ID is not valid UTF-8, not an input of the node, or if no type was
+/// declared for it. Declared types that are not supported by the C API are
+/// returned as [`DoraDataType::Unknown`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_input_declared_type(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> i32 {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let id = unsafe { read_id(id_ptr, id_len) }?;
+        if !context.node.has_input(&id) {
+            eyre::bail!("unknown input {id}");
+        }
+        declared_type(context, &id)
+    });
+    value_status(result) as i32
+}
+
+/// Returns the declared data type of the given output.
+///
+/// Works like [`dora_input_declared_type`], but for outputs of the node.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_declared_type(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> i32 {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let id = unsafe { read_id(id_ptr, id_len) }?;
+        if !context.node.has_output(&id) {
+            eyre::bail!("unknown output {id}");
+        }
+        declared_type(context, &id)
+    });
+    value_status(result) as i32
+}
+
+/// Reads out the configuration value with the given key.
+///
+/// Configuration values are set through the `config` field of the node in the
+/// dataflow YAML, e.g. `config: { gain: 0.5 }`, or at runtime through
+/// [`dora_set_config`]. Values set at runtime take precedence over the YAML values.
+/// Environment variables are not considered; values of the `env` field of the node
+/// are read through `getenv` as usual. Numbers and booleans are formatted as
+/// strings, nested values (lists and maps) are formatted as YAML.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded value. Returns a [`DoraStatus`] code. Writes a null pointer and
+/// length `0` if the key is not valid UTF-8 or if there is no value with this key.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned `out_ptr`
+/// must not be used after the value is changed through [`dora_set_config`] or after
+/// freeing the context.
+#[no_mangle]
+pub unsafe extern "C" fn dora_get_config(
+    context: *mut c_void,
+    key_ptr: *const u8,
+    key_len: usize,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let value = unsafe { live_context(context) }.and_then(|context| {
+        let key = std::str::from_utf8(unsafe { slice::from_raw_parts(key_ptr, key_len) })
+            .wrap_err("config key is not valid UTF-8")?;
+        context
+            .config
+            .get(key)
+            .map(String::as_bytes)
+            .ok_or_else(|| eyre::eyre!("no config value with key `{key}`"))
+    });
+    unsafe { write_slice(value, out_ptr, out_len) }
+}
+
+/// Overrides the configuration value with the given key at runtime.
+///
+/// The override replaces the value from the dataflow YAML for subsequent
+/// [`dora_get_config`] calls, e.g. for live tuning. It is kept in memory only, so it
+/// neither changes the dataflow YAML nor other nodes and is lost when the node
+/// exits.
+///
+/// Returns a [`DoraStatus`] code, [`DoraStatus::InvalidUtf8Id`] if the key or value
+/// is not valid UTF-8.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `key_ptr`/`key_len`
+/// and `value_ptr`/`value_len` fields must be the start pointers and lengths of
+/// UTF8-encoded strings.
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_config(
+    context: *mut c_void,
+    key_ptr: *const u8,
+    key_len: usize,
+    value_ptr: *const u8,
+    value_len: usize,
+) -> isize {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let key = std::str::from_utf8(unsafe { slice::from_raw_parts(key_ptr, key_len) })
+            .wrap_err("config key is not valid UTF-8")?;
+        let value = std::str::from_utf8(unsafe { slice::from_raw_parts(value_ptr, value_len) })
+            .wrap_err("config value is not valid UTF-8")?;
+        context.config.insert(key.to_owned(), value.to_owned());
+        Ok(())
+    });
+    send_status(result)
+}
+
+/// Checks that configuration values exist for all of the given keys.
+///
+/// Nodes can call this once after initialization to fail fast with a clear message
+/// if the dataflow YAML lacks required values, instead of failing when a value is
+/// first used. Values are looked up like in [`dora_get_config`].
+///
+/// Returns a [`DoraStatus`] code. If keys are missing, the error message lists all
+/// of them and is stored as last error (see [`dora_last_error`]).
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `keys_ptr` and
+/// `key_lens_ptr` arguments must point to `count` elements each. Each key pointer
+/// must point to the given number of bytes.
+#[no_mangle]
+pub unsafe extern "C" fn dora_require_config(
+    context: *mut c_void,
+    keys_ptr: *const *const u8,
+    key_lens_ptr: *const usize,
+    count: usize,
+) -> isize {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let (key_ptrs, key_lens) = unsafe {
+            (
+                slice::from_raw_parts(keys_ptr, count),
+                slice::from_raw_parts(key_lens_ptr, count),
+            )
+        };
+        let mut missing = Vec::new();
+        for (ptr, len) in key_ptrs.iter().zip(key_lens) {
+            let key = std::str::from_utf8(unsafe { slice::from_raw_parts(*ptr, *len) })
+                .wrap_err("config key is not valid UTF-8")?;
+            if !context.config.contains_key(key) {
+                missing.push(format!("`{key}`"));
+            }
+        }
+        if !missing.is_empty() {
+            eyre::bail!("missing required config values: {}", missing.join(", "));
+        }
+        Ok(())
+    });
+    send_status(result)
+}
+
+/// Reads out a JSON description of the dataflow graph.
+///
+/// The description has the following format:
+///
+/// ```json
+/// {
+///   "nodes": [{ "id": "camera", "inputs": ["tick"], "outputs": ["image"] }, ...],
+///   "edges": [
+///     { "source": "dora", "output": "timer/millis/100", "target": "camera", "input": "tick" },
+///     ...
+///   ]
+/// }
+/// ```
+///
+/// Inputs and outputs of operators are prefixed with the operator ID, e.g.
+/// `object_detection/bbox`. Timer inputs are represented as edges from the `dora`
+/// source. The description is derived from the dataflow descriptor that the node
+/// receives from its daemon at startup, which is the same descriptor that the
+/// coordinator uses, so no additional request is needed.
+///
+/// Writes the `out_json_ptr` and `out_json_len` with the start pointer and length
+/// of the UTF8-encoded JSON. Returns a [`DoraStatus`] code. Writes a null pointer
+/// and length `0` for standalone contexts, which are not part of a dataflow.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_json_ptr` must not be used after freeing the context.
+#[no_mangle]
+pub unsafe extern "C" fn dora_get_topology(
+    context: *mut c_void,
+    out_json_ptr: *mut *const u8,
+    out_json_len: *mut usize,
+) -> isize {
+    let json = unsafe { live_context(context) }.and_then(|context| {
+        if context.topology.is_none() {
+            let topology = context.node.topology()?;
+            let json = serde_json::to_string(&topology).wrap_err("failed to serialize topology")?;
+            context.topology = Some(json);
+        }
+        Ok(context.topology.as_deref().unwrap_or_default().as_bytes())
+    });
+    unsafe { write_slice(json, out_json_ptr, out_json_len) }
+}
+
+/// Returns the number of downstream nodes that subscribe to the given output.
+///
+/// The count is based on the dataflow descriptor, i.e. it includes all nodes that
+/// map at least one of their inputs to the output, regardless of whether these nodes
+/// are still running. Nodes that map multiple inputs to the output are counted once.
+/// A count of `0` means that the output is not connected, so sending it has no
+/// effect. Standalone contexts always report `0`.
+///
+/// Returns a negative [`DoraStatus`] code if the ID is not valid UTF-8 or if the
+/// node has no such output.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_subscriber_count(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> i32 {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let id = unsafe { read_id(id_ptr, id_len) }?;
+        if !context.node.has_output(&id) {
+            eyre::bail!("unknown output {id}");
+        }
+        Ok(context.node.subscriber_count(&id).min(i32::MAX as usize) as isize)
+    });
+    value_status(result) as i32
+}
+
+/// Returns the maximum number of messages of the given output that can be queued
+/// for a receiver before messages are dropped.
+///
+/// Dora does not apply back-pressure to senders. Instead, the daemon queues the
+/// messages for each receiving input and drops the oldest queued messages once the
+/// queue size of the input is exceeded. The queue size is configured through the
+/// `queue_size` field of the input in the dataflow descriptor and defaults to `10`.
+/// If multiple inputs are mapped to the output, the smallest queue size is returned.
+///
+/// Returns `0` if the output is not connected or if the context is standalone.
+/// Returns a negative [`DoraStatus`] code if the ID is not valid UTF-8 or if the
+/// node has no such output.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_queue_capacity(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> isize {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let id = unsafe { read_id(id_ptr, id_len) }?;
+        if !context.node.has_output(&id) {
+            eyre::bail!("unknown output {id}");
+        }
+        let capacity = context.node.output_queue_capacity(&id).unwrap_or(0);
+        Ok(capacity.try_into().unwrap_or(isize::MAX))
+    });
+    value_status(result)
+}
+
+fn declared_type(context: &DoraContext, id: &DataId) -> eyre::Result<isize> {
+    match context.node.declared_type(id) {
+        Some(name) => Ok(DoraDataType::from_name(name) as isize),
+        None => eyre::bail!("no type declared for {id}"),
+    }
+}
+
+/// Limits the number of input events that are buffered for the node.
+///
+/// When the node receives inputs faster than it processes them, incoming events are
+/// buffered. This function limits the buffer to `max_events` input events. If the
+/// limit is reached, either the oldest buffered input (if `drop_oldest` is `true`)
+/// or the newly received input (if `drop_oldest` is `false`) is dropped. The number
+/// of dropped inputs can be queried through [`dora_dropped_events`].
+///
+/// Only input events are dropped. Other events such as `Stop` and `InputClosed`
+/// are never dropped and don't count towards the limit.
+///
+/// Setting `max_events` to `0` removes the limit again.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_event_queue_policy(
+    context: *mut c_void,
+    max_events: usize,
+    drop_oldest: bool,
+) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return;
+        }
+    };
+    context.queue_policy = (max_events > 0).then_some(QueuePolicy {
+        max_events,
+        drop_oldest,
+    });
+}
+
+/// Returns the number of input events that were dropped because of the queue policy
+/// set through [`dora_set_event_queue_policy`] or the link policy set through
+/// [`dora_set_link_policy`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_dropped_events(context: *mut c_void) -> u64 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return 0;
+        }
+    };
+    context.dropped_events
+}
+
+/// Chooses between reliable and latest-wins delivery of inputs to the node.
+///
+/// With `reliable` set to `true` (the default), every input that reaches the node
+/// is handed out through [`dora_next_event`], in order. With `reliable` set to
+/// `false`, a buffered input is replaced as soon as newer data of the same input
+/// arrives, so that slow nodes always process the freshest data instead of
+/// working through a backlog. Replaced inputs are counted in
+/// [`dora_dropped_events`]. Events other than inputs are never dropped.
+///
+/// The policy only applies to the node side of the link. The connection to the
+/// daemon (TCP or shared memory) always delivers reliably, and inputs are still
+/// subject to the `queue_size` of the dataflow description on the daemon side.
+/// Outputs are always sent reliably. The policy can be combined with
+/// [`dora_set_event_queue_policy`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_link_policy(context: *mut c_void, reliable: bool) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    context.latest_wins = !reliable;
+    DoraStatus::Ok as isize
+}
+
+/// Returns a stop token that is set when dora requests the node to stop.
+///
+/// Compute-heavy nodes might not call [`dora_next_event`] for a long time, so
+/// they don't notice `Stop` events in time. Such nodes can poll the returned token
+/// in their long-running loops through [`dora_stop_token_is_set`] to stop early.
+///
+/// Installing the token moves the event reception to a background thread, which
+/// sets the token as soon as a `Stop` event arrives. The `Stop` event is still
+/// returned by [`dora_next_event`] as usual. Calling this function multiple times
+/// returns the same token.
+///
+/// The token is valid until the context is freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_install_stop_token(context: *mut c_void) -> *const c_void {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            return ptr::null();
+        }
+    };
+    Arc::as_ptr(&context.stop_token()).cast()
+}
+
+/// Checks whether the given stop token is set, i.e. whether dora requested the node
+/// to stop.
+///
+/// ## Safety
+///
+/// The `token` argument must be a stop token returned by
+/// [`dora_install_stop_token`]. The context that the token belongs to must be still
+/// valid, i.e., not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_stop_token_is_set(token: *const c_void) -> bool {
+    let token: &AtomicBool = unsafe { &*token.cast() };
+    token.load(Ordering::Acquire)
+}
+
+/// Reads out the type of the given event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_event_type(event: *const ()) -> EventType {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    event_type(event)
+}
+
+fn event_type(event: &Event) -> EventType {
+    match event {
+        Event::Stop => EventType::Stop,
+        Event::Input { .. } => EventType::Input,
+        Event::InputClosed { .. } => EventType::InputClosed,
+        Event::Error(_) => EventType::Error,
+        _ => EventType::Unknown,
+    }
+}
+
+#[repr(C)]
+pub enum EventType {
+    Stop,
+    Input,
+    InputClosed,
+    Error,
+    Unknown,
+}
+
+/// Reads out whether the given event is an input or an `InputClosed` event, together
+/// with the input ID.
+///
+/// Returns `0` for `Input` events and `1` for `InputClosed` events. In both cases,
+/// `out_id_ptr` and `out_id_len` are set to the start pointer and length of the ID
+/// string of the input. The ID is guaranteed to be valid UTF-8.
+///
+/// Returns `-1` for all other events and writes a null pointer and length `0`.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_id_ptr` must not be used after freeing the `event`, since it points
+/// directly into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_event_input_status(
+    event: *const (),
+    out_id_ptr: *mut *const u8,
+    out_id_len: *mut usize,
+) -> i32 {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let (status, id) = match event {
+        Event::Input { id, .. } => (0, Some(id)),
+        Event::InputClosed { id } => (1, Some(id)),
+        _ => (-1, None),
+    };
+    let (ptr, len) = match id {
+        Some(id) => (id.as_str().as_ptr(), id.as_str().len()),
+        None => (ptr::null(), 0),
+    };
+    unsafe {
+        *out_id_ptr = ptr;
+        *out_id_len = len;
+    }
+    status
+}
+
+/// Reads out the arrow data type of the given input event as human-readable string.
+///
+/// The string uses the same format as the error messages of dora, e.g. `UInt8` or
+/// `Float32`, which makes it useful for logging unexpected input types. It is not
+/// meant to be parsed.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded string. Returns `0` on success. Returns `-1` and writes a null
+/// pointer and length `0` if the given event is not an input event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_type_name(
+    event: *const (),
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent {
+        event, type_name, ..
+    } = unsafe { &*event.cast::<DoraEvent>() };
+    let name = input_data(event).map(|data| {
+        type_name
+            .get_or_init(|| data.data_type().to_string())
+            .as_bytes()
+    });
+    unsafe { write_slice(name, out_ptr, out_len) }
+}
+
+/// Reads out the ID of the given input event.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// ID string of the input. The ID is guaranteed to be valid UTF-8.
+///
+/// Writes a null pointer and length `0` if the given event is not an input event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_id(
+    event: *const (),
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { id, .. } => {
+            let id = id.as_str().as_bytes();
+            let ptr = id.as_ptr();
+            let len = id.len();
+            unsafe {
+                *out_ptr = ptr;
+                *out_len = len;
+            }
+        }
+        _ => unsafe {
+            *out_ptr = ptr::null();
+            *out_len = 0;
+        },
+    }
+}
+
+/// Reads out the arrow field name of the given input event.
+///
+/// Senders can attach a field name to their outputs by setting the
+/// [`FIELD_NAME_PARAMETER`] custom metadata parameter to a string value.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// field name. Writes a null pointer and length `0` if the given event is not an
+/// input event or if the input has no field name.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_field_name(
+    event: *const (),
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let field_name = match event {
+        Event::Input { metadata, .. } => metadata.parameters.custom.get(FIELD_NAME_PARAMETER),
+        _ => None,
+    };
+    match field_name {
+        Some(Parameter::String(name)) => unsafe {
+            *out_ptr = name.as_ptr();
+            *out_len = name.len();
+        },
+        _ => unsafe {
+            *out_ptr = ptr::null();
+            *out_len = 0;
+        },
+    }
+}
+
+/// Reads out how old the given input is, in nanoseconds.
+///
+/// The age is the difference between the current time of the node's clock and the
+/// timestamp that the sender attached to the input. Real-time nodes can use this
+/// to skip stale inputs.
+///
+/// Returns `0` on success and `-1` if the given event is not an input event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_age_nanos(
+    event: *const (),
+    context: *mut c_void,
+    out_nanos: *mut u64,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    match event {
+        Event::Input { metadata, .. } => {
+            let now = context
+                .node
+                .clock()
+                .new_timestamp()
+                .get_time()
+                .to_duration();
+            let sent = metadata.timestamp().get_time().to_duration();
+            let age = now.saturating_sub(sent);
+            unsafe { *out_nanos = age.as_nanos().try_into().unwrap_or(u64::MAX) };
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Reads out the time at which the given event was received, in nanoseconds since
+/// the UNIX epoch.
+///
+/// The ingress time is taken from the node's clock when the event is handed out
+/// through [`dora_next_event`] (or a related function), so it uses the same time
+/// base as the timestamp that the sender attached to the input. The difference
+/// between the two is the transport latency of the input, excluding the time that
+/// the node spends processing it. In contrast to [`read_dora_input_age_nanos`], the
+/// result does not depend on when this function is called.
+///
+/// The ingress time is recorded for all event types. Returns `0` on success.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_ingress_nanos(
+    event: *const (),
+    out_nanos: *mut u64,
+) -> isize {
+    let DoraEvent { ingress, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let nanos = ingress.get_time().to_duration().as_nanos();
+    unsafe { *out_nanos = nanos.try_into().unwrap_or(u64::MAX) };
+    0
+}
+
+/// Reads out the sequence number of the given input.
+///
+/// Nodes using the C API number the messages of each output consecutively, starting
+/// at `0`, and store the number in the [`SEQUENCE_PARAMETER`] metadata parameter. A
+/// gap between the sequence numbers of two consecutive inputs indicates lost
+/// messages, a decreasing number indicates out-of-order delivery.
+///
+/// Returns `0` on success and `-1` if the given event is not an input event or has
+/// no sequence number (e.g. because it was sent by a node using another node API).
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_sequence(event: *const (), out_seq: *mut u64) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { metadata, .. } => match metadata.parameters.custom.get(SEQUENCE_PARAMETER) {
+            Some(Parameter::Integer(sequence)) => {
+                unsafe { *out_seq = *sequence as u64 };
+                0
+            }
+            _ => -1,
+        },
+        _ => -1,
+    }
+}
+
+/// Checks whether the given input event marks a dropped frame.
+///
+/// Dropped frames are sent through [`dora_send_output_dropped`] to keep the
+/// cadence of fixed-rate outputs. They carry no data, so consumers typically skip
+/// them, but they can still count them as ticks.
+///
+/// Returns `1` if the input marks a dropped frame, `0` if it is a regular input,
+/// and `-1` if the given event is not an input event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_is_dropped(event: *const ()) -> i32 {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { metadata, .. } => i32::from(
+            metadata.parameters.custom.get(DROPPED_PARAMETER) == Some(&Parameter::Bool(true)),
+        ),
+        _ => -1,
+    }
+}
+
+/// Verifies the checksum of the given `UInt8` input.
+///
+/// Recomputes the CRC-32 checksum of the input data and compares it with the
+/// checksum that the sender stored in the metadata through
+/// [`dora_send_output_u8_checked`].
+///
+/// Returns `1` if the checksums match and `0` if they differ, i.e. if the data was
+/// corrupted. Returns `-1` if the event is not an input event, if the input has no
+/// checksum, or if the input is not an `UInt8` array.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_verify_checksum(event: *const ()) -> i32 {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let Event::Input { data, metadata, .. } = event else {
+        return -1;
+    };
+    let Some(Parameter::Integer(expected)) = metadata.parameters.custom.get(CHECKSUM_PARAMETER)
+    else {
+        return -1;
+    };
+    if data.data_type() != &DataType::UInt8 {
+        return -1;
+    }
+    let values = data.as_primitive::<UInt8Type>().values();
+    i32::from(i64::from(crc32fast::hash(values)) == *expected)
+}
+
+/// Reads out the given input event as interleaved image.
+///
+/// The image dimensions are read from the [`SHAPE_PARAMETER`] metadata parameter,
+/// which senders set to `[height, width, channels]` (e.g. `[480, 640, 3]` for an RGB
+/// image) or `[height, width]` for single-channel images. The pixel data is expected
+/// as a flat primitive array in row-major order with interleaved channels.
+///
+/// On success, writes the start pointer of the pixel data to `out_ptr`, the image
+/// dimensions to `out_width`, `out_height`, and `out_channels`, and the
+/// [`DoraDataType`] of the channel values as integer to `out_dtype`. The returned
+/// pointer must not be used after freeing the event.
+///
+/// Returns a [`DoraStatus`] code. Fails if the event is not an input, if it has no
+/// valid shape, or if the array length does not match the shape. On error, a null
+/// pointer is written to `out_ptr` and the error is stored as last error (see
+/// [`dora_last_error`]).
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). All `out_*` arguments
+/// must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_image(
+    event: *const (),
+    out_ptr: *mut *const c_void,
+    out_width: *mut usize,
+    out_height: *mut usize,
+    out_channels: *mut usize,
+    out_dtype: *mut i32,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let image = || {
+        let Event::Input { data, metadata, .. } = event else {
+            eyre::bail!("event is not an input event");
+        };
+        let shape = match metadata.parameters.custom.get(SHAPE_PARAMETER) {
+            Some(Parameter::ListInt(shape)) => shape,
+            _ => eyre::bail!("input has no `{SHAPE_PARAMETER}` list parameter"),
+        };
+        let dims: Vec<usize> = shape
+            .iter()
+            .map(|&d| usize::try_from(d))
+            .collect::<Result<_, _>>()
+            .wrap_err("image shape must not be negative")?;
+        let (height, width, channels) = match dims[..] {
+            [height, width] => (height, width, 1),
+            [height, width, channels] => (height, width, channels),
+            _ => eyre::bail!("expected image shape of length 2 or 3, got {shape:?}"),
+        };
+        let (ptr, len, dtype) = primitive_values(&data.0)?;
+        if len != height * width * channels {
+            eyre::bail!(
+                "image shape {shape:?} requires {} values, but input has {len}",
+                height * width * channels
+            );
+        }
+        Ok((ptr, width, height, channels, dtype))
+    };
+    match image() {
+        Ok((ptr, width, height, channels, dtype)) => {
+            unsafe {
+                *out_ptr = ptr;
+                *out_width = width;
+                *out_height = height;
+                *out_channels = channels;
+                *out_dtype = dtype as i32;
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe { *out_ptr = ptr::null() };
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Describes the data of the given input as NumPy array, without copying it.
+///
+/// The description follows the NumPy array interface, so that bindings (e.g. written
+/// with pybind11 or Cython) can create a zero-copy NumPy view of the input:
+///
+/// - `out_data_ptr`: start pointer of the values
+/// - `out_shape_ptr`: size of each dimension. The shape is read from the `shape`
+///   metadata parameter (see [`SHAPE_PARAMETER`]) if present. Otherwise, the input
+///   is described as one-dimensional array.
+/// - `out_ndim`: number of dimensions, at most [`DORA_MAX_NDIM`]
+/// - `out_dtype_char`: the NumPy dtype character code, e.g. `'f'` for `float32`
+///   (see `numpy.dtype.char`). Supported are `'b'`, `'h'`, `'i'`, `'q'` for signed
+///   integers, `'B'`, `'H'`, `'I'`, `'Q'` for unsigned integers, and `'e'`, `'f'`,
+///   `'d'` for floating point values.
+/// - `out_strides_ptr`: stride of each dimension in bytes. The values are stored
+///   contiguously in row-major (C) order.
+///
+/// Returns a [`DoraStatus`] code. Fails if the event is not an input event, if the
+/// input values have no NumPy equivalent (e.g. strings or booleans), or if the shape
+/// does not match the number of values.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_shape_ptr` and
+/// `out_strides_ptr` arguments must point to arrays of at least [`DORA_MAX_NDIM`]
+/// elements. The other output arguments must be valid pointers. The returned
+/// `out_data_ptr` must not be used after freeing the event, since it points
+/// directly into the event's memory. Python objects that view the data need to keep
+/// the event alive accordingly.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_as_numpy_desc(
+    event: *const (),
+    out_data_ptr: *mut *const c_void,
+    out_shape_ptr: *mut usize,
+    out_ndim: *mut usize,
+    out_dtype_char: *mut c_char,
+    out_strides_ptr: *mut isize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let desc = || {
+        let Event::Input { data, metadata, .. } = event else {
+            eyre::bail!("event is not an input event");
+        };
+        let dtype_char = match data.data_type() {
+            DataType::Int8 => b'b',
+            DataType::Int16 => b'h',
+            DataType::Int32 => b'i',
+            DataType::Int64 => b'q',
+            DataType::UInt8 => b'B',
+            DataType::UInt16 => b'H',
+            DataType::UInt32 => b'I',
+            DataType::UInt64 => b'Q',
+            DataType::Float16 => b'e',
+            DataType::Float32 => b'f',
+            DataType::Float64 => b'd',
+            other => eyre::bail!("data type {other} has no NumPy equivalent"),
+        };
+        let shape: Vec<usize> = match metadata.parameters.custom.get(SHAPE_PARAMETER) {
+            Some(Parameter::ListInt(shape)) => shape
+                .iter()
+                .map(|&d| usize::try_from(d))
+                .collect::<Result<_, _>>()
+                .wrap_err("shape must not be negative")?,
+            _ => vec![data.len()],
+        };
+        if shape.is_empty() || shape.len() > DORA_MAX_NDIM {
+            eyre::bail!("shape {shape:?} must have between 1 and {DORA_MAX_NDIM} dimensions");
+        }
+        if shape.iter().product::<usize>() != data.len() {
+            eyre::bail!(
+                "shape {shape:?} does not match the {} input values",
+                data.len()
+            );
+        }
+        let item_size = data.data_type().primitive_width().unwrap_or(1);
+        let mut strides = vec![0; shape.len()];
+        let mut stride = item_size;
+        for (s, dim) in strides.iter_mut().zip(&shape).rev() {
+            *s = stride as isize;
+            stride *= dim;
+        }
+        let data_ptr = with_value_bytes(&data.0, |bytes| bytes.as_ptr())?;
+        Ok((data_ptr, shape, dtype_char, strides))
+    };
+    match desc() {
+        Ok((data_ptr, shape, dtype_char, strides)) => {
+            unsafe {
+                *out_data_ptr = data_ptr.cast();
+                *out_ndim = shape.len();
+                *out_dtype_char = dtype_char as c_char;
+                ptr::copy_nonoverlapping(shape.as_ptr(), out_shape_ptr, shape.len());
+                ptr::copy_nonoverlapping(strides.as_ptr(), out_strides_ptr, strides.len());
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe {
+                *out_data_ptr = ptr::null();
+                *out_ndim = 0;
+            }
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Copies the values of the given input event into a newly allocated buffer.
+///
+/// Normalizes the input so that C code doesn't need to handle the different arrow
+/// layouts: sliced arrays are copied starting at their offset, dictionary-encoded
+/// arrays are unpacked into their values, and null values are replaced by zero.
+/// The resulting buffer is contiguous and aligned to 8 bytes.
+///
+/// Writes the start pointer of the buffer to `out_ptr`, the number of elements to
+/// `out_len`, and the element type to `out_dtype`. The pointer needs to be cast to
+/// the element type indicated by `out_dtype`. `Null` inputs and empty inputs are
+/// represented by a null pointer.
+///
+/// The caller owns the buffer and must free it through [`dora_free_buffer`],
+/// passing the values written to `out_len` and `out_dtype`. Unlike the other
+/// `read_dora_input_*` functions, the buffer stays valid after the event is freed.
+///
+/// Returns `0` on success. Returns `-1` if the event is not an input event or if the
+/// (unpacked) input values are not of a type listed in [`DoraDataType`]. In this
+/// case, a null pointer is written to `out_ptr` and nothing needs to be freed.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_ptr`,
+/// `out_len`, and `out_dtype` arguments must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn dora_materialize_input(
+    event: *const (),
+    out_ptr: *mut *mut c_void,
+    out_len: *mut usize,
+    out_dtype: *mut DoraDataType,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match input_data(event).and_then(materialize) {
+        Ok((bytes, len, dtype)) => {
+            let buffer = if bytes.is_empty() {
+                ptr::null_mut()
+            } else {
+                let layout = buffer_layout(bytes.len());
+                let buffer = unsafe { std::alloc::alloc(layout) };
+                if buffer.is_null() {
+                    std::alloc::handle_alloc_error(layout);
+                }
+                unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), buffer, bytes.len()) };
+                buffer
+            };
+            unsafe {
+                *out_ptr = buffer.cast();
+                *out_len = len;
+                *out_dtype = dtype;
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe {
+                *out_ptr = ptr::null_mut();
+                *out_len = 0;
+                *out_dtype = DoraDataType::Unknown;
+            }
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Frees a buffer returned by [`dora_materialize_input`].
+///
+/// ## Safety
+///
+/// The `ptr`, `len`, and `dtype` arguments must be the values written by a
+/// successful [`dora_materialize_input`] call. The buffer must be freed exactly
+/// once and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn dora_free_buffer(ptr: *mut c_void, len: usize, dtype: DoraDataType) {
+    let Some(width) = dtype.byte_width() else {
+        return;
+    };
+    if !ptr.is_null() && len * width > 0 {
+        unsafe { std::alloc::dealloc(ptr.cast(), buffer_layout(len * width)) };
+    }
+}
+
+/// Returns the values of the given array as contiguous, fully valid bytes, together
+/// with the number of elements and their data type.
+fn materialize(array: &ArrayRef) -> eyre::Result<(Vec<u8>, usize, DoraDataType)> {
+    let array = match array.data_type() {
+        DataType::Dictionary(_, value_type) => {
+            cast(array, value_type).wrap_err("failed to unpack dictionary-encoded input")?
+        }
+        _ => array.clone(),
+    };
+    let dtype = DoraDataType::from(array.data_type());
+    let width = dtype
+        .byte_width()
+        .ok_or_else(|| eyre::eyre!("data type {} is not supported", array.data_type()))?;
+    if width == 0 {
+        return Ok((Vec::new(), array.len(), dtype));
+    }
+    let mut bytes = with_value_bytes(&array, <[u8]>::to_vec)?;
+    if array.null_count() > 0 {
+        for (index, value) in bytes.chunks_exact_mut(width).enumerate() {
+            if array.is_null(index) {
+                value.fill(0);
+            }
+        }
+    }
+    Ok((bytes, array.len(), dtype))
+}
+
+/// Layout of the buffers allocated by [`dora_materialize_input`].
+fn buffer_layout(size: usize) -> std::alloc::Layout {
+    std::alloc::Layout::from_size_align(size, 8).expect("buffer size overflows")
+}
+
+/// Reads out the raw value bytes of the given input event without copying them.
+///
+/// Writes the start pointer and the length in bytes of the input values to
+/// `out_ptr` and `out_len`. If the sender placed the message in shared memory and
+/// the input is mapped directly into this node, `out_is_shm` is set to `true`. In
+/// this case, the bytes can be processed in place, without any copy. Otherwise,
+/// `out_is_shm` is set to `false` and the pointer refers to a regular heap buffer
+/// of this process. Senders use shared memory for messages of at least
+/// `ZERO_COPY_THRESHOLD` bytes if the receiver runs on the same machine.
+///
+/// The returned bytes are only valid until the event is freed through
+/// [`free_dora_event`]. They must not be modified.
+///
+/// Returns `0` on success. Returns `-1` if the event is not an input event or if the
+/// input is not an array of fixed-width values (e.g. strings or lists). Use the
+/// regular `read_dora_input_*` functions for such inputs.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_ptr`,
+/// `out_len`, and `out_is_shm` arguments must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_shm_region(
+    event: *const (),
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+    out_is_shm: *mut bool,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let bytes = input_data(event).and_then(|data| {
+        // the bytes point into the array buffer, which is kept alive by the event
+        with_value_bytes(data, |bytes| {
+            (
+                bytes.as_ptr(),
+                bytes.len(),
+                MappedInputData::is_mapped(bytes),
+            )
+        })
+    });
+    match bytes {
+        Ok((ptr, len, is_shm)) => {
+            unsafe {
+                *out_ptr = ptr;
+                *out_len = len;
+                *out_is_shm = is_shm;
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+                *out_is_shm = false;
+            }
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Checks whether the data of the given input event is mapped from shared memory.
+///
+/// Returns `1` if the sender placed the message in shared memory and this node
+/// reads it in place, without any copy. This is the case for large messages (at
+/// least `ZERO_COPY_THRESHOLD` bytes) sent by nodes on the same machine. Holding on
+/// to such an input keeps the shared memory region of the sender occupied, so nodes
+/// that need the data for longer should copy it out instead.
+///
+/// Returns `0` if the data was sent inline, i.e. copied into a message by the
+/// transport (e.g. small messages or messages from other machines), and `-1` if the
+/// event is not an input event. In both cases that return `0` or `1`, the data stays
+/// valid until the event is freed through [`free_dora_event`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_input_is_zero_copy(event: *const ()) -> i32 {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match input_data(event) {
+        Ok(data) => is_mapped_array(&data.to_data()).into(),
+        Err(_) => -1,
+    }
+}
+
+/// Reads out the capacity of the buffer that holds the values of the given input.
+///
+/// Writes the number of bytes from the first value of the input to the end of the
+/// underlying buffer to `out_capacity`. This is at least the used length, i.e.
+/// the number of values times their size, and larger if the input only covers part
+/// of the buffer, e.g. because the sender placed the data in a larger buffer. C nodes
+/// that transform data in place can use this to check whether the results fit into
+/// the existing buffer. Only inputs with fixed-width values (e.g. `Float32`) are
+/// supported.
+///
+/// Writing to the buffer is only safe if the node owns it exclusively. This is
+/// never the case for inputs that are mapped from shared memory (see
+/// [`dora_input_is_zero_copy`]), which are mapped read-only. Other inputs are only
+/// owned exclusively if no other reference to the data exists, e.g. through
+/// [`dora_clone_event_reader`], [`dora_enable_input_history`], or
+/// [`dora_latest_as_struct`].
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_capacity(
+    event: *const (),
+    out_capacity: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let capacity = input_data(event).and_then(|data| {
+        let data = data.to_data();
+        let width = data
+            .data_type()
+            .primitive_width()
+            .filter(|_| data.buffers().len() == 1 && data.child_data().is_empty())
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "data type {} is not supported, expected fixed-width values",
+                    data.data_type()
+                )
+            })?;
+        let buffer = &data.buffers()[0];
+        // the offset of the buffer in its allocation is not exposed by arrow, so the
+        // space after the end of the buffer can't be reported safely
+        let start = data.offset() * width;
+        Ok(buffer.len().saturating_sub(start))
+    });
+    match capacity {
+        Ok(capacity) => {
+            unsafe { *out_capacity = capacity };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Checks whether any buffer of the given array lies in mapped shared memory.
+fn is_mapped_array(data: &ArrayData) -> bool {
+    data.buffers()
+        .iter()
+        .any(|buffer| MappedInputData::is_mapped(buffer.as_slice()))
+        || data.child_data().iter().any(is_mapped_array)
+}
+
+/// Returns the alignment of the values of the given input event, in bytes.
+///
+/// The alignment refers to the pointer returned by the `read_dora_input_data_*`
+/// functions and by [`read_dora_input_shm_region`]. It is the largest power of two
+/// that divides the address of the first value, capped at `4096`. C nodes can use it
+/// to choose between aligned and unaligned SIMD loads, e.g. aligned AVX loads
+/// require an alignment of at least `32`.
+///
+/// Dora only guarantees that values are aligned to their own size (e.g. `4` for
+/// `float` values). Input data that is received through shared memory or over the
+/// network is stored in a single message buffer, in which each arrow buffer is
+/// only padded to the alignment of its element type. Sliced arrays start at an
+/// offset into their buffer, which might reduce the alignment further. The larger
+/// 64-byte alignment of arrow only holds for buffers allocated by arrow itself, so
+/// it should not be assumed.
+///
+/// Returns `0` if the event is not an input event or if the input is not an array of
+/// fixed-width values.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_alignment(event: *const ()) -> usize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let alignment = input_data(event)
+        .and_then(|data| with_value_bytes(data, |bytes| alignment(bytes.as_ptr())));
+    match alignment {
+        Ok(alignment) => alignment,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            0
+        }
+    }
+}
+
+/// Returns the largest power of two that divides the given address, up to the
+/// page size of `4096`.
+fn alignment(ptr: *const u8) -> usize {
+    1 << (ptr as usize).trailing_zeros().min(12)
+}
+
+/// Calls the given function with the bytes of the values of the given fixed-width
+/// array, e.g. a primitive array.
+fn with_value_bytes<R>(array: &ArrayRef, f: impl FnOnce(&[u8]) -> R) -> eyre::Result<R> {
+    let data = array.to_data();
+    let width = data
+        .data_type()
+        .primitive_width()
+        .filter(|_| data.buffers().len() == 1 && data.child_data().is_empty())
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "data type {} is not supported, expected fixed-width values",
+                data.data_type()
+            )
+        })?;
+    let values = data.buffers()[0].as_slice();
+    let range = data.offset() * width..(data.offset() + data.len()) * width;
+    let bytes = values
+        .get(range)
+        .ok_or_else(|| eyre::eyre!("input values are out of bounds"))?;
+    Ok(f(bytes))
+}
+
+/// Reads out the null bitmap of the given input event.
+///
+/// Writes one byte per value of the input to `out_validity_ptr`: `1` if the value is
+/// valid and `0` if it is null. Inputs without null bitmap are reported as fully
+/// valid. See [`dora_send_output_f32_nullable`] for sending arrays with nulls.
+///
+/// Returns the number of null values on success. Returns `-1` if the event is not an
+/// input event or if the input has more than `len` values. The number of values is
+/// reported by the `read_dora_input_data_*` functions, e.g. [`read_dora_input_data_f32`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_validity_ptr`
+/// argument must point to an array of at least `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_validity(
+    event: *const (),
+    out_validity_ptr: *mut u8,
+    len: usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let Event::Input { data, .. } = event else {
+        return -1;
+    };
+    if data.len() > len {
+        return -1;
+    }
+    let out = unsafe { slice::from_raw_parts_mut(out_validity_ptr, data.len()) };
+    for (i, valid) in out.iter_mut().enumerate() {
+        *valid = u8::from(data.is_valid(i));
+    }
+    data.null_count() as isize
+}
+
+/// Checks whether the given input event is the first input received for its ID.
+///
+/// Returns `1` if no input with the same ID was received before (or since the last
+/// [`dora_reset_input_first`] call for this ID), `0` if the ID was seen before, and
+/// `-1` if the given event is not an input event. This is useful for edge-triggered
+/// logic that should only react to the first arrival of an input.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_is_first(event: *const ()) -> i32 {
+    let DoraEvent {
+        event, is_first, ..
+    } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { .. } => i32::from(*is_first),
+        _ => -1,
+    }
+}
+
+/// Checks whether the data type of the given input event differs from the data
+/// type of the previous input with the same ID.
+///
+/// Returns `1` if the data type changed, `0` if it is the same or if this is the
+/// first input with this ID, and `-1` if the given event is not an input event.
+/// A changed data type usually indicates a misconfigured upstream node. Checking
+/// for it before calling a typed reader such as [`read_dora_input_data_f32`]
+/// allows nodes to report the misconfiguration instead of failing in the reader.
+/// The data type name is available through [`read_dora_input_data_type_name`].
+///
+/// Events of readers created through [`dora_clone_event_reader`] always report
+/// `0`.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_type_changed(event: *const ()) -> i32 {
+    let DoraEvent {
+        event,
+        type_changed,
+        ..
+    } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { .. } => i32::from(*type_changed),
+        _ => -1,
+    }
+}
+
+/// Resets the "first input" state of the given input ID.
+///
+/// The next input event with this ID will be reported as first input again by
+/// [`read_dora_input_is_first`].
+///
+/// Returns a [`DoraStatus`] code, [`DoraStatus::InvalidUtf8Id`] if the given ID is
+/// not valid UTF-8.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_reset_input_first(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    match unsafe { read_id(id_ptr, id_len) } {
+        Ok(id) => {
+            context.seen_inputs.remove(&id);
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Reads out the data of the given `UInt8` input event.
+///
+/// If the sender marked the input as compressed through the
+/// [`COMPRESSION_PARAMETER`], the data is decompressed transparently and the
+/// decompressed bytes are returned. They are stored in the event, so the returned
+/// pointer stays valid until the event is freed. If decompression fails, a null
+/// pointer and length `0` are written and the error is available through
+/// [`dora_last_error`].
+///
+/// Returns a [`DoraStatus`] code. Fails if the length of the input doesn't fit
+/// into the address space of the platform, which can happen on 32-bit targets,
+/// or if it exceeds the received data. Events without data (e.g. `Stop` events)
+/// are reported as null pointer with length `0`. Byte blobs that are sent as
+/// `Binary` or `LargeBinary` arrays can be read through
+/// [`read_dora_input_data_binary`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_u8(
+    event: *const (),
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent {
+        event,
+        decompressed,
+        ..
+    } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
+            dora_node_api::arrow::datatypes::DataType::UInt8
+                if metadata
+                    .parameters
+                    .custom
+                    .contains_key(COMPRESSION_PARAMETER) =>
+            {
+                let values = match decompressed.get() {
+                    Some(values) => Ok(values),
+                    None => decompress(metadata, data.as_primitive::<UInt8Type>().values())
+                        .map(|values| decompressed.get_or_init(|| values)),
+                };
+                unsafe { write_slice(values.map(Vec::as_slice), out_ptr, out_len) }
+            }
+            dora_node_api::arrow::datatypes::DataType::UInt8 => {
+                let array: &UInt8Array = data.as_primitive();
+                let values = checked_values(array.values(), metadata);
+                unsafe { write_slice(values, out_ptr, out_len) }
+            }
+            dora_node_api::arrow::datatypes::DataType::Null => {
+                unsafe {
+                    *out_ptr = ptr::null();
+                    *out_len = 0;
+                }
+                DoraStatus::Ok as isize
+            }
+            _ => {
+                panic!("You used {}, must use U8!", metadata.type_info.data_type);
+            }
+        },
+        _ => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            DoraStatus::Ok as isize
+        }
+    }
+}
+
+/// Decompresses the given input data according to its [`COMPRESSION_PARAMETER`].
+fn decompress(metadata: &Metadata, data: &[u8]) -> eyre::Result<Vec<u8>> {
+    match metadata.parameters.custom.get(COMPRESSION_PARAMETER) {
+        Some(Parameter::String(algorithm)) if algorithm == "lz4" => {
+            lz4_flex::decompress_size_prepended(data).wrap_err("failed to decompress LZ4 input")
+        }
+        Some(Parameter::String(algorithm)) if algorithm == "zstd" => {
+            zstd::decode_all(data).wrap_err("failed to decompress Zstandard input")
+        }
+        Some(other) => eyre::bail!("unsupported compression {other:?}"),
+        None => Ok(data.to_vec()),
+    }
+}
+
+/// Returns the first `metadata.type_info.len` elements of the given values.
+///
+/// Fails if the length reported in the metadata exceeds the received values or if
+/// the values don't fit into the address space, which is limited to `isize::MAX`
+/// bytes (i.e. 2 GiB on 32-bit targets). This way, the data readers never report
+/// a length that is truncated or points past the end of the input.
+fn checked_values<'a, T>(values: &'a [T], metadata: &Metadata) -> eyre::Result<&'a [T]> {
+    let len = metadata.type_info.len;
+    let fits = len
+        .checked_mul(std::mem::size_of::<T>())
+        .is_some_and(|bytes| bytes <= isize::MAX as usize);
+    if !fits {
+        eyre::bail!("input length {len} exceeds the address space of this platform");
+    }
+    values.get(..len).ok_or_else(|| {
+        eyre::eyre!(
+            "input length {len} exceeds the {} received values",
+            values.len()
+        )
+    })
+}
+
+/// Reads out the data of the given `Int32` input event.
+///
+/// Works like [`read_dora_input_data_u8`], including the length checks, but
+/// without decompression.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_data_u8`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_i32(
+    event: *const (),
+    out_ptr: *mut *const i32,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match event {
+        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
+            dora_node_api::arrow::datatypes::DataType::Int32 => {
+                let array: &Int32Array = data.as_primitive();
+                let values = checked_values(array.values(), metadata);
+                unsafe { write_slice(values, out_ptr, out_len) }
+            }
+            dora_node_api::arrow::datatypes::DataType::Null => {
+                unsafe {
+                    *out_ptr = ptr::null();
+                    *out_len = 0;
+                }
+                DoraStatus::Ok as isize
+            }
+            _ => {
+                panic!("You used {}, must use Int32!", metadata.type_info.data_type);
+            }
+        },
+        _ => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            DoraStatus::Ok as isize
+        }
+    }
+}
+
+/// Reads out the data of the given `Float32` input event.
+///
+/// Works like [`read_dora_input_data_u8`], including the length checks, but
+/// without decompression.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_data_u8`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_f32(
+    event: *const (),
+    out_ptr: *mut *const f32,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+
+    match event {
+        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
+            dora_node_api::arrow::datatypes::DataType::Float32 => {
+                let array: &Float32Array = data.as_primitive();
+                let values = checked_values(array.values(), metadata);
+                unsafe { write_slice(values, out_ptr, out_len) }
+            }
+            dora_node_api::arrow::datatypes::DataType::Null => {
+                unsafe {
+                    *out_ptr = ptr::null();
+                    *out_len = 0;
+                }
+                DoraStatus::Ok as isize
+            }
+            _ => {
+                panic!(
+                    "You used {}, must use Float32!",
+                    metadata.type_info.data_type
+                );
+            }
+        },
+        _ => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            DoraStatus::Ok as isize
+        }
+    }
+}
+
+/// Reads out the data of the given `Float32` input event as tensor with the given
+/// shape.
+///
+/// The shape is given as `requested_ndim` dimension sizes at `requested_dims_ptr`,
+/// in row-major order. The data is not moved: on success, the start pointer of the
+/// flat values is written to `out_ptr`. This allows nodes to state the expected
+/// shape of an input at the read site, even if the sender does not attach it as
+/// [`SHAPE_PARAMETER`], so that mismatches are detected early.
+///
+/// Returns a [`DoraStatus`] code. Fails if the event is not a `Float32` input or if
+/// the product of the requested dimensions does not match the number of values. On
+/// error, a null pointer is written to `out_ptr` and the error is stored as last
+/// error (see [`dora_last_error`]).
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The
+/// `requested_dims_ptr` argument must point to `requested_ndim` values. The
+/// returned pointer must not be used after freeing the event.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_f32_reshaped(
+    event: *const (),
+    requested_dims_ptr: *const usize,
+    requested_ndim: usize,
+    out_ptr: *mut *const f32,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let dims = unsafe { slice::from_raw_parts(requested_dims_ptr, requested_ndim) };
+    let reshaped = || {
+        let Event::Input { data, metadata, .. } = event else {
+            eyre::bail!("event is not an input event");
+        };
+        let Some(array) = data.as_primitive_opt::<Float32Type>() else {
+            eyre::bail!("expected Float32 input, got {}", data.data_type());
+        };
+        let values = checked_values(array.values(), metadata)?;
+        let requested = dims
+            .iter()
+            .try_fold(1usize, |product, &dim| product.checked_mul(dim));
+        if requested != Some(values.len()) {
+            eyre::bail!(
+                "requested shape {dims:?} does not match the {} input values",
+                values.len()
+            );
+        }
+        Ok(values.as_ptr())
+    };
+    match reshaped() {
+        Ok(ptr) => {
+            unsafe { *out_ptr = ptr };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe { *out_ptr = ptr::null() };
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Reads out the data of the given `UInt64` input event.
+///
+/// Works like [`read_dora_input_data_u8`], including the length checks, but
+/// without decompression.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_data_u8`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_u64(
+    event: *const (),
+    out_ptr: *mut *const u64,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+
+    match event {
+        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
+            dora_node_api::arrow::datatypes::DataType::UInt64 => {
+                let array: &UInt64Array = data.as_primitive();
+                let values = checked_values(array.values(), metadata);
+                unsafe { write_slice(values, out_ptr, out_len) }
+            }
+            dora_node_api::arrow::datatypes::DataType::Null => {
+                unsafe {
+                    *out_ptr = ptr::null();
+                    *out_len = 0;
+                }
+                DoraStatus::Ok as isize
+            }
+            _ => {
+                panic!(
+                    "You used {}, must use UInt64!",
+                    metadata.type_info.data_type
+                );
+            }
+        },
+        _ => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            DoraStatus::Ok as isize
+        }
+    }
+}
+
+/// Returns the number of elements of the given `Binary` or `LargeBinary` input.
+///
+/// Each element is a variable-length byte blob, which can be read through
+/// [`read_dora_input_data_binary`]. Returns `-1` if the event is not an input event
+/// or if the input is not a binary array.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_binary_count(event: *const ()) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    match input_data(event) {
+        Ok(data) if matches!(data.data_type(), DataType::Binary | DataType::LargeBinary) => {
+            data.len() as isize
+        }
+        _ => -1,
+    }
+}
+
+/// Reads out the bytes of element `index` of the given `Binary` or `LargeBinary`
+/// input.
+///
+/// Binary arrays store a list of variable-length byte blobs, e.g. serialized
+/// protobuf messages or compressed images. Both the variant with 32-bit offsets
+/// (`Binary`) and with 64-bit offsets (`LargeBinary`) are supported. The number of
+/// elements is returned by [`read_dora_input_binary_count`]. Null elements are
+/// reported with length `0`, see [`read_dora_input_validity`] for telling them
+/// apart from empty blobs.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0`
+/// if the event is not an input event, if the input is not a binary array, or if
+/// `index` is out of bounds.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_data_binary(
+    event: *const (),
+    index: usize,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let value = input_data(event).and_then(|data| {
+        if index >= data.len() {
+            eyre::bail!(
+                "index {index} is out of bounds for binary input with {} elements",
+                data.len()
+            );
+        }
+        match data.data_type() {
+            DataType::Binary => Ok(data.as_binary::<i32>().value(index)),
+            DataType::LargeBinary => Ok(data.as_binary::<i64>().value(index)),
+            other => eyre::bail!("input has data type {other}, expected Binary or LargeBinary"),
+        }
+    });
+    unsafe { write_slice(value, out_ptr, out_len) }
+}
+
+/// Reads out the value of the given `Float32` input that consists of a single element.
+///
+/// Returns `0` on success. Returns `-1` if the event is not an input event, if the
+/// input is not a `Float32` array, or if it doesn't have exactly one element.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_scalar_f32(
+    event: *const (),
+    out_value: *mut f32,
+) -> isize {
+    unsafe { read_scalar::<Float32Type>(event, out_value) }
+}
+
+/// Reads out the value of the given `Int32` input that consists of a single element.
+///
+/// See [`read_dora_input_scalar_f32`] for details.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_scalar_f32`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_scalar_i32(
+    event: *const (),
+    out_value: *mut i32,
+) -> isize {
+    unsafe { read_scalar::<Int32Type>(event, out_value) }
+}
+
+/// Reads out the value of the given `Float64` input that consists of a single element.
+///
+/// See [`read_dora_input_scalar_f32`] for details.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_scalar_f32`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_scalar_f64(
+    event: *const (),
+    out_value: *mut f64,
+) -> isize {
+    unsafe { read_scalar::<Float64Type>(event, out_value) }
+}
+
+/// Reads out the value of the given `UInt64` input that consists of a single element.
+///
+/// See [`read_dora_input_scalar_f32`] for details.
+///
+/// ## Safety
+///
+/// See [`read_dora_input_scalar_f32`].
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_scalar_u64(
+    event: *const (),
+    out_value: *mut u64,
+) -> isize {
+    unsafe { read_scalar::<UInt64Type>(event, out_value) }
+}
+
+/// Writes the single value of the given input event to `out_value`.
+///
+/// ## Safety
+///
+/// The `event` argument must be a valid dora event and `out_value` must be valid
+/// for writes.
+unsafe fn read_scalar<T: ArrowPrimitiveType>(event: *const (), out_value: *mut T::Native) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let value = input_data(event).and_then(|data| {
+        let array = data.as_primitive_opt::<T>().ok_or_else(|| {
+            eyre::eyre!(
+                "input has data type {}, expected {}",
+                data.data_type(),
+                T::DATA_TYPE
+            )
+        })?;
+        match array.len() {
+            1 => Ok(array.value(0)),
+            len => Err(eyre::eyre!(
+                "input has {len} elements, expected a single value"
+            )),
+        }
+    });
+    match value {
+        Ok(value) => {
+            unsafe { *out_value = value };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Sliding window over the last values of a scalar input, see
+/// [`dora_scalar_filter_new`].
+#[derive(Debug)]
+struct ScalarFilter {
+    window: usize,
+    /// The values in the window, oldest first.
+    values: VecDeque<f64>,
+}
+
+impl ScalarFilter {
+    fn push(&mut self, value: f64) {
+        if self.values.len() == self.window {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.values.iter().sum();
+        Some(sum / self.values.len() as f64)
+    }
+
+    fn min(&self) -> Option<f64> {
+        self.values.iter().copied().reduce(f64::min)
+    }
+
+    fn max(&self) -> Option<f64> {
+        self.values.iter().copied().reduce(f64::max)
+    }
+}
+
+/// Creates a filter that computes statistics over the last `window` values of a
+/// scalar input.
+///
+/// Values are added through [`dora_scalar_filter_push`] or
+/// [`dora_scalar_filter_push_f32`], e.g. after reading them through
+/// [`read_dora_input_scalar_f32`]. Once the window is full, each new value replaces
+/// the oldest one. The statistics over the values in the window are available
+/// through [`dora_scalar_filter_mean`], [`dora_scalar_filter_min`], and
+/// [`dora_scalar_filter_max`]. Values are stored as `f64`, so `f32` values are
+/// represented exactly.
+///
+/// Returns a null pointer if `window` is `0`. The returned filter must be freed
+/// through [`free_dora_scalar_filter`].
+#[no_mangle]
+pub extern "C" fn dora_scalar_filter_new(window: usize) -> *mut c_void {
+    if window == 0 {
+        set_last_error("window must not be 0".into());
+        return ptr::null_mut();
+    }
+    let filter = ScalarFilter {
+        window,
+        values: VecDeque::with_capacity(window),
+    };
+    Box::into_raw(Box::new(filter)).cast()
+}
+
+/// Adds the given value to the filter, replacing the oldest value if the window is
+/// full.
+///
+/// ## Safety
+///
+/// The `filter` argument must be a filter created through [`dora_scalar_filter_new`]
+/// that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_scalar_filter_push(filter: *mut c_void, value: f64) {
+    let filter: &mut ScalarFilter = unsafe { &mut *filter.cast() };
+    filter.push(value);
+}
+
+/// Works like [`dora_scalar_filter_push`], but for `f32` values.
+///
+/// ## Safety
+///
+/// See [`dora_scalar_filter_push`].
+#[no_mangle]
+pub unsafe extern "C" fn dora_scalar_filter_push_f32(filter: *mut c_void, value: f32) {
+    let filter: &mut ScalarFilter = unsafe { &mut *filter.cast() };
+    filter.push(value.into());
+}
+
+/// Writes the mean of the values in the window to `out_value`.
+///
+/// Returns a [`DoraStatus`] code. Fails if no value was pushed yet.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `filter` argument
+/// must be a filter created through [`dora_scalar_filter_new`] that was not freed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_scalar_filter_mean(
+    filter: *const c_void,
+    out_value: *mut f64,
+) -> isize {
+    let filter: &ScalarFilter = unsafe { &*filter.cast() };
+    unsafe { write_statistic(filter.mean(), out_value) }
+}
+
+/// Writes the minimum of the values in the window to `out_value`.
+///
+/// Returns a [`DoraStatus`] code. Fails if no value was pushed yet. `NaN` values
+/// are ignored unless all values are `NaN`.
+///
+/// ## Safety
+///
+/// See [`dora_scalar_filter_mean`].
+#[no_mangle]
+pub unsafe extern "C" fn dora_scalar_filter_min(
+    filter: *const c_void,
+    out_value: *mut f64,
+) -> isize {
+    let filter: &ScalarFilter = unsafe { &*filter.cast() };
+    unsafe { write_statistic(filter.min(), out_value) }
+}
+
+/// Writes the maximum of the values in the window to `out_value`.
+///
+/// Returns a [`DoraStatus`] code. Fails if no value was pushed yet. `NaN` values
+/// are ignored unless all values are `NaN`.
+///
+/// ## Safety
+///
+/// See [`dora_scalar_filter_mean`].
+#[no_mangle]
+pub unsafe extern "C" fn dora_scalar_filter_max(
+    filter: *const c_void,
+    out_value: *mut f64,
+) -> isize {
+    let filter: &ScalarFilter = unsafe { &*filter.cast() };
+    unsafe { write_statistic(filter.max(), out_value) }
+}
+
+/// Writes the given statistic of a [`ScalarFilter`] to `out_value`.
+///
+/// ## Safety
+///
+/// The `out_value` argument must be valid for writes.
+unsafe fn write_statistic(value: Option<f64>, out_value: *mut f64) -> isize {
+    match value {
+        Some(value) => {
+            unsafe { *out_value = value };
+            DoraStatus::Ok as isize
+        }
+        None => {
+            set_last_error("scalar filter is empty".into());
+            DoraStatus::Error as isize
+        }
+    }
+}
+
+/// Frees the given scalar filter.
+///
+/// ## Safety
+///
+/// Only pointers created through [`dora_scalar_filter_new`] are allowed as
+/// arguments. Each filter must be freed exactly once. After freeing, the pointer
+/// must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_scalar_filter(filter: *mut c_void) {
+    let _: Box<ScalarFilter> = unsafe { Box::from_raw(filter.cast()) };
+}
+
+/// Reads out the offsets of the given `List` input.
+///
+/// A list array stores the values of all lists in a single flat values array, which
+/// can be read through the `read_dora_input_list_values_*` functions. The
+/// list at index `i` consists of the values in the range
+/// `offsets[i]..offsets[i + 1]`. Thus, `out_len` is the number of lists plus one.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0`
+/// if the event is not an input event or if the input is not a `List` array.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_list_offsets(
+    event: *const (),
+    out_ptr: *mut *const i32,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let offsets = input_data(event).and_then(|data| match data.data_type() {
+        DataType::List(_) => Ok(data.as_list::<i32>().value_offsets()),
+        other => eyre::bail!("input has data type {other}, expected List"),
+    });
+    unsafe { write_slice(offsets, out_ptr, out_len) }
+}
+
+/// Reads out the flat `Float32` values of the given `List` input.
+///
+/// Use [`read_dora_input_list_offsets`] to split the values into the individual
+/// lists.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0`
+/// if the event is not an input event or if the input is not a list of `Float32`
+/// values.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_list_values_f32(
+    event: *const (),
+    out_ptr: *mut *const f32,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let values = input_data(event).and_then(|data| match data.data_type() {
+        DataType::List(field) if field.data_type() == &DataType::Float32 => {
+            let values: &Float32Array = data.as_list::<i32>().values().as_primitive();
+            Ok(&values.values()[..])
+        }
+        other => eyre::bail!("input has data type {other}, expected List of Float32"),
+    });
+    unsafe { write_slice(values, out_ptr, out_len) }
+}
+
+/// Converts the `Float64` data of the given input event to `f32` values.
+///
+/// Writes the converted values into the caller-provided buffer `out_buf`, which has
+/// room for `cap` values, and sets `out_written` to the number of written values.
+/// The conversion rounds each value to the nearest `f32`, so precision is lost for
+/// values that are not exactly representable as `f32`. Values outside of the `f32`
+/// range become positive or negative infinity.
+///
+/// Returns `0` on success. Returns `-1` if the event is not an input event or if
+/// the input is not a `Float64` array. If the buffer is too small, nothing is
+/// written, `out_written` is set to the required capacity, and `-1` is returned.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `out_buf` argument
+/// must be valid for writes of `cap` values.
+#[no_mangle]
+pub unsafe extern "C" fn dora_downcast_f64_to_f32(
+    event: *const (),
+    out_buf: *mut f32,
+    cap: usize,
+    out_written: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let values = input_data(event).and_then(|data| match data.data_type() {
+        DataType::Float64 => Ok(data.as_primitive::<Float64Type>().values()),
+        other => eyre::bail!("input has data type {other}, expected Float64"),
+    });
+    let values = match values {
+        Ok(values) => values,
+        Err(err) => {
+            unsafe { *out_written = 0 };
+            return send_status(Err(err));
+        }
+    };
+    if values.len() > cap {
+        set_last_error(format!(
+            "buffer too small: capacity is {cap}, but {} values are required",
+            values.len()
+        ));
+        unsafe { *out_written = values.len() };
+        return DoraStatus::Error as isize;
+    }
+
+    let out = unsafe { slice::from_raw_parts_mut(out_buf, values.len()) };
+    for (out, value) in out.iter_mut().zip(values.iter()) {
+        *out = *value as f32;
+    }
+    unsafe { *out_written = values.len() };
+    DoraStatus::Ok as isize
+}
+
+/// Reads out the keys and values of the given dictionary-encoded input.
+///
+/// Dictionary-encoded arrays store each distinct value only once in a values array.
+/// The actual elements are given as keys, i.e. indices into the values array. Only
+/// dictionaries with `Int32` keys are supported.
+///
+/// Writes the `out_keys_ptr` and `out_keys_len` with the start pointer and length of
+/// the keys. Writes `out_values_handle` with a handle to the values array, which can
+/// be read through the `read_dora_array_*` functions, e.g.
+/// [`read_dora_array_string`] for string dictionaries.
+///
+/// Returns `0` on success. Returns `-1` and writes null pointers and length `0` if
+/// the event is not an input event or if the input is not a dictionary array with
+/// `Int32` keys.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_keys_ptr` and `out_values_handle` must not be used after freeing the
+/// `event`, since they point directly into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_dictionary(
+    event: *const (),
+    out_keys_ptr: *mut *const i32,
+    out_keys_len: *mut usize,
+    out_values_handle: *mut *const c_void,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let dictionary = input_data(event).and_then(|data| match data.data_type() {
+        DataType::Dictionary(key_type, _) if key_type.as_ref() == &DataType::Int32 => {
+            Ok(data.as_dictionary::<Int32Type>())
+        }
+        other => eyre::bail!("input has data type {other}, expected Dictionary with Int32 keys"),
+    });
+    let (keys, values_handle) = match dictionary {
+        Ok(dictionary) => (
+            Ok(&dictionary.keys().values()[..]),
+            dictionary.values() as *const ArrayRef as *const c_void,
+        ),
+        Err(err) => (Err(err), ptr::null()),
+    };
+    unsafe { *out_values_handle = values_handle };
+    unsafe { write_slice(keys, out_keys_ptr, out_keys_len) }
+}
+
+/// Reads out the run ends and values of the given run-end encoded input.
+///
+/// Run-end encoded arrays store repeated values only once per run, e.g. for
+/// segmentation masks. Run `i` consists of the value at index `i` of the values
+/// array, repeated up to (excluding) the logical index given by run end `i`. Only
+/// run ends of type `Int32` are supported.
+///
+/// Writes the `out_run_ends_ptr` and `out_run_count` with the start pointer and
+/// number of the run ends. Writes `out_values_handle` with a handle to the values
+/// array, which has one element per run and can be read through the
+/// `read_dora_array_*` functions.
+///
+/// Returns `0` on success. Returns `-1` and writes null pointers and count `0` if
+/// the event is not an input event or if the input is not a run-end encoded array
+/// with `Int32` run ends.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_run_ends_ptr` and `out_values_handle` must not be used after freeing the
+/// `event`, since they point directly into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_ree(
+    event: *const (),
+    out_run_ends_ptr: *mut *const i32,
+    out_run_count: *mut usize,
+    out_values_handle: *mut *const c_void,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let runs = input_data(event).and_then(|data| {
+        let runs = data
+            .as_any()
+            .downcast_ref::<RunArray<Int32Type>>()
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "input has data type {}, expected RunEndEncoded with Int32 run ends",
+                    data.data_type()
+                )
+            })?;
+        if runs.run_ends().offset() != 0 {
+            eyre::bail!("sliced run-end encoded arrays are not supported");
+        }
+        Ok(runs)
+    });
+    let (run_ends, values_handle) = match runs {
+        Ok(runs) => (
+            Ok(runs.run_ends().values()),
+            runs.values() as *const ArrayRef as *const c_void,
+        ),
+        Err(err) => (Err(err), ptr::null()),
+    };
+    unsafe { *out_values_handle = values_handle };
+    unsafe { write_slice(run_ends, out_run_ends_ptr, out_run_count) }
+}
+
+/// Returns the number of key-value entries of the given `Map` input.
+///
+/// The entries can be read through [`read_dora_input_map_entry`]. If the input
+/// holds multiple maps, their entries are counted together, in the order of the
+/// maps. Returns a negative [`DoraStatus`] code if the event is not an input event
+/// or if the input is not a `Map` array.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_map_len(event: *const ()) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    value_status(map_entries(event).map(|(_, entries)| entries.len() as isize))
+}
+
+/// Reads out the key and value of the entry at the given index of a `Map` input.
+///
+/// Map arrays store key-value pairs, e.g. parameters that are sent as data. Keys
+/// must be strings (`Utf8` or `LargeUtf8`). Values must be strings or byte blobs
+/// (`Utf8`, `LargeUtf8`, `Binary`, or `LargeBinary`), so that the value is always
+/// returned as bytes. String values are not null-terminated. Null values are
+/// reported with length `0`. The number of entries is returned by
+/// [`read_dora_input_map_len`].
+///
+/// Returns `0` on success. Returns `-1` and writes null pointers and length `0` if
+/// the event is not an input event, if the input is not a `Map` array with
+/// supported key and value types, or if `index` is out of bounds.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_key_ptr` and `out_val_ptr` must not be used after freeing the `event`,
+/// since they point directly into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_map_entry(
+    event: *const (),
+    index: usize,
+    out_key_ptr: *mut *const u8,
+    out_key_len: *mut usize,
+    out_val_ptr: *mut *const u8,
+    out_val_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let entry = map_entries(event).and_then(|(map, entries)| {
+        let position = entries.start + index;
+        if position >= entries.end {
+            eyre::bail!(
+                "index {index} is out of bounds for map input with {} entries",
+                entries.len()
+            );
+        }
+        match map.keys().data_type() {
+            DataType::Utf8 | DataType::LargeUtf8 => {}
+            other => eyre::bail!("map has key type {other}, expected Utf8"),
+        }
+        Ok((
+            value_bytes(map.keys(), position)?,
+            Some(value_bytes(map.values(), position)?),
+        ))
+    });
+    let (key, value) = match entry {
+        Ok((key, value)) => (Ok(key), value),
+        Err(err) => (Err(err), None),
+    };
+    unsafe {
+        *out_val_ptr = value.map_or(ptr::null(), <[u8]>::as_ptr);
+        *out_val_len = value.map_or(0, <[u8]>::len);
+    }
+    unsafe { write_slice(key, out_key_ptr, out_key_len) }
+}
+
+/// Returns the map array of the given input event and the range of its entries.
+fn map_entries(event: &Event) -> eyre::Result<(&MapArray, std::ops::Range<usize>)> {
+    let data = input_data(event)?;
+    let DataType::Map(..) = data.data_type() else {
+        eyre::bail!("input has data type {}, expected Map", data.data_type());
+    };
+    let map = data.as_map();
+    let offsets = map.value_offsets();
+    let start = offsets.first().copied().unwrap_or(0) as usize;
+    let end = offsets.last().copied().unwrap_or(0) as usize;
+    Ok((map, start..end))
+}
+
+/// Returns the bytes of the string or binary element at the given index.
+fn value_bytes(array: &ArrayRef, index: usize) -> eyre::Result<&[u8]> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(array.as_string::<i32>().value(index).as_bytes()),
+        DataType::LargeUtf8 => Ok(array.as_string::<i64>().value(index).as_bytes()),
+        DataType::Binary => Ok(array.as_binary::<i32>().value(index)),
+        DataType::LargeBinary => Ok(array.as_binary::<i64>().value(index)),
+        other => eyre::bail!("unsupported map value type {other}"),
+    }
+}
+
+/// Reads out which variant the element at the given index of a union input holds.
+///
+/// Union arrays model tagged messages: each element holds a value of one of several
+/// variants, which are identified by their type ID. Writes the type ID of the
+/// element to `out_type_id` and the position of its value in the child array of
+/// this variant to `out_value_offset`. The child array can be read through
+/// [`read_dora_input_union_child`]. Both sparse and dense unions are supported.
+///
+/// Returns `0` on success. Returns `-1` if the event is not an input event, if the
+/// input is not a union array, or if the index is out of bounds.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_union_variant(
+    event: *const (),
+    index: usize,
+    out_type_id: *mut i8,
+    out_value_offset: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let variant = input_union(event).and_then(|union| {
+        if index >= union.len() {
+            eyre::bail!(
+                "index {index} is out of bounds for union of length {}",
+                union.len()
+            );
+        }
+        Ok((union.type_id(index), union.value_offset(index)))
+    });
+    match variant {
+        Ok((type_id, value_offset)) => {
+            unsafe {
+                *out_type_id = type_id;
+                *out_value_offset = value_offset;
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
+    }
+}
+
+/// Reads out the child array of the given variant of a union input.
+///
+/// Writes the field name of the variant to `out_name_ptr` and `out_name_len` and
+/// returns an array handle for the values of the variant. The array handle can be
+/// read through the `read_dora_array_*` functions, e.g. [`read_dora_array_len`].
+/// Returns a null pointer if the event is not an input event, if the input is not
+/// a union array, or if the union has no variant with the given type ID.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned name and
+/// array handle must not be used after freeing the `event`, since they point
+/// directly into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_union_child(
+    event: *const (),
+    type_id: i8,
+    out_name_ptr: *mut *const u8,
+    out_name_len: *mut usize,
+) -> *const c_void {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let variant = input_union(event).and_then(|union| {
+        let DataType::Union(fields, _) = union.data_type() else {
+            unreachable!("union array has data type {}", union.data_type());
+        };
+        let field = fields
+            .iter()
+            .find_map(|(id, field)| (id == type_id).then_some(field))
+            .ok_or_else(|| eyre::eyre!("union has no variant with type ID {type_id}"))?;
+        Ok((field.name().as_bytes(), union.child(type_id)))
+    });
+    let (name, child) = match variant {
+        Ok((name, child)) => (Ok(name), (child as *const ArrayRef).cast()),
+        Err(err) => (Err(err), ptr::null()),
+    };
+    unsafe { write_slice(name, out_name_ptr, out_name_len) };
+    child
+}
+
+fn input_union(event: &Event) -> eyre::Result<&UnionArray> {
+    let data = input_data(event)?;
+    data.as_any()
+        .downcast_ref::<UnionArray>()
+        .ok_or_else(|| eyre::eyre!("input has data type {}, expected Union", data.data_type()))
+}
+
+/// Reads out the detections of the given input, see [`dora_send_detections`].
+///
+/// Writes the number of detections to `out_count`. Writes the start pointer of the
+/// bounding boxes, which contain four `f32` coordinates per detection, to
+/// `out_boxes_ptr` and the start pointer of the `f32` scores to `out_scores_ptr`.
+/// Writes `out_labels_handle` with a handle to the labels, which can be read through
+/// [`read_dora_array_string`].
+///
+/// Returns `0` on success. Returns `-1` and writes null pointers and count `0` if the
+/// event is not an input event or if the input does not have the detection schema
+/// described in [`dora_send_detections`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned pointers
+/// and handle must not be used after freeing the `event`, since they point directly
+/// into the event's memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_detections(
+    event: *const (),
+    out_boxes_ptr: *mut *const f32,
+    out_scores_ptr: *mut *const f32,
+    out_labels_handle: *mut *const c_void,
+    out_count: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let detections = input_data(event).and_then(|data| {
+        let DataType::Struct(_) = data.data_type() else {
+            eyre::bail!("input has data type {}, expected Struct", data.data_type());
+        };
+        let detections = data.as_struct();
+        let column = |name: &str| {
+            detections
+                .column_by_name(name)
+                .ok_or_else(|| eyre::eyre!("detections have no field `{name}`"))
+        };
+        let (bbox, scores, labels) = (
+            column(DETECTION_BBOX_FIELD)?,
+            column(DETECTION_SCORE_FIELD)?,
+            column(DETECTION_LABEL_FIELD)?,
+        );
+        match (bbox.data_type(), scores.data_type(), labels.data_type()) {
+            (DataType::FixedSizeList(item, 4), DataType::Float32, DataType::Utf8)
+                if item.data_type() == &DataType::Float32 => {}
+            other => eyre::bail!("detection fields have unexpected data types {other:?}"),
+        }
+        let bbox = bbox.as_fixed_size_list();
+        let boxes = bbox.values().as_primitive::<Float32Type>().values();
+        let start = if bbox.is_empty() {
+            0
+        } else {
+            bbox.value_offset(0) as usize
+        };
+        let scores = scores.as_primitive::<Float32Type>().values();
+        Ok((
+            boxes[start..].as_ptr(),
+            scores.as_ptr(),
+            labels as *const ArrayRef as *const c_void,
+            detections.len(),
+        ))
+    });
+    let (boxes, scores, labels, count, status) = match detections {
+        Ok((boxes, scores, labels, count)) => (boxes, scores, labels, count, DoraStatus::Ok),
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            (ptr::null(), ptr::null(), ptr::null(), 0, DoraStatus::Error)
+        }
+    };
+    unsafe {
+        *out_boxes_ptr = boxes;
+        *out_scores_ptr = scores;
+        *out_labels_handle = labels;
+        *out_count = count;
+    }
+    status as isize
+}
+
+/// Returns the number of elements of the given array handle.
+///
+/// ## Safety
+///
+/// The `array` argument must be an array handle returned by one of the dora API
+/// functions, e.g. [`read_dora_input_dictionary`]. The event that the handle was
+/// read from must be still valid, i.e., not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_array_len(array: *const c_void) -> usize {
+    let array: &ArrayRef = unsafe { &*array.cast() };
+    array.len()
+}
+
+/// Reads out the string at the given index of the given string array handle.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded string. Both `Utf8` and `LargeUtf8` arrays are supported.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0` if
+/// the array is not a string array or if the index is out of bounds.
+///
+/// ## Safety
+///
+/// The `array` argument must be an array handle returned by one of the dora API
+/// functions, e.g. [`read_dora_input_dictionary`]. The event that the handle was
+/// read from must be still valid, i.e., not freed yet. The returned `out_ptr` must
+/// not be used after freeing this event, since it points directly into the event's
+/// memory.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_array_string(
+    array: *const c_void,
+    index: usize,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let array: &ArrayRef = unsafe { &*array.cast() };
+    let value = if index >= array.len() {
+        Err(eyre::eyre!(
+            "index {index} is out of bounds for array of length {}",
+            array.len()
+        ))
+    } else {
+        match array.data_type() {
+            DataType::Utf8 => Ok(array.as_string::<i32>().value(index).as_bytes()),
+            DataType::LargeUtf8 => Ok(array.as_string::<i64>().value(index).as_bytes()),
+            other => Err(eyre::eyre!("array has data type {other}, expected Utf8")),
+        }
+    };
+    unsafe { write_slice(value, out_ptr, out_len) }
+}
+
+/// Writes the data of the given input event to a parquet file at the given path.
+///
+/// The written file contains a single column, which is named after the input ID and
+/// has the data type of the input. Each element of the input array is stored as one
+/// row. Existing files at the given path are overwritten.
+///
+/// Returns `0` on success and `-1` on error (see [`dora_last_error`]), e.g. if the
+/// event is not an input event or if the file can't be written.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_input_to_parquet(
+    event: *const (),
+    path_ptr: *const u8,
+    path_len: usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let path = unsafe { slice::from_raw_parts(path_ptr, path_len) };
+    let result = std::str::from_utf8(path)
+        .wrap_err("path is not valid UTF-8")
+        .and_then(|path| write_parquet(event, Path::new(path)));
+    send_status(result)
+}
+
+fn write_parquet(event: &Event, path: &Path) -> eyre::Result<()> {
+    let Event::Input { id, data, .. } = event else {
+        eyre::bail!("event is not an input event");
+    };
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        id.clone(),
+        data.data_type().clone(),
+        true,
+    )]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![data.0.clone()])
+        .wrap_err("failed to create record batch")?;
+
+    let file = File::create(path)
+        .wrap_err_with(|| format!("failed to create file `{}`", path.display()))?;
+    let mut writer =
+        ArrowWriter::try_new(file, schema, None).wrap_err("failed to create parquet writer")?;
+    writer
+        .write(&batch)
+        .wrap_err("failed to write record batch")?;
+    writer.close().wrap_err("failed to close parquet writer")?;
+    Ok(())
+}
+
+/// Reads the given file and sends its content on the given output.
+///
+/// Both parquet files and Arrow IPC files (also known as Feather V2 files) are
+/// supported. The format is detected from the magic bytes at the start of the file.
+/// The file must contain exactly one column, e.g. as written by
+/// [`dora_input_to_parquet`]. If the file consists of multiple record batches, they
+/// are concatenated and sent as a single message.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The same applies to
+/// `path_ptr` and `path_len`.
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_arrow_file(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    path_ptr: *const u8,
+    path_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let path = unsafe { slice::from_raw_parts(path_ptr, path_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let path = std::str::from_utf8(path).wrap_err("path is not valid UTF-8")?;
+        let data = read_arrow_file(Path::new(path))?;
+        context.send_output(output_id, Default::default(), data)
+    });
+    send_status(result)
+}
+
+fn read_arrow_file(path: &Path) -> eyre::Result<ArrayRef> {
+    let mut file =
+        File::open(path).wrap_err_with(|| format!("failed to open file `{}`", path.display()))?;
+    let mut magic = [0; 6];
+    file.read_exact(&mut magic)
+        .wrap_err("failed to read file header")?;
+    file.rewind()?;
+
+    let batches: Vec<RecordBatch> = if magic.starts_with(b"PAR1") {
+        ParquetRecordBatchReaderBuilder::try_new(file)
+            .wrap_err("failed to read parquet file")?
+            .build()
+            .wrap_err("failed to read parquet file")?
+            .collect::<Result<_, _>>()
+    } else if &magic == b"ARROW1" {
+        FileReader::try_new(file, None)
+            .wrap_err("failed to read Arrow IPC file")?
+            .collect::<Result<_, _>>()
+    } else {
+        eyre::bail!(
+            "`{}` is neither a parquet nor an Arrow IPC file",
+            path.display()
+        );
+    }
+    .wrap_err("failed to read record batch")?;
+
+    let mut columns = Vec::with_capacity(batches.len());
+    for batch in &batches {
+        if batch.num_columns() != 1 {
+            eyre::bail!("expected a single column, found {}", batch.num_columns());
+        }
+        columns.push(batch.column(0).as_ref());
+    }
+    match columns.as_slice() {
+        [] => eyre::bail!("file contains no record batches"),
+        [column] => Ok(make_array(column.to_data())),
+        columns => concat(columns).wrap_err("failed to concatenate record batches"),
+    }
+}
+
+/// Reads out the data of the given input as a record batch.
+///
+/// Dora messages consist of a single arrow array. If this array is a `Struct` array,
+/// each field of the struct becomes a column of the record batch. Otherwise, the
+/// record batch consists of a single column that is named after the input ID.
+///
+/// Writes a record batch handle to `out_batch_handle`, which can be read through
+/// [`read_dora_record_batch_num_columns`] and [`read_dora_record_batch_column`].
+/// The handle must be freed through [`free_dora_record_batch`]. The columns share
+/// the memory of the input, so no data is copied.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer if the event is
+/// not an input event.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_record_batch(
+    event: *const (),
+    out_batch_handle: *mut *mut c_void,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let batch = match event {
+        Event::Input { id, data, metadata } => match data.data_type() {
+            DataType::Struct(_) => Ok(RecordBatch::from(data.as_struct())),
+            _ => {
+                let field = Field::new(id.clone(), data.data_type().clone(), true)
+                    .with_metadata(schema_metadata(metadata));
+                RecordBatch::try_new(Arc::new(Schema::new(vec![field])), vec![data.0.clone()])
+                    .wrap_err("failed to create record batch")
+            }
+        },
+        _ => Err(eyre::eyre!("event is not an input event")),
+    };
+    match batch {
+        Ok(batch) => {
+            let batch = DoraRecordBatch {
+                schema: batch.schema(),
+                batch,
+            };
+            unsafe { *out_batch_handle = Box::into_raw(Box::new(batch)).cast() };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe { *out_batch_handle = ptr::null_mut() };
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Returns the number of columns of the given record batch handle.
+///
+/// ## Safety
+///
+/// The `batch` argument must be a record batch handle created through
+/// [`read_dora_input_record_batch`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_record_batch_num_columns(batch: *const c_void) -> usize {
+    let DoraRecordBatch { batch, .. } = unsafe { &*batch.cast() };
+    batch.num_columns()
+}
+
+/// Reads out the column at the given index of the given record batch handle.
+///
+/// Writes the name of the column to `out_name_ptr` and `out_name_len` and returns
+/// an array handle for the column data. The array handle can be read through the
+/// `read_dora_array_*` functions, e.g. [`read_dora_array_len`]. Returns a null
+/// pointer if the index is out of bounds.
+///
+/// ## Safety
+///
+/// The `batch` argument must be a record batch handle created through
+/// [`read_dora_input_record_batch`] that was not freed yet. The returned name and
+/// array handle must not be used after freeing the record batch.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_record_batch_column(
+    batch: *const c_void,
+    index: usize,
+    out_name_ptr: *mut *const u8,
+    out_name_len: *mut usize,
+) -> *const c_void {
+    let DoraRecordBatch { batch, schema } = unsafe { &*batch.cast() };
+    let name = schema
+        .fields()
+        .get(index)
+        .map(|field| field.name().as_bytes())
+        .ok_or_else(|| eyre::eyre!("column index {index} is out of bounds"));
+    unsafe { write_slice(name, out_name_ptr, out_name_len) };
+    match batch.columns().get(index) {
+        Some(column) => (column as *const ArrayRef).cast(),
+        None => ptr::null(),
+    }
+}
+
+/// Frees the given record batch handle.
+///
+/// ## Safety
+///
+/// Only handles created through [`read_dora_input_record_batch`] are allowed as
+/// arguments. Each handle must be freed exactly once. After freeing, the handle and
+/// all column handles and names read from it must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_record_batch(batch: *mut c_void) {
+    let _: Box<DoraRecordBatch> = unsafe { Box::from_raw(batch.cast()) };
+}
+
+/// A record batch handed out through [`read_dora_input_record_batch`].
+struct DoraRecordBatch {
+    batch: RecordBatch,
+    /// The schema of the batch, stored separately to hand out references to the
+    /// column names.
+    schema: SchemaRef,
+}
+
+/// Sends the given columns as a record batch.
+///
+/// Dora messages consist of a single arrow array, so the record batch is sent as a
+/// `Struct` array with one field per column. Receivers can read it through
+/// [`read_dora_input_record_batch`], and Python nodes receive a
+/// `pyarrow.StructArray` that can be converted through
+/// `pyarrow.RecordBatch.from_struct_array`. The schema metadata is sent as custom
+/// metadata parameters, see [`read_dora_input_schema_metadata`].
+///
+/// The schema is given as JSON in the following format:
+///
+/// ```json
+/// {
+///   "fields": [{ "name": "x", "format": "f", "nullable": false }, ...],
+///   "metadata": { "frame": "base_link" }
+/// }
+/// ```
+///
+/// The `format` of each field is a format string of the Arrow C data interface,
+/// e.g. `f` for `Float32` or `u` for `Utf8`. Only types without children are
+/// supported, i.e. no nested types such as lists or structs. The `nullable` and
+/// `metadata` entries are optional and default to `true` and no metadata.
+///
+/// The `columns_ptr` argument points to `column_count` arrays of the Arrow C data
+/// interface (`struct ArrowArray`), one for each field of the schema. All columns
+/// must have the same length. This function moves the arrays: it takes ownership of
+/// them and marks them as released, also if sending fails. The data is copied into
+/// the output message.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `id_ptr` and
+/// `id_len` fields must be the start pointer and length of an UTF8-encoded string,
+/// and the `schema_json_ptr` and `schema_json_len` fields must be the start pointer
+/// and length of a byte array. The `columns_ptr` argument must point to
+/// `column_count` valid, unreleased `struct ArrowArray` instances.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn dora_send_record_batch(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    schema_json_ptr: *const u8,
+    schema_json_len: usize,
+    columns_ptr: *mut FFI_ArrowArray,
+    column_count: usize,
+) -> isize {
+    // take ownership first to release the columns on all error paths
+    let columns: Vec<FFI_ArrowArray> = (0..column_count)
+        .map(|i| unsafe { ptr::replace(columns_ptr.add(i), FFI_ArrowArray::empty()) })
+        .collect();
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let schema_json = unsafe { slice::from_raw_parts(schema_json_ptr, schema_json_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let schema: RecordBatchSchema =
+            serde_json::from_slice(schema_json).wrap_err("failed to parse schema JSON")?;
+        let mut parameters = MetadataParameters::default();
+        for (key, value) in &schema.metadata {
+            parameters.custom.insert(
+                format!("{SCHEMA_METADATA_PREFIX}{key}"),
+                Parameter::String(value.clone()),
+            );
+        }
+        let array = unsafe { record_batch_from_ffi(schema.fields, columns) }?;
+        context.send_output(output_id, parameters, Arc::new(array))
+    });
+    send_status(result)
+}
+
+/// Schema of the record batch sent through [`dora_send_record_batch`].
+#[derive(serde::Deserialize)]
+struct RecordBatchSchema {
+    fields: Vec<RecordBatchField>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(serde::Deserialize)]
+struct RecordBatchField {
+    name: String,
+    /// Format string of the Arrow C data interface.
+    format: String,
+    nullable: Option<bool>,
+}
+
+/// Imports the given columns of the Arrow C data interface as `Struct` array.
+///
+/// ## Safety
+///
+/// The columns must be valid arrays of the Arrow C data interface.
+unsafe fn record_batch_from_ffi(
+    fields: Vec<RecordBatchField>,
+    columns: Vec<FFI_ArrowArray>,
+) -> eyre::Result<StructArray> {
+    if fields.is_empty() {
+        eyre::bail!("record batch must have at least one column");
+    }
+    if fields.len() != columns.len() {
+        eyre::bail!(
+            "schema has {} fields, but {} columns were given",
+            fields.len(),
+            columns.len()
+        );
+    }
+    let mut struct_fields = Vec::with_capacity(fields.len());
+    let mut arrays = Vec::with_capacity(fields.len());
+    for (field, column) in fields.into_iter().zip(columns) {
+        let name = field.name;
+        let schema = FFI_ArrowSchema::try_new(&field.format, Vec::new(), None)
+            .wrap_err_with(|| format!("invalid format `{}` of column `{name}`", field.format))?;
+        let data_type = DataType::try_from(&schema).wrap_err_with(|| {
+            format!("unsupported format `{}` of column `{name}`", field.format)
+        })?;
+        let data = from_ffi(column, &schema)
+            .wrap_err_with(|| format!("failed to import column `{name}`"))?;
+        struct_fields.push(Field::new(name, data_type, field.nullable.unwrap_or(true)));
+        arrays.push(make_array(data));
+    }
+    StructArray::try_new(struct_fields.into(), arrays, None)
+        .wrap_err("failed to create record batch")
+}
+
+/// Reads out the value of the given arrow schema metadata key of the given input.
+///
+/// Senders can attach schema metadata through
+/// [`dora_send_output_f32_with_schema_meta`]. The metadata is also attached to the
+/// field of the record batch returned by [`read_dora_input_record_batch`].
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded value. Returns `0` on success. Returns `-1` and writes a null
+/// pointer and length `0` if the event is not an input event or if the input has no
+/// metadata entry with the given key.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_input_schema_metadata(
+    event: *const (),
+    key_ptr: *const u8,
+    key_len: usize,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> isize {
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let key = unsafe { slice::from_raw_parts(key_ptr, key_len) };
+    let value = std::str::from_utf8(key)
+        .wrap_err("key is not valid UTF-8")
+        .and_then(|key| match event {
+            Event::Input { metadata, .. } => {
+                let name = format!("{SCHEMA_METADATA_PREFIX}{key}");
+                match metadata.parameters.custom.get(&name) {
+                    Some(Parameter::String(value)) => Ok(value.as_bytes()),
+                    _ => Err(eyre::eyre!("input has no schema metadata entry `{key}`")),
+                }
+            }
+            _ => Err(eyre::eyre!("event is not an input event")),
+        });
+    unsafe { write_slice(value, out_ptr, out_len) }
+}
+
+/// Collects the arrow schema metadata entries of the given message metadata.
+fn schema_metadata(metadata: &Metadata) -> HashMap<String, String> {
+    metadata
+        .parameters
+        .custom
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = name.strip_prefix(SCHEMA_METADATA_PREFIX)?;
+            match value {
+                Parameter::String(value) => Some((key.to_owned(), value.clone())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the data array of the given input event.
+fn input_data(event: &Event) -> eyre::Result<&ArrayRef> {
+    match event {
+        Event::Input { data, .. } => Ok(&data.0),
+        _ => eyre::bail!("event is not an input event"),
+    }
+}
+
+/// Writes the start pointer and length of the given slice to `out_ptr` and `out_len`.
+///
+/// On error, a null pointer and length `0` are written instead and the error is
+/// stored as last error (see [`dora_last_error`]).
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// The `out_ptr` and `out_len` arguments must be valid for writes.
+unsafe fn write_slice<T>(
+    result: eyre::Result<&[T]>,
+    out_ptr: *mut *const T,
+    out_len: *mut usize,
+) -> isize {
+    match result {
+        Ok(slice) => {
+            unsafe {
+                *out_ptr = slice.as_ptr();
+                *out_len = slice.len();
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Frees the given dora event.
+///
+/// ## Safety
+///
+/// Only pointers created through [`dora_next_event`] are allowed
+/// as arguments. Each context pointer must be freed exactly once. After
+/// freeing, the pointer and all derived pointers must not be used anymore.
+/// This also applies to the `read_dora_event_*` functions, which return
+/// pointers into the original event structure.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_event(event: *mut c_void) {
+    let _: Box<DoraEvent> = unsafe { Box::from_raw(event.cast()) };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_u8(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> isize {
+    send_status(unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) })
+}
+
+/// Sends the given `UInt8` data together with a checksum.
+///
+/// Works like `dora_send_output_u8`, but additionally computes a CRC-32 checksum
+/// (IEEE polynomial, as used by zlib and Ethernet) over the data and stores it in
+/// the message metadata. Receivers can check it through
+/// [`read_dora_input_verify_checksum`] to detect corruption of the data in transit.
+///
+/// The checksum is opt-in per message because computing it requires a full pass
+/// over the data, which adds overhead on hot paths.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_u8_checked(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let mut parameters = MetadataParameters::default();
+        parameters.custom.insert(
+            CHECKSUM_PARAMETER.to_owned(),
+            Parameter::Integer(crc32fast::hash(data).into()),
+        );
+        context.send_output(output_id, parameters, data.to_arrow())
+    });
+    send_status(result)
+}
+
+/// Registers `UInt8` data that is sent on the given output when the node stops.
+///
+/// This allows publishing a final message, such as a summary of accumulated
+/// statistics, without racing against the shutdown of the node. The data is copied,
+/// so the buffer can be freed after this call. The output is sent exactly once, at
+/// the first of the following points:
+///
+/// - When the `Stop` event is received, before it is returned by
+///   [`dora_next_event`] (or passed to the callback of
+///   [`dora_start_background_loop`]). All outputs that were sent before are thus
+///   delivered before the shutdown output. Inputs that are still buffered when the
+///   `Stop` event is received don't delay the shutdown output.
+/// - When the context is freed through [`free_dora_context`], if no `Stop` event was
+///   received before, e.g. because the main loop exited early. The output is sent
+///   after the background loop was stopped and after the outputs of an unfinished
+///   tick were discarded, but before the connection to the daemon is closed.
+///
+/// The shutdown output bypasses ticks, rate limits, and flow control windows, so it
+/// is never buffered or rejected. Output hooks are invoked for it as for other
+/// outputs. Failures to send it are only logged. Calling this function again
+/// replaces the registered data.
+///
+/// Returns a [`DoraStatus`] code. Fails if the ID is not an output of the node.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `data_ptr` and
+/// `data_len` fields must be the start pointer and length of a byte array.
+#[no_mangle]
+pub unsafe extern "C" fn dora_set_shutdown_output(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        if !context.node.has_output(&output_id) {
+            eyre::bail!("unknown output {output_id}");
+        }
+        context.shutdown_output = Some((output_id, data.to_arrow()));
+        Ok(())
+    });
+    send_status(result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_i32(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const i32,
+    data_len: usize,
+) -> isize {
+    send_status(unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_f32(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+) -> isize {
+    send_status(unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) })
+}
+
+/// Works like `dora_send_output_f32`, but only delivers the message to the given
+/// downstream nodes.
+///
+/// The `targets_ptr` and `target_lens_ptr` arrays contain the start pointer and
+/// length of the UTF8-encoded ID of each target node. Receivers of the output that
+/// are not listed do not get the message. Listed nodes that don't receive the output
+/// are ignored. The targets are stored as `target.<node_id>` metadata parameters
+/// and the filtering happens in the daemon, so the message is not transferred to
+/// other nodes at all.
+///
+/// Returns a [`DoraStatus`] code. Fails if `target_count` is `0`.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `targets_ptr` and
+/// `target_lens_ptr` arguments must point to `target_count` elements each. Each
+/// target pointer must point to the given number of bytes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn dora_send_output_f32_to(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+    targets_ptr: *const *const u8,
+    target_lens_ptr: *const usize,
+    target_count: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    if target_count == 0 {
+        return send_status(Err(eyre::eyre!("at least one target node is required")));
+    }
+    let (target_ptrs, target_lens) = unsafe {
+        (
+            slice::from_raw_parts(targets_ptr, target_count),
+            slice::from_raw_parts(target_lens_ptr, target_count),
+        )
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let mut parameters = MetadataParameters::default();
+        for (ptr, len) in target_ptrs.iter().zip(target_lens) {
+            let target = std::str::from_utf8(unsafe { slice::from_raw_parts(*ptr, *len) })
+                .wrap_err("target node ID is not valid UTF-8")?;
+            parameters.custom.insert(
+                format!("{TARGET_PARAMETER_PREFIX}{target}"),
+                Parameter::Bool(true),
+            );
+        }
+        context.send_output(output_id, parameters, data.to_arrow())
+    });
+    send_status(result)
+}
+
+/// Sends the given object detections as a single message.
+///
+/// The detections are sent as a `Struct` array with one element per detection and
+/// the following fields:
+///
+/// - `bbox`: `FixedSizeList<Float32, 4>`, the bounding box as `[x_min, y_min,
+///   x_max, y_max]`
+/// - `score`: `Float32`, the confidence score of the detection
+/// - `label`: `Utf8`, the class label of the detection
+///
+/// In Python, this corresponds to a `pyarrow.StructArray` with the fields
+/// `("bbox", pa.list_(pa.float32(), 4))`, `("score", pa.float32())`, and
+/// `("label", pa.utf8())`. Use [`read_dora_input_detections`] to decode the message.
+///
+/// The `boxes_ptr` array contains the four coordinates of each detection, one
+/// detection after the other. The `labels_ptr` and `label_lens_ptr` arrays contain
+/// the start pointer and length of the UTF8-encoded label of each detection.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `boxes_ptr`
+/// argument must point to `4 * count` `f32` values. The `scores_ptr`, `labels_ptr`,
+/// and `label_lens_ptr` arguments must point to `count` elements each. Each label
+/// pointer must point to the given number of bytes. If `count` is `0`, the pointers
+/// are not accessed.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn dora_send_detections(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    boxes_ptr: *const f32,
+    scores_ptr: *const f32,
+    labels_ptr: *const *const u8,
+    label_lens_ptr: *const usize,
+    count: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let (boxes, scores, label_ptrs, label_lens) = if count == 0 {
+        (&[][..], &[][..], &[][..], &[][..])
+    } else {
+        unsafe {
+            (
+                slice::from_raw_parts(boxes_ptr, 4 * count),
+                slice::from_raw_parts(scores_ptr, count),
+                slice::from_raw_parts(labels_ptr, count),
+                slice::from_raw_parts(label_lens_ptr, count),
+            )
+        }
+    };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let labels = label_ptrs
+            .iter()
+            .zip(label_lens)
+            .map(|(ptr, len)| std::str::from_utf8(unsafe { slice::from_raw_parts(*ptr, *len) }))
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("detection label is not valid UTF-8")?;
+        let array = detections_array(boxes, scores, labels)?;
+        context.send_output(output_id, MetadataParameters::default(), Arc::new(array))
+    });
+    send_status(result)
+}
+
+/// Builds the `Struct` array that [`dora_send_detections`] sends.
+fn detections_array(boxes: &[f32], scores: &[f32], labels: Vec<&str>) -> eyre::Result<StructArray> {
+    let bbox = FixedSizeListArray::try_new(
+        Arc::new(Field::new("item", DataType::Float32, false)),
+        4,
+        Arc::new(Float32Array::from(boxes.to_vec())),
+        None,
+    )
+    .wrap_err("failed to create bounding box array")?;
+    let fields = vec![
+        Field::new(DETECTION_BBOX_FIELD, bbox.data_type().clone(), false),
+        Field::new(DETECTION_SCORE_FIELD, DataType::Float32, false),
+        Field::new(DETECTION_LABEL_FIELD, DataType::Utf8, false),
+    ];
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(bbox),
+        Arc::new(Float32Array::from(scores.to_vec())),
+        Arc::new(StringArray::from(labels)),
+    ];
+    StructArray::try_new(fields.into(), columns, None).wrap_err("failed to create detections array")
+}
+
+/// Sends the given `Float32` data with a null bitmap.
+///
+/// The `validity_ptr` array contains one byte per value: `0` marks the value as
+/// null (i.e. missing), all other values mark it as valid. The values of null slots
+/// are ignored. Receivers can read the validity through [`read_dora_input_validity`].
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `data_ptr` and
+/// `validity_ptr` arguments must point to arrays of at least `len` `f32` values and
+/// bytes, respectively.
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_f32_nullable(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    validity_ptr: *const u8,
+    len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, len) };
+    let validity = unsafe { slice::from_raw_parts(validity_ptr, len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let array: Float32Array = data
+            .iter()
+            .zip(validity)
+            .map(|(value, valid)| (*valid != 0).then_some(*value))
+            .collect();
+        context.send_output(output_id, MetadataParameters::default(), Arc::new(array))
+    });
+    send_status(result)
+}
+
+/// Creates an accumulator that builds an output array incrementally.
+///
+/// Data is appended through [`dora_output_accumulator_push_f32`] and sent as a
+/// single message on the given output through [`dora_output_accumulator_flush`].
+/// This allows assembling a large message (e.g. a point cloud) over multiple compute
+/// steps. The `dtype` argument is the [`DoraDataType`] of the array as integer.
+/// Currently, only `Float32` accumulators are supported.
+///
+/// Returns a handle to the accumulator, which must be freed through
+/// [`free_dora_output_accumulator`]. On error, a null pointer is returned and the
+/// error is stored as last error (see [`dora_last_error`]).
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The context must be
+/// still valid, i.e., not freed yet, as long as the accumulator is used.
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_accumulator_new(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    dtype: i32,
+) -> *mut c_void {
+    let context_ptr: *mut DoraContext = context.cast();
+    let accumulator = unsafe { live_context(context) }.and_then(|context| {
+        let output_id = unsafe { read_id(id_ptr, id_len) }?;
+        if dtype != DoraDataType::Float32 as i32 {
+            eyre::bail!("unsupported accumulator data type {dtype}, expected Float32");
+        }
+        if !context.node.has_output(&output_id) {
+            eyre::bail!("unknown output {output_id}");
+        }
+        Ok(DoraOutputAccumulator {
+            context: context_ptr,
+            output_id,
+            builder: Float32Builder::new(),
+        })
+    });
+    match accumulator {
+        Ok(accumulator) => Box::into_raw(Box::new(accumulator)).cast(),
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Appends the given `Float32` values to the given accumulator.
+///
+/// The values are copied, so the data can be reused after this call.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `accumulator`
+/// argument must be a handle created through [`dora_output_accumulator_new`] that
+/// was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_accumulator_push_f32(
+    accumulator: *mut c_void,
+    data_ptr: *const f32,
+    data_len: usize,
+) {
+    let accumulator: &mut DoraOutputAccumulator = unsafe { &mut *accumulator.cast() };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    accumulator.builder.append_slice(data);
+}
+
+/// Sends all values of the given accumulator as a single message.
+///
+/// The accumulator is empty afterwards and can be used to build the next message.
+/// The message is sent like through [`dora_send_output_f32`], so rate limits, flow
+/// control windows, and ticks apply. If the send fails, the accumulated values are
+/// discarded.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// The `accumulator` argument must be a handle created through
+/// [`dora_output_accumulator_new`] that was not freed yet. Its context must be
+/// still valid.
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_accumulator_flush(accumulator: *mut c_void) -> isize {
+    let accumulator: &mut DoraOutputAccumulator = unsafe { &mut *accumulator.cast() };
+    let context = match unsafe { live_context(accumulator.context.cast()) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let array: ArrayRef = Arc::new(accumulator.builder.finish());
+    send_status(context.send_output(
+        accumulator.output_id.clone(),
+        MetadataParameters::default(),
+        array,
+    ))
+}
+
+/// Frees the given accumulator, discarding all values that were not flushed.
+///
+/// ## Safety
+///
+/// The `accumulator` argument must be a handle created through
+/// [`dora_output_accumulator_new`]. It must not be used anymore after this call.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_output_accumulator(accumulator: *mut c_void) {
+    let _: Box<DoraOutputAccumulator> = unsafe { Box::from_raw(accumulator.cast()) };
+}
+
+/// Sends the given `Float32` data with the given arrow schema metadata.
+///
+/// The `keys` and `vals` arguments are arrays of `count` null-terminated UTF-8
+/// strings. The entry `keys[i]: vals[i]` is attached to the arrow schema of the
+/// output, which receivers can read through [`read_dora_input_schema_metadata`].
+/// Unlike custom metadata parameters, schema metadata is visible to tools that
+/// inspect the arrow schema, e.g. when the input is read as record batch.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `keys` and `vals`
+/// arguments must point to `count` valid null-terminated strings each.
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_f32_with_schema_meta(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+    keys: *const *const c_char,
+    vals: *const *const c_char,
+    count: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let keys = unsafe { slice::from_raw_parts(keys, count) };
+    let vals = unsafe { slice::from_raw_parts(vals, count) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let mut parameters = MetadataParameters::default();
+        for (&key, &value) in keys.iter().zip(vals) {
+            let key = unsafe { CStr::from_ptr(key) }
+                .to_str()
+                .wrap_err("schema metadata key is not valid UTF-8")?;
+            let value = unsafe { CStr::from_ptr(value) }
+                .to_str()
+                .wrap_err("schema metadata value is not valid UTF-8")?;
+            parameters.custom.insert(
+                format!("{SCHEMA_METADATA_PREFIX}{key}"),
+                Parameter::String(value.to_owned()),
+            );
+        }
+        context.send_output(output_id, parameters, data.to_arrow())
+    });
+    send_status(result)
+}
+
+/// Sends the given `Float32` data only if it differs from the last data sent on the
+/// output.
+///
+/// The payload of the last send through this function is cached per output. If the
+/// new data is byte-identical to the cached payload, nothing is sent and
+/// [`DoraStatus::Unchanged`] is returned. This reduces traffic for slowly changing
+/// signals.
+///
+/// The cache keeps a copy of the last payload of each output that is sent through
+/// this function, so its memory cost is the size of one message per output. Thus,
+/// this function is best suited for small messages.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_f32_on_change(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let output_id = match unsafe { read_id(id_ptr, id_len) } {
+        Ok(output_id) => output_id,
+        Err(err) => return send_status(Err(err)),
+    };
+    let payload: Vec<u8> = data.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    if context.last_payloads.get(&output_id) == Some(&payload) {
+        return DoraStatus::Unchanged as isize;
+    }
+    let result = context.send_output(output_id.clone(), Default::default(), data.to_arrow());
+    if result.is_ok() {
+        context.last_payloads.insert(output_id, payload);
+    }
+    send_status(result)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_u64(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const u64,
+    data_len: usize,
+) -> isize {
+    send_status(unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) })
+}
+
+/// Sends an empty output that marks a dropped frame.
+///
+/// Fixed-rate consumers rely on receiving one message per tick. When a node can't
+/// produce a frame (e.g. because a sensor read failed), it can send this placeholder
+/// instead, so that downstream nodes keep their tick count aligned. The output
+/// carries a `Null` array of length `0` and is marked as dropped in its metadata,
+/// which receivers can check through [`read_dora_input_is_dropped`].
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_dropped(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let mut parameters = MetadataParameters::default();
+        parameters
+            .custom
+            .insert(DROPPED_PARAMETER.to_owned(), Parameter::Bool(true));
+        context.send_output(output_id, parameters, Arc::new(NullArray::new(0)))
+    });
+    send_status(result)
+}
+
+/// Collects the latest data of each input.
+///
+/// Drains all events that are currently available and stores the most recent data
+/// of each input ID in a snapshot ("sample and hold"). If no input is available, it
+/// waits up to `timeout_ms` milliseconds for one. Events that are not inputs (e.g.
+/// `Stop`) are kept and returned by subsequent [`dora_next_event`] calls, so nodes
+/// should still check for them regularly. While inputs are paused (see
+/// [`dora_pause_inputs`]), the snapshot is always empty and the inputs are kept
+/// until they are resumed.
+///
+/// Writes a snapshot handle to `out_handle`, which can be read through
+/// [`dora_snapshot_get`]. The snapshot is empty if no input arrived before the
+/// timeout. It must be freed through [`free_dora_snapshot`].
+///
+/// Returns `0` on success.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_collect_latest(
+    context: *mut c_void,
+    timeout_ms: u64,
+    out_handle: *mut *mut c_void,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            unsafe { *out_handle = ptr::null_mut() };
+            return send_status(Err(err));
+        }
+    };
+    let inputs = context.collect_latest(Duration::from_millis(timeout_ms));
+    let snapshot = Box::new(DoraSnapshot { inputs });
+    unsafe { *out_handle = Box::into_raw(snapshot).cast() };
+    DoraStatus::Ok as isize
+}
+
+/// Reads out the latest data of the given input from the given snapshot.
+///
+/// Writes the start pointer of the array values to `out_data_ptr`, the number of
+/// elements to `out_len`, and the data type of the elements to `out_dtype`. The
+/// pointer needs to be cast to the element type indicated by `out_dtype`.
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0` if
+/// the snapshot contains no data for the given input or if the data is not a
+/// primitive array. In the latter case, `out_dtype` is set to
+/// [`DoraDataType::Unknown`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `snapshot` argument
+/// must be a snapshot handle created through [`dora_collect_latest`] that was not
+/// freed yet. The returned `out_data_ptr` must not be used after freeing the
+/// snapshot, since it points directly into the snapshot's memory.
+#[no_mangle]
+pub unsafe extern "C" fn dora_snapshot_get(
+    snapshot: *const c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    out_data_ptr: *mut *const c_void,
+    out_len: *mut usize,
+    out_dtype: *mut DoraDataType,
+) -> isize {
+    let snapshot: &DoraSnapshot = unsafe { &*snapshot.cast() };
+    let values = unsafe { read_id(id_ptr, id_len) }.and_then(|input_id| {
+        let array = snapshot
+            .inputs
+            .get(&input_id)
+            .ok_or_else(|| eyre::eyre!("snapshot contains no data for input {input_id}"))?;
+        primitive_values(array)
+    });
+    match values {
+        Ok((ptr, len, dtype)) => {
+            unsafe {
+                *out_data_ptr = ptr;
+                *out_len = len;
+                *out_dtype = dtype;
+            }
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe {
+                *out_data_ptr = ptr::null();
+                *out_len = 0;
+                *out_dtype = DoraDataType::Unknown;
+            }
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Frees the given snapshot.
+///
+/// ## Safety
+///
+/// Only handles created through [`dora_collect_latest`] are allowed as arguments.
+/// Each snapshot must be freed exactly once. After freeing, the handle and all
+/// pointers read from it must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_snapshot(snapshot: *mut c_void) {
+    let _: Box<DoraSnapshot> = unsafe { Box::from_raw(snapshot.cast()) };
+}
+
+/// Bundles the latest data of all inputs into a single struct array.
+///
+/// Works like [`dora_collect_latest`], but holds the data of each input across
+/// calls ("sample and hold"): inputs that did not receive new data since the last
+/// call keep their previous value. Inputs that never received any data are not
+/// part of the struct.
+///
+/// The struct array has a single row with one field per input, ordered by input
+/// ID. Since the inputs can have different lengths, each field is a list that
+/// contains the input data. Writes a struct handle to `out_handle`, which can be
+/// read field-by-field through [`read_dora_struct_num_fields`] and
+/// [`read_dora_struct_field`], and re-emitted as a synchronized bundle through
+/// [`dora_send_output_struct`]. The handle must be freed through
+/// [`free_dora_struct`].
+///
+/// Returns `0` on success. Returns `-1` and writes a null pointer on error.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_latest_as_struct(
+    context: *mut c_void,
+    timeout_ms: u64,
+    out_handle: *mut *mut c_void,
+) -> isize {
+    let result = unsafe { live_context(context) }.and_then(|context| {
+        let latest = context.collect_latest(Duration::from_millis(timeout_ms));
+        context.held_inputs.extend(latest);
+        latest_struct(&context.held_inputs)
+    });
+    match result {
+        Ok(array) => {
+            unsafe { *out_handle = Box::into_raw(Box::new(DoraStruct { array })).cast() };
+            DoraStatus::Ok as isize
+        }
+        Err(err) => {
+            unsafe { *out_handle = ptr::null_mut() };
+            send_status(Err(err))
+        }
+    }
+}
+
+/// Returns the number of fields of the given struct handle.
+///
+/// ## Safety
+///
+/// The `handle` argument must be a struct handle created through
+/// [`dora_latest_as_struct`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_struct_num_fields(handle: *const c_void) -> usize {
+    let DoraStruct { array } = unsafe { &*handle.cast() };
+    array.num_columns()
+}
+
+/// Reads out the field at the given index of the given struct handle.
+///
+/// Writes the name of the field, i.e. the input ID, to `out_name_ptr` and
+/// `out_name_len` and returns an array handle for the input data. The array handle
+/// can be read through the `read_dora_array_*` functions, e.g.
+/// [`read_dora_array_len`]. Returns a null pointer if the index is out of bounds.
+///
+/// ## Safety
+///
+/// The `handle` argument must be a struct handle created through
+/// [`dora_latest_as_struct`] that was not freed yet. The returned name and array
+/// handle must not be used after freeing the struct handle.
+#[no_mangle]
+pub unsafe extern "C" fn read_dora_struct_field(
+    handle: *const c_void,
+    index: usize,
+    out_name_ptr: *mut *const u8,
+    out_name_len: *mut usize,
+) -> *const c_void {
+    let DoraStruct { array } = unsafe { &*handle.cast() };
+    let name = array
+        .fields()
+        .get(index)
+        .map(|field| field.name().as_bytes())
+        .ok_or_else(|| eyre::eyre!("field index {index} is out of bounds"));
+    unsafe { write_slice(name, out_name_ptr, out_name_len) };
+    match array.columns().get(index) {
+        Some(column) => (column.as_list::<i32>().values() as *const ArrayRef).cast(),
+        None => ptr::null(),
+    }
+}
+
+/// Sends the given struct handle as output.
+///
+/// Receivers get the struct array described in [`dora_latest_as_struct`].
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The `handle` argument
+/// must be a struct handle created through [`dora_latest_as_struct`] that was not
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_struct(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    handle: *const c_void,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let DoraStruct { array } = unsafe { &*handle.cast() };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        context.send_output(
+            output_id,
+            MetadataParameters::default(),
+            Arc::new(array.clone()),
+        )
+    });
+    send_status(result)
+}
+
+/// Frees the given struct handle.
+///
+/// ## Safety
+///
+/// Only handles created through [`dora_latest_as_struct`] are allowed as arguments.
+/// Each handle must be freed exactly once. After freeing, the handle and all field
+/// handles and names read from it must not be used anymore.
+#[no_mangle]
+pub unsafe extern "C" fn free_dora_struct(handle: *mut c_void) {
+    let _: Box<DoraStruct> = unsafe { Box::from_raw(handle.cast()) };
+}
+
+/// A bundle of the latest input data, see [`dora_latest_as_struct`].
+struct DoraStruct {
+    array: StructArray,
+}
+
+/// Creates a single-row struct array with one list field per input.
+fn latest_struct(inputs: &BTreeMap<DataId, ArrayRef>) -> eyre::Result<StructArray> {
+    let mut fields = Vec::with_capacity(inputs.len());
+    let mut columns = Vec::with_capacity(inputs.len());
+    for (id, data) in inputs {
+        let item = Arc::new(Field::new("item", data.data_type().clone(), true));
+        let list = ListArray::try_new(
+            item.clone(),
+            OffsetBuffer::from_lengths([data.len()]),
+            data.clone(),
+            None,
+        )
+        .wrap_err_with(|| format!("failed to wrap data of input {id}"))?;
+        fields.push(Field::new(id.to_string(), DataType::List(item), false));
+        columns.push(Arc::new(list) as ArrayRef);
+    }
+    StructArray::try_new(fields.into(), columns, None).wrap_err("failed to create struct array")
+}
+
+/// Returns the start pointer, length, and data type of the values of the given
+/// primitive array.
+///
+/// `Null` arrays are represented by a null pointer and length `0`.
+fn primitive_values(array: &ArrayRef) -> eyre::Result<(*const c_void, usize, DoraDataType)> {
+    let (ptr, dtype): (*const c_void, _) = match array.data_type() {
+        DataType::Null => return Ok((ptr::null(), 0, DoraDataType::Null)),
+        DataType::UInt8 => (
+            array.as_primitive::<UInt8Type>().values().as_ptr().cast(),
+            DoraDataType::UInt8,
+        ),
+        DataType::Int32 => (
+            array.as_primitive::<Int32Type>().values().as_ptr().cast(),
+            DoraDataType::Int32,
+        ),
+        DataType::Float32 => (
+            array.as_primitive::<Float32Type>().values().as_ptr().cast(),
+            DoraDataType::Float32,
+        ),
+        DataType::UInt64 => (
+            array.as_primitive::<UInt64Type>().values().as_ptr().cast(),
+            DoraDataType::UInt64,
+        ),
+        DataType::Float64 => (
+            array.as_primitive::<Float64Type>().values().as_ptr().cast(),
+            DoraDataType::Float64,
+        ),
+        other => eyre::bail!("data type {other} is not supported, expected a primitive type"),
+    };
+    Ok((ptr, array.len(), dtype))
+}
+
+/// Reads out the ID of the machine that the node is deployed on.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded machine ID. The ID is empty if the dataflow doesn't assign the node
+/// to a specific machine, i.e. if the node runs on the default machine.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned `out_ptr`
+/// must not be used after freeing the context.
+#[no_mangle]
+pub unsafe extern "C" fn dora_machine_id(
+    context: *mut c_void,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => {
+            set_last_error(format!("{err:?}"));
+            unsafe {
+                *out_ptr = ptr::null();
+                *out_len = 0;
+            }
+            return;
+        }
+    };
+    let machine_id = context.node.machine_id();
+    unsafe {
+        *out_ptr = machine_id.as_ptr();
+        *out_len = machine_id.len();
+    }
+}
+
+/// Checks whether sending an output of the given size would fail because no shared
+/// memory is available.
+///
+/// Large outputs are sent through shared memory. If the shared memory file system
+/// is full, sending them fails with [`DoraStatus::ShmFull`]. Real-time nodes can use
+/// this function before a large send to adapt, e.g. by skipping a frame.
+///
+/// To check the availability, this function allocates a shared memory region of the
+/// given size. The region is reused by the next send, so the allocation is not
+/// wasted. Outputs smaller than the zero-copy threshold (4096 bytes) don't use
+/// shared memory and never block.
+///
+/// Returns `1` if no shared memory region could be allocated and `0` otherwise.
+/// Returns a negative [`DoraStatus`] code on other errors.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_output_would_block(context: *mut c_void, bytes: usize) -> i32 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)) as i32,
+    };
+    match context.node.reserve_shared_memory(bytes) {
+        Ok(()) => 0,
+        Err(err) if err.downcast_ref::<SharedMemoryFull>().is_some() => 1,
+        Err(err) => send_status(Err(err)) as i32,
+    }
+}
+
+/// Acknowledges that the given input was processed.
+///
+/// Sends the sequence number of the input (see [`read_dora_input_sequence`]) on the
+/// output `ack_<input_id>`, which must be declared in the dataflow. Producers can
+/// subscribe to this output and pass it to [`dora_set_output_window`] to limit the
+/// number of unacknowledged messages. This way, slow consumers can apply
+/// back-pressure to fast producers instead of buffering or dropping inputs.
+///
+/// Acknowledgements are regular dora messages, so this works with all transports,
+/// both for local and for remote nodes.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_ack_input(context: *mut c_void, event: *const ()) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let result = match event {
+        Event::Input { id, metadata, .. } => {
+            match metadata.parameters.custom.get(SEQUENCE_PARAMETER) {
+                Some(Parameter::Integer(sequence)) => {
+                    let ack_id = DataId::from(format!("ack_{id}"));
+                    let ack = UInt64Array::from(vec![*sequence as u64]);
+                    context.send_output(ack_id, Default::default(), Arc::new(ack))
+                }
+                _ => Err(eyre::eyre!("input {id} has no sequence number")),
+            }
+        }
+        _ => Err(eyre::eyre!("event is not an input event")),
+    };
+    send_status(result)
+}
+
+/// Sends the given `f32` data as request and waits for the reply.
+///
+/// This builds synchronous request/response exchanges on top of the dataflow. The
+/// request is sent on the given output with a `correlation_id` metadata parameter
+/// (see [`CORRELATION_PARAMETER`]) that is unique per context. The reply is
+/// expected on the input `reply_<output_id>`, which must be declared in the
+/// dataflow and mapped to the reply output of the responder. The responder sends
+/// the reply through [`dora_send_reply_f32`], which copies the correlation ID:
+///
+/// ```yaml
+/// - id: client
+///   outputs: [query]
+///   inputs:
+///     reply_query: server/reply_request
+/// - id: server
+///   outputs: [reply_request]
+///   inputs:
+///     request: client/query
+/// ```
+///
+/// Once the reply arrives, this function returns [`DoraStatus::Ok`] and the reply
+/// is returned by the next [`dora_next_event`] call, before all other pending
+/// events. Events that arrive while waiting are kept. Replies to earlier requests
+/// that timed out are returned by [`dora_next_event`] as regular inputs.
+///
+/// Returns [`DoraStatus::Timeout`] if no reply arrives within `timeout_ms`
+/// milliseconds, or another [`DoraStatus`] code if sending fails.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_send_output_f32_sync(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    data_ptr: *const f32,
+    data_len: usize,
+    timeout_ms: u64,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        context.send_request(
+            output_id,
+            data.to_arrow(),
+            Duration::from_millis(timeout_ms),
+        )
+    });
+    send_status(result)
 }
 
-/// Initializes a dora context from the environment variables that were set by
-/// the dora-coordinator.
+/// Replies to a request that was sent through [`dora_send_output_f32_sync`].
 ///
-/// Returns a pointer to the dora context on success. This pointer can be
-/// used to call dora API functions that expect a `context` argument. Any
-/// other use is prohibited. To free the dora context when it is no longer
-/// needed, use the [`free_dora_context`] function.
+/// Sends the given `f32` data on the output `reply_<input_id>`, which must be
+/// declared in the dataflow, and copies the correlation ID of the request.
 ///
-/// On error, a null pointer is returned.
+/// Returns a [`DoraStatus`] code. Fails if the event is not an input event or if
+/// it has no correlation ID.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub extern "C" fn init_dora_context_from_env() -> *mut c_void {
-    let context = || {
-        let (node, events) = DoraNode::init_from_env()?;
-        let node = Box::leak(Box::new(node));
-        Result::<_, eyre::Report>::Ok(DoraContext { node, events })
+pub unsafe extern "C" fn dora_send_reply_f32(
+    context: *mut c_void,
+    event: *const (),
+    data_ptr: *const f32,
+    data_len: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
     };
-    let context = match context().context("failed to initialize node") {
-        Ok(n) => n,
-        Err(err) => {
-            let err: eyre::Error = err;
-            tracing::error!("{err:?}");
-            return ptr::null_mut();
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+    let result = match event {
+        Event::Input { id, metadata, .. } => {
+            match metadata.parameters.custom.get(CORRELATION_PARAMETER) {
+                Some(correlation) => {
+                    let mut parameters = MetadataParameters::default();
+                    parameters
+                        .custom
+                        .insert(CORRELATION_PARAMETER.to_owned(), correlation.clone());
+                    let reply_id = DataId::from(format!("reply_{id}"));
+                    context.send_output(reply_id, parameters, data.to_arrow())
+                }
+                None => Err(eyre::eyre!("input {id} has no correlation ID")),
+            }
         }
+        _ => Err(eyre::eyre!("event is not an input event")),
     };
-
-    Box::into_raw(Box::new(context)).cast()
+    send_status(result)
 }
 
-/// Frees the given dora context.
+/// Limits the number of unacknowledged messages on the given output.
+///
+/// The `ack_input_ptr` and `ack_input_len` arguments specify the input on which
+/// the consumer's acknowledgements are received, i.e. an input that is mapped to
+/// the `ack_<input_id>` output of the consumer (see [`dora_ack_input`]). Once
+/// `window` messages are unacknowledged, `dora_send_output_*` calls for the output
+/// return [`DoraStatus::WindowFull`] without sending. The producer can then retry
+/// later. Acknowledgement inputs are consumed internally and are not returned by
+/// [`dora_next_event`]. A `window` of `0` removes the limit.
+///
+/// This function must be called before the first message is sent on the output.
+///
+/// Returns a [`DoraStatus`] code.
 ///
 /// ## Safety
 ///
-/// Only pointers created through [`init_dora_context_from_env`] are allowed
-/// as arguments. Each context pointer must be freed exactly once. After
-/// freeing, the pointer must not be used anymore.
+/// See the [crate-level safety requirements](crate#safety). The same applies to
+/// `ack_input_ptr` and `ack_input_len`.
 #[no_mangle]
-pub unsafe extern "C" fn free_dora_context(context: *mut c_void) {
-    let context: Box<DoraContext> = unsafe { Box::from_raw(context.cast()) };
-    // drop all fields except for `node`
-    let DoraContext { node, .. } = *context;
-    // convert the `'static` reference back to a Box, then drop it
-    let _ = unsafe { Box::from_raw(node as *const DoraNode as *mut DoraNode) };
+pub unsafe extern "C" fn dora_set_output_window(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    ack_input_ptr: *const u8,
+    ack_input_len: usize,
+    window: u64,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        let ack_input = unsafe { read_id(ack_input_ptr, ack_input_len) }?;
+        if !context.node.has_output(&output_id) {
+            eyre::bail!("unknown output {output_id}");
+        }
+        if !context.node.has_input(&ack_input) {
+            eyre::bail!("unknown input {ack_input}");
+        }
+        if window > 0 {
+            let window = FlowWindow {
+                ack_input,
+                size: window,
+                acked: 0,
+                sent_before_reconnect: 0,
+            };
+            context.windows.insert(output_id, window);
+        } else {
+            context.windows.remove(&output_id);
+        }
+        Ok(())
+    });
+    send_status(result)
 }
 
-/// Waits for the next incoming event for the node.
+/// Measures the round-trip latency to the given downstream node.
 ///
-/// Returns a pointer to the event on success. This pointer must not be used
-/// directly. Instead, use the `read_dora_event_*` functions to read out the
-/// type and payload of the event. When the event is not needed anymore, use
-/// [`free_dora_event`] to free it again.
+/// Sends a latency probe on the output `ping_<downstream_id>` and waits until the
+/// downstream node echoes it back on the input `pong_<downstream_id>`. Both must be
+/// declared in the dataflow. The downstream node needs to echo the probes through
+/// [`dora_echo_probe`]. Other events that arrive while waiting are returned by
+/// subsequent [`dora_next_event`] calls as usual.
 ///
-/// Returns a null pointer when all event streams were closed. This means that
-/// no more event will be available. Nodes typically react by stopping.
+/// Writes the measured round-trip time to `out_nanos`. Returns
+/// [`DoraStatus::Timeout`] if no echo arrives within `timeout_ms` milliseconds, or
+/// another [`DoraStatus`] code if sending the probe fails.
 ///
 /// ## Safety
 ///
-/// The `context` argument must be a dora context created through
-/// [`init_dora_context_from_env`]. The context must be still valid, i.e., not
-/// freed yet.
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn dora_next_event(context: *mut c_void) -> *mut c_void {
-    let context: &mut DoraContext = unsafe { &mut *context.cast() };
-    match context.events.recv() {
-        Some(event) => Box::into_raw(Box::new(event)).cast(),
-        None => ptr::null_mut(),
-    }
+pub unsafe extern "C" fn dora_measure_rtt(
+    context: *mut c_void,
+    downstream_id_ptr: *const u8,
+    downstream_id_len: usize,
+    out_nanos: *mut u64,
+    timeout_ms: u64,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let timeout = Duration::from_millis(timeout_ms);
+    let result = unsafe { read_id(downstream_id_ptr, downstream_id_len) }
+        .and_then(|downstream| context.measure_rtt(downstream.as_str(), timeout));
+    let result = result.map(|rtt| unsafe {
+        *out_nanos = rtt.as_nanos().try_into().unwrap_or(u64::MAX);
+    });
+    send_status(result)
 }
 
-/// Reads out the type of the given event.
+/// Echoes the given latency probe back to its sender.
+///
+/// Nodes that should support latency measurements through [`dora_measure_rtt`]
+/// call this function for their inputs. If the input is a latency probe, it is sent
+/// back on the output `echo_<input_id>`, which the sender needs to map to its
+/// `pong_<downstream_id>` input.
+///
+/// Returns `1` if the input was a probe and was echoed, `0` if it was no probe, and
+/// `-1` on error (see [`dora_last_error`]).
 ///
 /// ## Safety
 ///
-/// The `event` argument must be a dora event received through
-/// [`dora_next_event`]. The event must be still valid, i.e., not
-/// freed yet.
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn read_dora_event_type(event: *const ()) -> EventType {
-    let event: &Event = unsafe { &*event.cast() };
-    match event {
-        Event::Stop => EventType::Stop,
-        Event::Input { .. } => EventType::Input,
-        Event::InputClosed { .. } => EventType::InputClosed,
-        Event::Error(_) => EventType::Error,
-        _ => EventType::Unknown,
+pub unsafe extern "C" fn dora_echo_probe(context: *mut c_void, event: *const ()) -> i32 {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)) as i32,
+    };
+    let DoraEvent { event, .. } = unsafe { &*event.cast::<DoraEvent>() };
+    let Event::Input { id, metadata, data } = event else {
+        return 0;
+    };
+    let Some(probe) = metadata.parameters.custom.get(RTT_PROBE_PARAMETER) else {
+        return 0;
+    };
+    let mut parameters = MetadataParameters::default();
+    parameters
+        .custom
+        .insert(RTT_PROBE_PARAMETER.to_owned(), probe.clone());
+    let echo = DataId::from(format!("echo_{id}"));
+    let timestamp = context.new_timestamp();
+    match context.send_array(echo, parameters, data.0.clone(), timestamp) {
+        Ok(()) => 1,
+        Err(err) => send_status(Err(err)) as i32,
     }
 }
 
-#[repr(C)]
-pub enum EventType {
-    Stop,
-    Input,
-    InputClosed,
-    Error,
-    Unknown,
+/// Starts recording the data of the last `capacity` inputs with the given ID.
+///
+/// The recorded data stays in memory and can be read back through
+/// [`dora_get_history`], e.g. to dump the inputs that led to an anomaly. Only inputs
+/// that are handed out through [`dora_next_event`] or [`dora_drain_inputs`] are
+/// recorded. Calling this function again for the same input changes the capacity,
+/// dropping the oldest entries if necessary. A `capacity` of `0` stops the recording
+/// and frees the recorded data.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_enable_input_history(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    capacity: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|input_id| {
+        if !context.node.has_input(&input_id) {
+            eyre::bail!("unknown input {input_id}");
+        }
+        if capacity == 0 {
+            context.input_history.remove(&input_id);
+            return Ok(());
+        }
+        let history = context
+            .input_history
+            .entry(input_id)
+            .or_insert_with(|| InputHistory {
+                capacity,
+                entries: VecDeque::with_capacity(capacity),
+            });
+        history.capacity = capacity;
+        let excess = history.entries.len().saturating_sub(capacity);
+        history.entries.drain(..excess);
+        Ok(())
+    });
+    send_status(result)
 }
 
-/// Reads out the ID of the given input event.
+/// Reads out a recorded input of the given ID, see [`dora_enable_input_history`].
 ///
-/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
-/// ID string of the input. The ID is guaranteed to be valid UTF-8.
+/// The `index` counts backwards in time: `0` is the most recent input, `1` the one
+/// before, and so on. Writes the start pointer of the array values to
+/// `out_data_ptr`, the number of elements to `out_len`, and the data type of the
+/// elements to `out_dtype`. The pointer needs to be cast to the element type
+/// indicated by `out_dtype`.
 ///
-/// Writes a null pointer and length `0` if the given event is not an input event.
+/// Returns `0` on success. Returns `-1` and writes a null pointer and length `0` if
+/// the history of the input is not enabled, if fewer than `index + 1` inputs were
+/// recorded, or if the data is not a primitive array.
 ///
 /// ## Safety
 ///
-/// The `event` argument must be a dora event received through
-/// [`dora_next_event`]. The event must be still valid, i.e., not
-/// freed yet. The returned `out_ptr` must not be used after
-/// freeing the `event`, since it points directly into the event's
-/// memory.
+/// See the [crate-level safety requirements](crate#safety). The returned
+/// `out_data_ptr` points into the recorded history. It must not be used after the
+/// next call to [`dora_next_event`], [`dora_drain_inputs`], or
+/// [`dora_enable_input_history`], since these calls might remove the entry from the
+/// history.
 #[no_mangle]
-pub unsafe extern "C" fn read_dora_input_id(
-    event: *const (),
-    out_ptr: *mut *const u8,
+pub unsafe extern "C" fn dora_get_history(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    index: usize,
+    out_data_ptr: *mut *const c_void,
     out_len: *mut usize,
-) {
-    let event: &Event = unsafe { &*event.cast() };
-    match event {
-        Event::Input { id, .. } => {
-            let id = id.as_str().as_bytes();
-            let ptr = id.as_ptr();
-            let len = id.len();
+    out_dtype: *mut DoraDataType,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let values = unsafe { read_id(id_ptr, id_len) }.and_then(|input_id| {
+        let history = context
+            .input_history
+            .get(&input_id)
+            .ok_or_else(|| eyre::eyre!("input history of {input_id} is not enabled"))?;
+        let array = history.entries.iter().rev().nth(index).ok_or_else(|| {
+            eyre::eyre!(
+                "input history of {input_id} contains only {} entries",
+                history.entries.len()
+            )
+        })?;
+        primitive_values(array)
+    });
+    match values {
+        Ok((ptr, len, dtype)) => {
             unsafe {
-                *out_ptr = ptr;
+                *out_data_ptr = ptr;
                 *out_len = len;
+                *out_dtype = dtype;
             }
+            DoraStatus::Ok as isize
         }
-        _ => unsafe {
-            *out_ptr = ptr::null();
-            *out_len = 0;
-        },
-    }
-}
-
-#[no_mangle]
-pub unsafe extern "C" fn read_dora_input_data_u8(
-    event: *const (),
-    out_ptr: *mut *const u8,
-    out_len: *mut usize,
-) {
-    let event: &Event = unsafe { &*event.cast() };
-    match event {
-        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
-            dora_node_api::arrow::datatypes::DataType::UInt8 => {
-                let array: &UInt8Array = data.as_primitive();
-                let ptr = array.values().as_ptr();
-                unsafe {
-                    *out_ptr = ptr;
-                    *out_len = metadata.type_info.len;
-                }
-            }
-            dora_node_api::arrow::datatypes::DataType::Null => unsafe {
-                *out_ptr = ptr::null();
+        Err(err) => {
+            unsafe {
+                *out_data_ptr = ptr::null();
                 *out_len = 0;
-            },
-            _ => {
-                panic!("You used {}, must use U8!", metadata.type_info.data_type);
+                *out_dtype = DoraDataType::Unknown;
             }
-        },
-        _ => unsafe {
-            *out_ptr = ptr::null();
-            *out_len = 0;
-        },
+            send_status(Err(err))
+        }
     }
 }
 
+/// Limits the rate at which messages are sent on the given output.
+///
+/// After this call, `dora_send_output_*` calls for the given output that happen
+/// less than `1 / hz` seconds after the last accepted send are dropped silently and
+/// return [`DoraStatus::RateLimited`]. This allows downsampling high-rate data
+/// without manual timers. A `hz` value of `0` or less removes the rate limit.
+///
+/// Returns a [`DoraStatus`] code.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn read_dora_input_data_i32(
-    event: *const (),
-    out_ptr: *mut *const i32,
-    out_len: *mut usize,
-) {
-    let event: &Event = unsafe { &*event.cast() };
-    match event {
-        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
-            dora_node_api::arrow::datatypes::DataType::Int32 => {
-                let array: &Int32Array = data.as_primitive();
-                let ptr = array.values().as_ptr();
-                unsafe {
-                    *out_ptr = ptr;
-                    *out_len = metadata.type_info.len;
-                }
-            }
-            dora_node_api::arrow::datatypes::DataType::Null => unsafe {
-                *out_ptr = ptr::null();
-                *out_len = 0;
-            },
-            _ => {
-                panic!("You used {}, must use Int32!", metadata.type_info.data_type);
-            }
-        },
-        _ => unsafe {
-            *out_ptr = ptr::null();
-            *out_len = 0;
-        },
-    }
+pub unsafe extern "C" fn dora_set_output_rate_limit(
+    context: *mut c_void,
+    id_ptr: *const u8,
+    id_len: usize,
+    hz: f64,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let result = unsafe { read_id(id_ptr, id_len) }.and_then(|output_id| {
+        if !context.node.has_output(&output_id) {
+            eyre::bail!("unknown output {output_id}");
+        }
+        if hz > 0.0 {
+            let limit = RateLimit {
+                min_interval: Duration::from_secs_f64(1.0 / hz),
+                last_sent: None,
+            };
+            context.rate_limits.insert(output_id, limit);
+        } else {
+            context.rate_limits.remove(&output_id);
+        }
+        Ok(())
+    });
+    send_status(result)
 }
 
+/// Restricts sending to the given set of outputs, e.g. for switching between
+/// operating modes of the node.
+///
+/// After this call, `dora_send_output_*` calls for outputs that are not listed are
+/// skipped before any other processing and return [`DoraStatus::OutputDisabled`].
+/// The new set replaces the previous one as a whole, so that a node can switch
+/// between groups of outputs in one step. Passing a null `ids_ptr` makes all
+/// outputs active again. The output registered through [`dora_set_shutdown_output`]
+/// is always sent.
+///
+/// Returns a [`DoraStatus`] code. Fails without changing the active outputs if one
+/// of the IDs is not valid UTF-8 or not an output of the node.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). Unless `ids_ptr` is
+/// null, the `ids_ptr` and `id_lens_ptr` arguments must point to `count` elements
+/// each. Each ID pointer must point to the given number of bytes.
 #[no_mangle]
-pub unsafe extern "C" fn read_dora_input_data_f32(
-    event: *const (),
-    out_ptr: *mut *const f32,
-    out_len: *mut usize,
-) {
-    let event: &Event = unsafe { &*event.cast() };
-
-    match event {
-        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
-            dora_node_api::arrow::datatypes::DataType::Float32 => {
-                let array: &Float32Array = data.as_primitive();
-                let ptr = array.values().as_ptr();
-                unsafe {
-                    *out_ptr = ptr;
-                    *out_len = metadata.type_info.len;
-                }
-            }
-            dora_node_api::arrow::datatypes::DataType::Null => unsafe {
-                *out_ptr = ptr::null();
-                *out_len = 0;
-            },
-            _ => {
-                panic!(
-                    "You used {}, must use Float32!",
-                    metadata.type_info.data_type
-                );
+pub unsafe extern "C" fn dora_set_active_outputs(
+    context: *mut c_void,
+    ids_ptr: *const *const u8,
+    id_lens_ptr: *const usize,
+    count: usize,
+) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    if ids_ptr.is_null() {
+        context.active_outputs = None;
+        return DoraStatus::Ok as isize;
+    }
+    let (id_ptrs, id_lens) = unsafe {
+        (
+            slice::from_raw_parts(ids_ptr, count),
+            slice::from_raw_parts(id_lens_ptr, count),
+        )
+    };
+    let active = id_ptrs
+        .iter()
+        .zip(id_lens)
+        .map(|(ptr, len)| {
+            let output_id = unsafe { read_id(*ptr, *len) }?;
+            if !context.node.has_output(&output_id) {
+                eyre::bail!("unknown output {output_id}");
             }
-        },
-        _ => unsafe {
-            *out_ptr = ptr::null();
-            *out_len = 0;
-        },
+            Ok(output_id)
+        })
+        .collect::<eyre::Result<HashSet<_>>>();
+    match active {
+        Ok(active) => {
+            context.active_outputs = Some(active);
+            DoraStatus::Ok as isize
+        }
+        Err(err) => send_status(Err(err)),
     }
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn read_dora_input_data_u64(
-    event: *const (),
-    out_ptr: *mut *const u64,
-    out_len: *mut usize,
-) {
-    let event: &Event = unsafe { &*event.cast() };
-
-    match event {
-        Event::Input { data, metadata, .. } => match metadata.type_info.data_type {
-            dora_node_api::arrow::datatypes::DataType::UInt64 => {
-                let array: &UInt64Array = data.as_primitive();
-                let ptr = array.values().as_ptr();
-                unsafe {
-                    *out_ptr = ptr;
-                    *out_len = metadata.type_info.len;
-                }
-            }
-            dora_node_api::arrow::datatypes::DataType::Null => unsafe {
-                *out_ptr = ptr::null();
-                *out_len = 0;
-            },
-            _ => {
-                panic!(
-                    "You used {}, must use UInt64!",
-                    metadata.type_info.data_type
-                );
-            }
-        },
-        _ => unsafe {
-            *out_ptr = ptr::null();
-            *out_len = 0;
-        },
+/// Starts a new tick, i.e. a group of outputs that belong to the same logical step.
+///
+/// All `dora_send_output_*` calls after this function are buffered instead of being
+/// sent directly. They are sent together on the next [`dora_end_tick`] call, using a
+/// single shared metadata timestamp. This way, downstream nodes see all outputs of a
+/// tick as one coherent frame.
+///
+/// If [`dora_end_tick`] is never called, the buffered outputs are never sent. They are
+/// discarded (with a warning) when the context is freed.
+///
+/// Returns `0` on success and `-1` if a tick is already in progress.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
+#[no_mangle]
+pub unsafe extern "C" fn dora_begin_tick(context: *mut c_void) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    if context.tick.is_some() {
+        let err = eyre::eyre!("`dora_begin_tick` called while a tick is already in progress");
+        return send_status(Err(err));
     }
+    context.tick = Some(Vec::new());
+    DoraStatus::Ok as isize
 }
 
-/// Frees the given dora event.
+/// Finishes the tick started by [`dora_begin_tick`] and sends all buffered outputs.
+///
+/// All outputs of the tick share the same metadata timestamp. If sending one output
+/// fails, the remaining outputs are still sent.
+///
+/// Returns `0` on success and `-1` if no tick is in progress or if sending any of the
+/// buffered outputs failed.
 ///
 /// ## Safety
 ///
-/// Only pointers created through [`dora_next_event`] are allowed
-/// as arguments. Each context pointer must be freed exactly once. After
-/// freeing, the pointer and all derived pointers must not be used anymore.
-/// This also applies to the `read_dora_event_*` functions, which return
-/// pointers into the original event structure.
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn free_dora_event(event: *mut c_void) {
-    let _: Box<Event> = unsafe { Box::from_raw(event.cast()) };
+pub unsafe extern "C" fn dora_end_tick(context: *mut c_void) -> isize {
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    send_status(try_end_tick(context))
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn dora_send_output_u8(
-    context: *mut c_void,
-    id_ptr: *const u8,
-    id_len: usize,
-    data_ptr: *const u8,
-    data_len: usize,
-) -> isize {
-    match unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) } {
-        Ok(()) => 0,
-        Err(err) => {
+fn try_end_tick(context: &mut DoraContext) -> eyre::Result<()> {
+    let Some(pending) = context.tick.take() else {
+        eyre::bail!("`dora_end_tick` called without a matching `dora_begin_tick`");
+    };
+    let timestamp = context.new_timestamp();
+
+    let mut result = Ok(());
+    for (output_id, parameters, data_array) in pending {
+        if let Err(err) = context.send_array(output_id, parameters, data_array, timestamp) {
             tracing::error!("{err:?}");
-            -1
+            result = Err(err);
         }
     }
+    result.wrap_err("failed to send outputs of tick")
 }
 
+/// Reads out statistics about the time between consecutive events of the given
+/// input.
+///
+/// The time between two inputs is measured when they are handed out through
+/// [`dora_next_event`] (or the other functions that return input events, such as
+/// [`dora_drain_inputs`]). Thus, it includes delays of the node's own event loop,
+/// e.g. if the node is busy while inputs are queued. Writes the mean, the maximum,
+/// and the standard deviation of all intervals since the start of the node in
+/// nanoseconds. A growing standard deviation or maximum compared to the mean
+/// indicates that the input is delivered irregularly. Before the second event of
+/// the input, all values are `0`.
+///
+/// Returns a [`DoraStatus`] code. Fails if the given ID is not valid UTF-8 or does
+/// not name an input of this node.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn dora_send_output_i32(
+pub unsafe extern "C" fn dora_input_jitter_stats(
     context: *mut c_void,
     id_ptr: *const u8,
     id_len: usize,
-    data_ptr: *const i32,
-    data_len: usize,
+    out_mean_ns: *mut u64,
+    out_max_ns: *mut u64,
+    out_std_ns: *mut u64,
 ) -> isize {
-    match unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) } {
-        Ok(()) => 0,
-        Err(err) => {
-            tracing::error!("{err:?}");
-            -1
-        }
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let input_id = match unsafe { read_id(id_ptr, id_len) } {
+        Ok(id) => id,
+        Err(err) => return send_status(Err(err)),
+    };
+    if !context.node.has_input(&input_id) {
+        return send_status(Err(eyre::eyre!("unknown input {input_id}")));
     }
+    let (mean, max, std) = context
+        .arrival_stats
+        .get(&input_id)
+        .map(|stats| {
+            let max = stats.max.as_nanos().try_into().unwrap_or(u64::MAX);
+            (stats.mean as u64, max, stats.std_dev() as u64)
+        })
+        .unwrap_or_default();
+    unsafe {
+        *out_mean_ns = mean;
+        *out_max_ns = max;
+        *out_std_ns = std;
+    }
+    0
 }
 
+/// Reads out how many messages and bytes were sent on the given output so far.
+///
+/// Writes the number of successfully sent messages to `out_messages` and the total
+/// number of sent payload bytes to `out_bytes`. Outputs of an unfinished tick (see
+/// [`dora_begin_tick`]) are only counted once they are sent.
+///
+/// Returns a [`DoraStatus`] code. Fails if the given ID is not valid UTF-8 or does
+/// not name an output of this node.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn dora_send_output_f32(
+pub unsafe extern "C" fn dora_output_stats(
     context: *mut c_void,
     id_ptr: *const u8,
     id_len: usize,
-    data_ptr: *const f32,
-    data_len: usize,
+    out_messages: *mut u64,
+    out_bytes: *mut u64,
 ) -> isize {
-    match unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) } {
-        Ok(()) => 0,
-        Err(err) => {
-            tracing::error!("{err:?}");
-            -1
-        }
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return send_status(Err(err)),
+    };
+    let output_id = match unsafe { read_id(id_ptr, id_len) } {
+        Ok(id) => id,
+        Err(err) => return send_status(Err(err)),
+    };
+    if !context.node.has_output(&output_id) {
+        return send_status(Err(eyre::eyre!("unknown output {output_id}")));
     }
+    let (messages, bytes) = context
+        .output_stats
+        .get(&output_id)
+        .map(|stats| (stats.messages, stats.bytes))
+        .unwrap_or_default();
+    unsafe {
+        *out_messages = messages;
+        *out_bytes = bytes;
+    }
+    0
 }
+
+/// Records the current value of the metric with the given name.
+///
+/// Metrics are gauges that describe the internal state of the node, e.g. its queue
+/// depth or processing time. They are kept separate from the data outputs and are
+/// exported through OpenTelemetry (OTLP) every 10 seconds, like the system metrics
+/// of dora runtime nodes. The exporter sends them to the collector given in the
+/// `OTEL_EXPORTER_OTLP_METRICS_ENDPOINT` environment variable, defaulting to
+/// `http://localhost:4317`. All metrics of a node are reported as the
+/// `dora.node.metric` gauge of a meter named after the node ID, with the metric name
+/// as `name` attribute. Each export reports the last recorded value of each metric.
+///
+/// The exporter is started on the first call of this function. It requires the
+/// `metrics` feature of this library, which is disabled by default because it
+/// includes an async runtime and the OpenTelemetry exporter.
+///
+/// Returns a [`DoraStatus`] code. Fails if the name is empty or not valid UTF-8, if
+/// the exporter could not be started, or if the library was built without the
+/// `metrics` feature.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety).
 #[no_mangle]
-pub unsafe extern "C" fn dora_send_output_u64(
+pub unsafe extern "C" fn dora_emit_metric(
     context: *mut c_void,
-    id_ptr: *const u8,
-    id_len: usize,
-    data_ptr: *const u64,
-    data_len: usize,
+    name_ptr: *const u8,
+    name_len: usize,
+    value: f64,
 ) -> isize {
-    match unsafe { try_send_output(context, id_ptr, id_len, data_ptr, data_len) } {
-        Ok(()) => 0,
-        Err(err) => {
-            tracing::error!("{err:?}");
-            -1
+    let emit = || {
+        let context = unsafe { live_context(context) }?;
+        let name = std::str::from_utf8(unsafe { slice::from_raw_parts(name_ptr, name_len) })
+            .wrap_err("metric name is not valid UTF-8")?;
+        if name.is_empty() {
+            eyre::bail!("metric name must not be empty");
+        }
+        emit_metric(context, name, value)
+    };
+    send_status(emit())
+}
+
+#[cfg(feature = "metrics")]
+fn emit_metric(context: &mut DoraContext, name: &str, value: f64) -> eyre::Result<()> {
+    let metrics = match &mut context.metrics {
+        Some(metrics) => metrics,
+        None => context.metrics.insert(NodeMetrics::new(context.node.id())?),
+    };
+    metrics
+        .values
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(name.to_owned(), value);
+    Ok(())
+}
+
+#[cfg(not(feature = "metrics"))]
+fn emit_metric(_context: &mut DoraContext, _name: &str, _value: f64) -> eyre::Result<()> {
+    eyre::bail!("metrics are not supported, the library was built without the `metrics` feature")
+}
+
+/// Status codes returned by the `dora_send_output_*` functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoraStatus {
+    /// The operation was successful.
+    Ok = 0,
+    /// A generic error occurred, see [`dora_last_error`] for details.
+    Error = -1,
+    /// The given ID, or another string argument, is not valid UTF-8.
+    InvalidUtf8Id = -2,
+    /// The output was dropped because it exceeded the rate limit set through
+    /// [`dora_set_output_rate_limit`].
+    RateLimited = -3,
+    /// No shared memory region could be allocated for the output data, see
+    /// [`dora_output_would_block`].
+    ShmFull = -4,
+    /// The output was rejected because too many previous messages were not
+    /// acknowledged yet, see [`dora_set_output_window`].
+    WindowFull = -5,
+    /// The output was skipped because its data is identical to the last sent data,
+    /// see [`dora_send_output_f32_on_change`].
+    Unchanged = -6,
+    /// No reply to the request arrived within the timeout, see
+    /// [`dora_send_output_f32_sync`], or a barrier was not released within the
+    /// timeout, see [`dora_barrier`].
+    Timeout = -7,
+    /// The output was skipped because it is not among the outputs set through
+    /// [`dora_set_active_outputs`].
+    OutputDisabled = -8,
+}
+
+/// Converts the result of an operation to a [`DoraStatus`] code.
+///
+/// Errors are logged and stored as last error, see [`dora_last_error`].
+fn send_status(result: eyre::Result<()>) -> isize {
+    let err = match result {
+        Ok(()) => return DoraStatus::Ok as isize,
+        Err(err) => err,
+    };
+    if let Some(rate_limited) = err.downcast_ref::<RateLimited>() {
+        // dropping sends is the purpose of a rate limit, so this is not an error
+        set_last_error(rate_limited.to_string());
+        return DoraStatus::RateLimited as isize;
+    }
+    if let Some(disabled) = err.downcast_ref::<OutputDisabled>() {
+        // skipping sends is the purpose of deactivating outputs, so this is not an error
+        set_last_error(disabled.to_string());
+        return DoraStatus::OutputDisabled as isize;
+    }
+    tracing::error!("{err:?}");
+    if let Some(window_full) = err.downcast_ref::<WindowFull>() {
+        set_last_error(window_full.to_string());
+        return DoraStatus::WindowFull as isize;
+    }
+    if err.downcast_ref::<SharedMemoryFull>().is_some() {
+        set_last_error(format!("{err:?}"));
+        return DoraStatus::ShmFull as isize;
+    }
+    if let Some(timeout) = err.downcast_ref::<ReplyTimeout>() {
+        set_last_error(timeout.to_string());
+        return DoraStatus::Timeout as isize;
+    }
+    if let Some(timeout) = err.downcast_ref::<BarrierTimeout>() {
+        set_last_error(timeout.to_string());
+        return DoraStatus::Timeout as isize;
+    }
+    let status = match err.downcast_ref::<std::str::Utf8Error>() {
+        Some(utf8_err) => {
+            set_last_error(format!(
+                "{err}: invalid byte sequence at byte offset {}",
+                utf8_err.valid_up_to()
+            ));
+            DoraStatus::InvalidUtf8Id
+        }
+        None => {
+            set_last_error(format!("{err:?}"));
+            DoraStatus::Error
         }
+    };
+    status as isize
+}
+
+/// Converts the result of an operation that returns a non-negative value on success.
+///
+/// Errors are converted to a negative [`DoraStatus`] code like in [`send_status`].
+fn value_status(result: eyre::Result<isize>) -> isize {
+    match result {
+        Ok(value) => value,
+        Err(err) => send_status(Err(err)),
     }
 }
 
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/// Reads out the message of the last error that occurred on the calling thread.
+///
+/// Writes the `out_ptr` and `out_len` with the start pointer and length of the
+/// UTF8-encoded error message. Writes a null pointer and length `0` if no error
+/// occurred yet.
+///
+/// ## Safety
+///
+/// See the [crate-level safety requirements](crate#safety). The returned `out_ptr`
+/// must not be used after the next dora API call on the same thread, since the
+/// message might be replaced by a newer error.
+#[no_mangle]
+pub unsafe extern "C" fn dora_last_error(out_ptr: *mut *const u8, out_len: *mut usize) {
+    LAST_ERROR.with(|last_error| match last_error.borrow().as_deref() {
+        Some(message) => unsafe {
+            *out_ptr = message.as_ptr();
+            *out_len = message.len();
+        },
+        None => unsafe {
+            *out_ptr = ptr::null();
+            *out_len = 0;
+        },
+    });
+}
+
 pub trait ToArrow {
     fn to_arrow(self) -> Arc<dyn Array>;
 }
@@ -395,6 +8064,43 @@ impl ToArrow for &[u8] {
     }
 }
 
+/// Addresses of all contexts that were created and not freed yet, see
+/// [`live_context`].
+static LIVE_CONTEXTS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
+/// Moves the given context to the heap and registers it as live.
+fn into_raw_context(context: DoraContext) -> *mut c_void {
+    let context: *mut c_void = Box::into_raw(Box::new(context)).cast();
+    LIVE_CONTEXTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(context as usize);
+    context
+}
+
+/// Returns the context behind the given pointer, or an error if the pointer does not
+/// belong to a live context, e.g. because the context was already freed.
+///
+/// This is only a best-effort safety net against callbacks that outlive their
+/// context: the check is based on the address of the context, so it doesn't detect
+/// freed pointers whose memory was reused for a new context. Using a freed context
+/// is still undefined behavior.
+///
+/// ## Safety
+///
+/// If the pointer belongs to a live context, the context must not be accessed
+/// concurrently.
+unsafe fn live_context<'a>(context: *mut c_void) -> eyre::Result<&'a mut DoraContext> {
+    let live = LIVE_CONTEXTS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .contains(&(context as usize));
+    if !live {
+        eyre::bail!("invalid dora context: the context was already freed or never created");
+    }
+    Ok(unsafe { &mut *context.cast() })
+}
+
 unsafe fn try_send_output<T>(
     context: *mut c_void,
     id_ptr: *const u8,
@@ -405,13 +8111,841 @@ unsafe fn try_send_output<T>(
 where
     for<'a> &'a [T]: ToArrow,
 {
-    let context: &mut DoraContext = unsafe { &mut *context.cast() };
-    let id = std::str::from_utf8(unsafe { slice::from_raw_parts(id_ptr, id_len) })?;
-    let output_id = id.to_owned().into();
+    let context = match unsafe { live_context(context) } {
+        Ok(context) => context,
+        Err(err) => return Err(err),
+    };
+    let output_id = unsafe { read_id(id_ptr, id_len) }?;
 
     let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
     let data_array = data.to_arrow();
-    context
-        .node
-        .send_output(output_id, Default::default(), data_array)
+    context.send_output(output_id, Default::default(), data_array)
+}
+
+/// Reads the UTF8-encoded input or output ID given through `id_ptr` and `id_len`.
+///
+/// ## Safety
+///
+/// The `id_ptr` and `id_len` fields must be the start pointer and length of a byte
+/// slice that is valid for reads.
+unsafe fn read_id(id_ptr: *const u8, id_len: usize) -> eyre::Result<DataId> {
+    let id = std::str::from_utf8(unsafe { slice::from_raw_parts(id_ptr, id_len) })
+        .wrap_err("ID is not valid UTF-8")?;
+    Ok(id.to_owned().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    fn standalone_context() -> *mut c_void {
+        let config = r#"{"node_id": "test", "inputs": ["in", "other", "ack"], "outputs": ["out"]}"#;
+        standalone_context_from(config)
+    }
+
+    fn standalone_context_from(config: &str) -> *mut c_void {
+        let context = unsafe { init_dora_context_standalone(config.as_ptr(), config.len()) };
+        assert!(!context.is_null());
+        context
+    }
+
+    /// Pushes an input with the given data and metadata parameters.
+    fn push_array(
+        context: *mut c_void,
+        input: &str,
+        array: ArrayRef,
+        parameters: MetadataParameters,
+    ) {
+        let event = input_event(input, array, parameters);
+        event_sender(context).send(event).unwrap();
+    }
+
+    /// Returns the sender that feeds the event stream of the given standalone context.
+    fn event_sender(context: *mut c_void) -> mpsc::Sender<Event> {
+        match &unsafe { live_context(context) }.unwrap().node {
+            NodeHandle::Standalone(node) => node.events.clone().unwrap(),
+            NodeHandle::Dora(_) => unreachable!(),
+        }
+    }
+
+    fn input_event(input: &str, array: ArrayRef, parameters: MetadataParameters) -> Event {
+        let array_data = array.to_data();
+        let mut buffer = vec![0; required_data_size(&array_data)];
+        let type_info = copy_array_into_sample(&mut buffer, &array_data);
+        let mut metadata = Metadata::new(uhlc::HLC::default().new_timestamp(), type_info);
+        metadata.parameters = parameters;
+        Event::Input {
+            id: DataId::from(input.to_owned()),
+            metadata,
+            data: ArrowData(array),
+        }
+    }
+
+    fn parameter(key: &str, value: Parameter) -> MetadataParameters {
+        let mut parameters = MetadataParameters::default();
+        parameters.custom.insert(key.to_owned(), value);
+        parameters
+    }
+
+    fn push(context: *mut c_void, input: &str, value: f32) {
+        let status = unsafe {
+            dora_standalone_push_input_f32(context, input.as_ptr(), input.len(), &value, 1)
+        };
+        assert_eq!(status, DoraStatus::Ok as isize);
+    }
+
+    fn send(context: *mut c_void, value: f32) -> isize {
+        let id = "out";
+        unsafe { dora_send_output_f32(context, id.as_ptr(), id.len(), &value, 1) }
+    }
+
+    fn sent_messages(context: *mut c_void) -> u64 {
+        let context = unsafe { live_context(context) }.unwrap();
+        let output_id = DataId::from("out".to_owned());
+        context
+            .output_stats
+            .get(&output_id)
+            .map_or(0, |s| s.messages)
+    }
+
+    /// Returns the ID and value of the next event, or `None` if it is not an input.
+    fn next_input(context: *mut c_void) -> Option<(String, f32)> {
+        let event = unsafe { dora_next_event(context) };
+        assert!(!event.is_null());
+        let input = match unsafe { read_dora_event_type(event.cast()) } {
+            EventType::Input => {
+                let (mut id_ptr, mut id_len) = (ptr::null(), 0);
+                let (mut data_ptr, mut data_len) = (ptr::null(), 0);
+                let id = unsafe {
+                    read_dora_input_id(event.cast(), &mut id_ptr, &mut id_len);
+                    read_dora_input_data_f32(event.cast(), &mut data_ptr, &mut data_len);
+                    std::str::from_utf8(slice::from_raw_parts(id_ptr, id_len)).unwrap()
+                };
+                assert_eq!(data_len, 1);
+                Some((id.to_owned(), unsafe { *data_ptr }))
+            }
+            _ => None,
+        };
+        unsafe { free_dora_event(event) };
+        input
+    }
+
+    /// Pushes an acknowledgement of the given sequence number on the `ack` input.
+    fn push_ack(context: *mut c_void, sequence: u64) {
+        let array = Arc::new(UInt64Array::from(vec![sequence]));
+        push_array(context, "ack", array, Default::default());
+    }
+
+    fn input(id: &str, value: f32) -> Option<(String, f32)> {
+        Some((id.to_owned(), value))
+    }
+
+    #[test]
+    fn tick_buffers_outputs() {
+        let context = standalone_context();
+        assert_eq!(unsafe { dora_begin_tick(context) }, 0);
+        assert_eq!(unsafe { dora_begin_tick(context) }, -1);
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        assert_eq!(sent_messages(context), 0);
+        assert_eq!(unsafe { dora_end_tick(context) }, 0);
+        assert_eq!(sent_messages(context), 2);
+        assert_eq!(unsafe { dora_end_tick(context) }, -1);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn rate_limit_drops_outputs() {
+        let context = standalone_context();
+        let id = "out";
+        let status = unsafe { dora_set_output_rate_limit(context, id.as_ptr(), id.len(), 0.01) };
+        assert_eq!(status, DoraStatus::Ok as isize);
+
+        // failed sends don't count towards the rate limit
+        let outputs = |context| match &mut unsafe { live_context(context) }.unwrap().node {
+            NodeHandle::Standalone(node) => &mut node.outputs,
+            NodeHandle::Dora(_) => unreachable!(),
+        };
+        outputs(context).clear();
+        assert_eq!(send(context, 1.0), DoraStatus::Error as isize);
+        outputs(context).insert(DataId::from(id.to_owned()));
+
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 3.0), DoraStatus::RateLimited as isize);
+        assert_eq!(sent_messages(context), 1);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn window_limits_unacknowledged_outputs() {
+        let context = standalone_context();
+        let (id, ack) = ("out", "ack");
+        let status = unsafe {
+            dora_set_output_window(context, id.as_ptr(), id.len(), ack.as_ptr(), ack.len(), 2)
+        };
+        assert_eq!(status, DoraStatus::Ok as isize);
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 3.0), DoraStatus::WindowFull as isize);
+        assert_eq!(sent_messages(context), 2);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn acks_reopen_window() {
+        let context = standalone_context();
+        let (id, ack) = ("out", "ack");
+        unsafe {
+            dora_set_output_window(context, id.as_ptr(), id.len(), ack.as_ptr(), ack.len(), 2);
+            dora_set_event_queue_policy(context, 1, false);
+        }
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 3.0), DoraStatus::WindowFull as isize);
+
+        // acknowledges the first message, the queue policy must not drop it
+        push(context, "in", 4.0);
+        push_ack(context, 0);
+        assert_eq!(send(context, 5.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 6.0), DoraStatus::WindowFull as isize);
+        // acknowledgements are not handed out as events
+        push_ack(context, 2);
+        assert_eq!(next_input(context), input("in", 4.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 0);
+        assert_eq!(send(context, 7.0), DoraStatus::Ok as isize);
+        assert_eq!(sent_messages(context), 4);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn queue_policy_drops_inputs() {
+        let context = standalone_context();
+        unsafe { dora_set_event_queue_policy(context, 2, true) };
+        for value in [1.0, 2.0, 3.0] {
+            push(context, "in", value);
+        }
+        assert_eq!(next_input(context), input("in", 2.0));
+        assert_eq!(next_input(context), input("in", 3.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 1);
+        unsafe { free_dora_context(context) };
+
+        let context = standalone_context();
+        unsafe { dora_set_event_queue_policy(context, 2, false) };
+        for value in [1.0, 2.0, 3.0] {
+            push(context, "in", value);
+        }
+        assert_eq!(next_input(context), input("in", 1.0));
+        assert_eq!(next_input(context), input("in", 2.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 1);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn latest_wins_link_policy() {
+        let context = standalone_context();
+        assert_eq!(
+            unsafe { dora_set_link_policy(context, false) },
+            DoraStatus::Ok as isize
+        );
+        push(context, "in", 1.0);
+        push(context, "other", 5.0);
+        push(context, "in", 2.0);
+        assert_eq!(next_input(context), input("other", 5.0));
+        assert_eq!(next_input(context), input("in", 2.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 1);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn paused_inputs_are_buffered() {
+        let context = standalone_context();
+        assert_eq!(
+            unsafe { dora_pause_inputs(context) },
+            DoraStatus::Ok as isize
+        );
+        push(context, "in", 1.0);
+        assert_eq!(
+            unsafe { dora_resume_inputs(context) },
+            DoraStatus::Ok as isize
+        );
+        assert_eq!(next_input(context), input("in", 1.0));
+
+        // only the `Stop` event is delivered while paused
+        unsafe { dora_pause_inputs(context) };
+        push(context, "in", 2.0);
+        assert_eq!(
+            unsafe { dora_standalone_finish(context) },
+            DoraStatus::Ok as isize
+        );
+        assert_eq!(next_input(context), None);
+        assert!(unsafe { dora_next_event(context) }.is_null());
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn drop_oldest_event() {
+        let context = standalone_context();
+        push(context, "in", 1.0);
+        push(context, "in", 2.0);
+        unsafe { dora_standalone_finish(context) };
+        assert_eq!(
+            unsafe { dora_drop_oldest_event(context) },
+            EventType::Input as i32
+        );
+        assert_eq!(next_input(context), input("in", 2.0));
+        // the `Stop` event is never dropped
+        assert_eq!(unsafe { dora_drop_oldest_event(context) }, -1);
+        assert_eq!(next_input(context), None);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn reconnect_is_refused() {
+        unsafe extern "C" fn ignore(_: *mut c_void, _: *const c_void, _: *mut c_void) {}
+        let last_error = || LAST_ERROR.with(|err| err.borrow().clone()).unwrap();
+
+        let context = standalone_context();
+        assert_eq!(
+            unsafe { dora_reconnect(context) },
+            DoraStatus::Error as isize
+        );
+        assert!(last_error().contains("standalone"));
+
+        let status = unsafe { dora_start_background_loop(context, ignore, ptr::null_mut()) };
+        assert_eq!(status, DoraStatus::Ok as isize);
+        assert_eq!(
+            unsafe { dora_reconnect(context) },
+            DoraStatus::Error as isize
+        );
+        assert!(last_error().contains("background loop"));
+        unsafe { dora_stop_background_loop(context) };
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn timeout_receive_reconnects_after_outage() {
+        let context = standalone_context();
+        let inner = unsafe { live_context(context) }.unwrap();
+        inner.auto_reconnect = Some(AutoReconnect {
+            buffer_outputs: false,
+            max_buffer_bytes: 0,
+            buffered: VecDeque::new(),
+            buffered_bytes: 0,
+            connected: true,
+            last_attempt: None,
+        });
+        push(context, "in", 1.0);
+        // close the event stream without a `Stop` event
+        if let NodeHandle::Standalone(node) = &mut inner.node {
+            node.events = None;
+        }
+        let timeout = Duration::from_secs(1);
+        assert!(matches!(
+            inner.next_event_timeout(timeout),
+            Ok(Event::Input { .. })
+        ));
+        assert!(matches!(
+            inner.next_event_timeout(timeout),
+            Err(RecvTimeoutError::Disconnected)
+        ));
+        // the reconnection was attempted, but failed
+        assert!(!inner.auto_reconnect.as_ref().unwrap().connected);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn measure_rtt_keeps_acks_and_inputs() {
+        let context = standalone_context_from(
+            r#"{"node_id": "test", "inputs": ["in", "ack", "pong_down"], "outputs": ["out", "ping_down"]}"#,
+        );
+        let (id, ack, downstream) = ("out", "ack", "down");
+        unsafe {
+            dora_set_output_window(context, id.as_ptr(), id.len(), ack.as_ptr(), ack.len(), 1);
+            dora_set_event_queue_policy(context, 1, false);
+        }
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::WindowFull as isize);
+
+        push(context, "in", 3.0);
+        push_array(
+            context,
+            ack,
+            Arc::new(UInt64Array::from(vec![0])),
+            Default::default(),
+        );
+        push_array(
+            context,
+            "pong_down",
+            Arc::new(NullArray::new(0)),
+            parameter(RTT_PROBE_PARAMETER, Parameter::Integer(0)),
+        );
+        let mut nanos = 0;
+        let status = unsafe {
+            dora_measure_rtt(
+                context,
+                downstream.as_ptr(),
+                downstream.len(),
+                &mut nanos,
+                1000,
+            )
+        };
+        assert_eq!(status, DoraStatus::Ok as isize);
+        // the acknowledgement neither took the place of the input nor got dropped
+        assert_eq!(send(context, 4.0), DoraStatus::Ok as isize);
+        assert_eq!(next_input(context), input("in", 3.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 0);
+
+        let status = unsafe {
+            dora_measure_rtt(
+                context,
+                downstream.as_ptr(),
+                downstream.len(),
+                &mut nanos,
+                10,
+            )
+        };
+        assert_eq!(status, DoraStatus::Timeout as isize);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn sync_request_keeps_acks_and_inputs() {
+        let context = standalone_context_from(
+            r#"{"node_id": "test", "inputs": ["in", "ack", "reply_out"], "outputs": ["out"]}"#,
+        );
+        let (id, ack) = ("out", "ack");
+        unsafe {
+            dora_set_output_window(context, id.as_ptr(), id.len(), ack.as_ptr(), ack.len(), 1);
+            dora_set_event_queue_policy(context, 1, false);
+        }
+        push(context, "in", 1.0);
+        // the acknowledgement and the reply arrive while waiting for the reply
+        let sender = event_sender(context);
+        let responder = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            let ack = input_event(
+                ack,
+                Arc::new(UInt64Array::from(vec![0])),
+                Default::default(),
+            );
+            let reply = input_event(
+                "reply_out",
+                Arc::new(Float32Array::from(vec![2.0])),
+                parameter(CORRELATION_PARAMETER, Parameter::Integer(0)),
+            );
+            sender.send(ack).unwrap();
+            sender.send(reply).unwrap();
+        });
+        let value = 3.0f32;
+        let status =
+            unsafe { dora_send_output_f32_sync(context, id.as_ptr(), id.len(), &value, 1, 5000) };
+        responder.join().unwrap();
+        assert_eq!(status, DoraStatus::Ok as isize);
+        assert_eq!(next_input(context), input("reply_out", 2.0));
+        assert_eq!(next_input(context), input("in", 1.0));
+        assert_eq!(unsafe { dora_dropped_events(context) }, 0);
+        // the request was acknowledged, so the window has room again
+        assert_eq!(send(context, 4.0), DoraStatus::Ok as isize);
+
+        let status =
+            unsafe { dora_send_output_f32_sync(context, id.as_ptr(), id.len(), &value, 1, 10) };
+        assert_eq!(status, DoraStatus::WindowFull as isize);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn collect_latest_inputs() {
+        let context = standalone_context();
+        let (id, ack) = ("out", "ack");
+        unsafe {
+            dora_set_output_window(context, id.as_ptr(), id.len(), ack.as_ptr(), ack.len(), 1);
+        }
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        push(context, "in", 1.0);
+        push(context, "in", 2.0);
+        push(context, "other", 3.0);
+        push_ack(context, 0);
+
+        let inner = unsafe { live_context(context) }.unwrap();
+        let latest = inner.collect_latest(Duration::ZERO);
+        let value = |id: &str| {
+            latest[&DataId::from(id.to_owned())]
+                .as_primitive::<Float32Type>()
+                .value(0)
+        };
+        assert_eq!(latest.len(), 2);
+        assert_eq!(value("in"), 2.0);
+        assert_eq!(value("other"), 3.0);
+        // the acknowledgement was processed instead of being collected
+        assert_eq!(send(context, 4.0), DoraStatus::Ok as isize);
+
+        // paused inputs are kept until they are resumed
+        unsafe { dora_pause_inputs(context) };
+        push(context, "in", 5.0);
+        let inner = unsafe { live_context(context) }.unwrap();
+        assert!(inner.collect_latest(Duration::ZERO).is_empty());
+        unsafe { dora_resume_inputs(context) };
+        unsafe { dora_standalone_finish(context) };
+        let inner = unsafe { live_context(context) }.unwrap();
+        let latest = inner.collect_latest(Duration::ZERO);
+        assert_eq!(latest.len(), 1);
+        // the `Stop` event is kept for `dora_next_event`
+        assert_eq!(next_input(context), None);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn outputs_are_numbered() {
+        let context = standalone_context();
+        let next_sequence = |context| {
+            let context = unsafe { live_context(context) }.unwrap();
+            context
+                .sequence_numbers
+                .get(&DataId::from("out".to_owned()))
+                .copied()
+        };
+        assert_eq!(next_sequence(context), None);
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        assert_eq!(next_sequence(context), Some(2));
+
+        // failed sends don't advance the sequence number
+        match &mut unsafe { live_context(context) }.unwrap().node {
+            NodeHandle::Standalone(node) => node.outputs.clear(),
+            NodeHandle::Dora(_) => unreachable!(),
+        }
+        assert_eq!(send(context, 3.0), DoraStatus::Error as isize);
+        assert_eq!(next_sequence(context), Some(2));
+
+        let array: ArrayRef = Arc::new(Float32Array::from(vec![4.0]));
+        let parameters = parameter(SEQUENCE_PARAMETER, Parameter::Integer(7));
+        push_array(context, "in", array, parameters);
+        push(context, "in", 5.0);
+        let mut sequence = 0;
+        let event = unsafe { dora_next_event(context) };
+        assert_eq!(
+            unsafe { read_dora_input_sequence(event.cast(), &mut sequence) },
+            0
+        );
+        assert_eq!(sequence, 7);
+        unsafe { free_dora_event(event) };
+        // inputs of nodes that don't number their outputs have no sequence number
+        let event = unsafe { dora_next_event(context) };
+        assert_eq!(
+            unsafe { read_dora_input_sequence(event.cast(), &mut sequence) },
+            -1
+        );
+        unsafe { free_dora_event(event) };
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn invalid_ids_are_reported() {
+        let last_error = || LAST_ERROR.with(|err| err.borrow().clone()).unwrap();
+        let context = standalone_context();
+        let invalid = b"ou\xfft";
+        let value = 1.0f32;
+        let status =
+            unsafe { dora_send_output_f32(context, invalid.as_ptr(), invalid.len(), &value, 1) };
+        assert_eq!(status, DoraStatus::InvalidUtf8Id as isize);
+        assert!(last_error().ends_with("invalid byte sequence at byte offset 2"));
+
+        let count = unsafe { dora_output_subscriber_count(context, invalid.as_ptr(), 4) };
+        assert_eq!(count, DoraStatus::InvalidUtf8Id as i32);
+        let capacity = unsafe { dora_output_queue_capacity(context, invalid.as_ptr(), 4) };
+        assert_eq!(capacity, DoraStatus::InvalidUtf8Id as isize);
+
+        let unknown = "unknown";
+        let count = unsafe { dora_output_subscriber_count(context, unknown.as_ptr(), 7) };
+        assert_eq!(count, DoraStatus::Error as i32);
+        assert!(last_error().contains("unknown output unknown"));
+        let id = "out";
+        let count = unsafe { dora_output_subscriber_count(context, id.as_ptr(), id.len()) };
+        assert_eq!(count, 0);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn tick_shares_one_timestamp() {
+        static CLOCK_READS: AtomicU64 = AtomicU64::new(0);
+        unsafe extern "C" fn now() -> u64 {
+            CLOCK_READS.fetch_add(1, Ordering::SeqCst) + 1
+        }
+        let context = standalone_context();
+        unsafe { dora_set_clock_source(context, Some(now)) };
+
+        assert_eq!(unsafe { dora_begin_tick(context) }, 0);
+        assert_eq!(send(context, 1.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 2.0), DoraStatus::Ok as isize);
+        // unknown outputs are rejected right away instead of failing the tick
+        let (id, value) = ("unknown", 3.0f32);
+        let status = unsafe { dora_send_output_f32(context, id.as_ptr(), id.len(), &value, 1) };
+        assert_eq!(status, DoraStatus::Error as isize);
+        assert_eq!(CLOCK_READS.load(Ordering::SeqCst), 0);
+        assert_eq!(unsafe { dora_end_tick(context) }, 0);
+        assert_eq!(sent_messages(context), 2);
+        assert_eq!(CLOCK_READS.load(Ordering::SeqCst), 1);
+
+        // outside of a tick, every output gets its own timestamp
+        assert_eq!(send(context, 4.0), DoraStatus::Ok as isize);
+        assert_eq!(send(context, 5.0), DoraStatus::Ok as isize);
+        assert_eq!(CLOCK_READS.load(Ordering::SeqCst), 3);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn queue_policy_keeps_stop_events() {
+        let context = standalone_context();
+        unsafe { dora_set_event_queue_policy(context, 1, false) };
+        push(context, "in", 1.0);
+        push(context, "in", 2.0);
+        unsafe { dora_standalone_finish(context) };
+        assert_eq!(next_input(context), input("in", 1.0));
+        // the `Stop` event neither counts towards the limit nor is dropped
+        assert_eq!(next_input(context), None);
+        assert!(unsafe { live_context(context) }.unwrap().stopped);
+        assert_eq!(unsafe { dora_dropped_events(context) }, 1);
+        unsafe { free_dora_context(context) };
+
+        // a limit of `0` removes the limit again
+        let context = standalone_context();
+        unsafe { dora_set_event_queue_policy(context, 1, false) };
+        unsafe { dora_set_event_queue_policy(context, 0, false) };
+        for value in [1.0, 2.0, 3.0] {
+            push(context, "in", value);
+        }
+        for value in [1.0, 2.0, 3.0] {
+            assert_eq!(next_input(context), input("in", value));
+        }
+        assert_eq!(unsafe { dora_dropped_events(context) }, 0);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn on_change_skips_identical_data() {
+        let context = standalone_context();
+        let id = "out";
+        let send_on_change = |values: &[f32]| unsafe {
+            dora_send_output_f32_on_change(
+                context,
+                id.as_ptr(),
+                id.len(),
+                values.as_ptr(),
+                values.len(),
+            )
+        };
+        assert_eq!(send_on_change(&[1.0, 2.0]), DoraStatus::Ok as isize);
+        assert_eq!(send_on_change(&[1.0, 2.0]), DoraStatus::Unchanged as isize);
+        assert_eq!(send_on_change(&[1.0]), DoraStatus::Ok as isize);
+        assert_eq!(sent_messages(context), 2);
+
+        // failed sends don't update the cached payload
+        let outputs = |context| match &mut unsafe { live_context(context) }.unwrap().node {
+            NodeHandle::Standalone(node) => &mut node.outputs,
+            NodeHandle::Dora(_) => unreachable!(),
+        };
+        outputs(context).clear();
+        assert_eq!(send_on_change(&[3.0]), DoraStatus::Error as isize);
+        outputs(context).insert(DataId::from(id.to_owned()));
+        assert_eq!(send_on_change(&[3.0]), DoraStatus::Ok as isize);
+        assert_eq!(sent_messages(context), 3);
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn accumulator_sends_appended_values() {
+        let context = standalone_context();
+        let (id, unknown) = ("out", "unknown");
+        let float32 = DoraDataType::Float32 as i32;
+        let new_accumulator = |id: &str, dtype| unsafe {
+            dora_output_accumulator_new(context, id.as_ptr(), id.len(), dtype)
+        };
+        assert!(new_accumulator(unknown, float32).is_null());
+        assert!(new_accumulator(id, DoraDataType::UInt8 as i32).is_null());
+
+        let accumulator = new_accumulator(id, float32);
+        assert!(!accumulator.is_null());
+        unsafe {
+            dora_output_accumulator_push_f32(accumulator, [1.0, 2.0].as_ptr(), 2);
+            dora_output_accumulator_push_f32(accumulator, [3.0].as_ptr(), 1);
+        }
+        // outputs of a tick are buffered, so they can be inspected
+        assert_eq!(unsafe { dora_begin_tick(context) }, 0);
+        let status = unsafe { dora_output_accumulator_flush(accumulator) };
+        assert_eq!(status, DoraStatus::Ok as isize);
+        // the accumulator is empty after flushing
+        let status = unsafe { dora_output_accumulator_flush(accumulator) };
+        assert_eq!(status, DoraStatus::Ok as isize);
+        let tick = unsafe { live_context(context) }
+            .unwrap()
+            .tick
+            .clone()
+            .unwrap();
+        let values: Vec<_> = tick
+            .iter()
+            .map(|(_, _, data)| data.as_primitive::<Float32Type>().values().to_vec())
+            .collect();
+        assert_eq!(values, [vec![1.0, 2.0, 3.0], vec![]]);
+        assert_eq!(unsafe { dora_end_tick(context) }, 0);
+        assert_eq!(sent_messages(context), 2);
+
+        unsafe { free_dora_output_accumulator(accumulator) };
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn window_reader_looks_ahead() {
+        let context = standalone_context();
+        for value in [1.0, 2.0, 3.0] {
+            push(context, "in", value);
+        }
+        unsafe { dora_standalone_finish(context) };
+        assert!(unsafe { dora_window_reader_new(context, 0) }.is_null());
+        let reader = unsafe { dora_window_reader_new(context, 2) };
+        assert!(!reader.is_null());
+
+        let get = |offset| {
+            let mut event = ptr::null();
+            let status = unsafe { dora_window_get(reader, offset, &mut event) };
+            (status == DoraStatus::Ok as isize).then_some(event)
+        };
+        let value = |offset| {
+            let mut value = 0.0;
+            let status = unsafe { read_dora_input_scalar_f32(get(offset)?.cast(), &mut value) };
+            (status == DoraStatus::Ok as isize).then_some(value)
+        };
+        assert_eq!(value(1), Some(2.0));
+        assert_eq!(value(0), Some(1.0));
+        // events are not consumed by reading them
+        assert_eq!(get(0), get(0));
+        assert_eq!(get(2), None);
+
+        assert_eq!(unsafe { dora_window_advance(reader) }, 0);
+        assert_eq!(value(0), Some(2.0));
+        assert_eq!(value(1), Some(3.0));
+        assert_eq!(unsafe { dora_window_advance(reader) }, 0);
+        assert_eq!(unsafe { dora_window_advance(reader) }, 0);
+        let stop = get(0).unwrap();
+        assert!(matches!(
+            unsafe { read_dora_event_type(stop.cast()) },
+            EventType::Stop
+        ));
+        // the event stream ends after the `Stop` event
+        assert_eq!(unsafe { dora_window_advance(reader) }, 0);
+        assert_eq!(get(0), None);
+        assert_eq!(unsafe { dora_window_advance(reader) }, -1);
+
+        unsafe { free_dora_window_reader(reader) };
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn compressed_inputs_are_decompressed() {
+        let context = standalone_context();
+        let plain = b"compressible compressible compressible".to_vec();
+        let compressed = [
+            ("lz4", lz4_flex::compress_prepend_size(&plain)),
+            ("zstd", zstd::encode_all(plain.as_slice(), 0).unwrap()),
+            ("zstd", b"invalid".to_vec()),
+            ("unknown", plain.clone()),
+        ];
+        for (algorithm, data) in compressed {
+            let array: ArrayRef = Arc::new(UInt8Array::from(data));
+            let parameters = parameter(
+                COMPRESSION_PARAMETER,
+                Parameter::String(algorithm.to_owned()),
+            );
+            push_array(context, "in", array, parameters);
+        }
+
+        let read = |event: *mut c_void| {
+            let (mut data_ptr, mut data_len) = (ptr::null(), 0);
+            let status =
+                unsafe { read_dora_input_data_u8(event.cast(), &mut data_ptr, &mut data_len) };
+            let data = (status == DoraStatus::Ok as isize)
+                .then(|| unsafe { slice::from_raw_parts(data_ptr, data_len) }.to_vec());
+            (data, data_ptr)
+        };
+        for _ in 0..2 {
+            let event = unsafe { dora_next_event(context) };
+            let (data, data_ptr) = read(event);
+            assert_eq!(data.as_ref(), Some(&plain));
+            // the decompressed data is kept in the event
+            assert_eq!(read(event).1, data_ptr);
+            unsafe { free_dora_event(event) };
+        }
+        for _ in 0..2 {
+            let event = unsafe { dora_next_event(context) };
+            assert_eq!(read(event), (None, ptr::null()));
+            unsafe { free_dora_event(event) };
+        }
+        unsafe { free_dora_context(context) };
+    }
+
+    #[test]
+    fn scalar_filter_statistics() {
+        assert!(dora_scalar_filter_new(0).is_null());
+        let filter = dora_scalar_filter_new(3);
+        let statistics = || {
+            let (mut mean, mut min, mut max) = (0.0, 0.0, 0.0);
+            let status = unsafe {
+                [
+                    dora_scalar_filter_mean(filter, &mut mean),
+                    dora_scalar_filter_min(filter, &mut min),
+                    dora_scalar_filter_max(filter, &mut max),
+                ]
+            };
+            status
+                .iter()
+                .all(|s| *s == DoraStatus::Ok as isize)
+                .then_some((mean, min, max))
+        };
+        assert_eq!(statistics(), None);
+
+        unsafe {
+            dora_scalar_filter_push(filter, 4.0);
+            dora_scalar_filter_push_f32(filter, -2.0);
+        }
+        assert_eq!(statistics(), Some((1.0, -2.0, 4.0)));
+        // the oldest values leave the window
+        for value in [3.0, 6.0, 9.0] {
+            unsafe { dora_scalar_filter_push(filter, value) };
+        }
+        assert_eq!(statistics(), Some((6.0, 3.0, 9.0)));
+        unsafe { free_dora_scalar_filter(filter) };
+    }
+
+    #[test]
+    fn reshaped_read_checks_shape() {
+        let context = standalone_context();
+        let values: Vec<f32> = (0..6).map(|v| v as f32).collect();
+        let array: ArrayRef = Arc::new(Float32Array::from(values.clone()));
+        push_array(context, "in", array, MetadataParameters::default());
+        let event = unsafe { dora_next_event(context) };
+
+        let reshaped = |dims: &[usize]| {
+            let mut data_ptr = ptr::null();
+            let status = unsafe {
+                read_dora_input_data_f32_reshaped(
+                    event.cast(),
+                    dims.as_ptr(),
+                    dims.len(),
+                    &mut data_ptr,
+                )
+            };
+            (status, data_ptr)
+        };
+        for dims in [&[2, 3][..], &[3, 2], &[6], &[1, 2, 3]] {
+            let (status, data_ptr) = reshaped(dims);
+            assert_eq!(status, DoraStatus::Ok as isize);
+            assert_eq!(unsafe { slice::from_raw_parts(data_ptr, 6) }, values);
+        }
+        // the product of the dimensions must match, without overflowing
+        for dims in [&[4, 2][..], &[], &[usize::MAX, 2]] {
+            assert_eq!(reshaped(dims), (DoraStatus::Error as isize, ptr::null()));
+        }
+        unsafe { free_dora_event(event) };
+        unsafe { free_dora_context(context) };
+    }
 }
@@ -1,4 +1,8 @@
-use std::{ptr::NonNull, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    ptr::NonNull,
+    sync::{Arc, Mutex},
+};
 
 use aligned_vec::{AVec, ConstAlign};
 use dora_arrow_convert::{ArrowData, IntoArrow};
@@ -103,6 +107,9 @@ pub struct MappedInputData {
     len: usize,
 }
 
+/// Start addresses and lengths of all currently mapped shared memory inputs.
+static MAPPED_REGIONS: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
 impl MappedInputData {
     pub(crate) unsafe fn map(shared_memory_id: &str, len: usize) -> eyre::Result<Self> {
         let memory = Box::new(
@@ -112,8 +119,37 @@ impl MappedInputData {
                 .open()
                 .wrap_err("failed to map shared memory input")?,
         );
+        let start = memory.as_ptr() as usize;
+        MAPPED_REGIONS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(start, len);
         Ok(MappedInputData { memory, len })
     }
+
+    /// Checks whether the given bytes lie in the shared memory region of a mapped
+    /// input.
+    ///
+    /// This is the case if the sender placed the message in shared memory and the
+    /// receiving node maps it directly, without copying. The check is only
+    /// meaningful while the input data is alive.
+    pub fn is_mapped(bytes: &[u8]) -> bool {
+        let start = bytes.as_ptr() as usize;
+        let regions = MAPPED_REGIONS.lock().unwrap_or_else(|err| err.into_inner());
+        match regions.range(..=start).next_back() {
+            Some((region_start, region_len)) => start + bytes.len() <= region_start + region_len,
+            None => false,
+        }
+    }
+}
+
+impl Drop for MappedInputData {
+    fn drop(&mut self) {
+        MAPPED_REGIONS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&(self.memory.as_ptr() as usize));
+    }
 }
 
 impl std::ops::Deref for MappedInputData {
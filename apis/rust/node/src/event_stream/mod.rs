@@ -1,9 +1,10 @@
 use std::{sync::Arc, time::Duration};
 
 pub use event::{Event, MappedInputData, RawData};
+use flume::{RecvTimeoutError, TryRecvError};
 use futures::{
     future::{select, Either},
-    Stream, StreamExt,
+    FutureExt, Stream, StreamExt,
 };
 use futures_timer::Delay;
 
@@ -28,7 +29,7 @@ mod thread;
 pub struct EventStream {
     node_id: NodeId,
     receiver: flume::r#async::RecvStream<'static, EventItem>,
-    _thread_handle: EventStreamThreadHandle,
+    thread_handle: EventStreamThreadHandle,
     close_channel: DaemonChannel,
     clock: Arc<uhlc::HLC>,
 }
@@ -100,12 +101,18 @@ impl EventStream {
         Ok(EventStream {
             node_id: node_id.clone(),
             receiver: rx.into_stream(),
-            _thread_handle: thread_handle,
+            thread_handle,
             close_channel,
             clock,
         })
     }
 
+    /// Sets the scheduling priority of the thread that receives the events from the
+    /// daemon, see [`DoraNode::set_realtime_priority`][crate::DoraNode::set_realtime_priority].
+    pub fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        self.thread_handle.set_realtime_priority(priority)
+    }
+
     /// wait for the next event on the events stream.
     pub fn recv(&mut self) -> Option<Event> {
         futures::executor::block_on(self.recv_async())
@@ -116,6 +123,31 @@ impl EventStream {
         futures::executor::block_on(self.recv_async_timeout(dur))
     }
 
+    /// Receives the next event if one is immediately available, without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if no event is available right now and
+    /// [`TryRecvError::Disconnected`] if the event stream is closed.
+    pub fn try_recv(&mut self) -> Result<Event, TryRecvError> {
+        match self.receiver.next().now_or_never() {
+            Some(Some(item)) => Ok(Self::convert_event_item(item)),
+            Some(None) => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Waits for the next event until the given timeout expires.
+    ///
+    /// Unlike [`recv_timeout`][Self::recv_timeout], an expired timeout is reported
+    /// as [`RecvTimeoutError::Timeout`] instead of as an [`Event::Error`].
+    pub fn try_recv_timeout(&mut self, dur: Duration) -> Result<Event, RecvTimeoutError> {
+        let next = futures::executor::block_on(select(Delay::new(dur), self.receiver.next()));
+        match next {
+            Either::Left((_elapsed, _)) => Err(RecvTimeoutError::Timeout),
+            Either::Right((Some(item), _)) => Ok(Self::convert_event_item(item)),
+            Either::Right((None, _)) => Err(RecvTimeoutError::Disconnected),
+        }
+    }
+
     pub async fn recv_async(&mut self) -> Option<Event> {
         self.receiver.next().await.map(Self::convert_event_item)
     }
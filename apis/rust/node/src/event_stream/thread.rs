@@ -10,7 +10,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::daemon_connection::DaemonChannel;
+use crate::{daemon_connection::DaemonChannel, priority::NativeThread};
 
 pub fn init(
     node_id: NodeId,
@@ -36,10 +36,12 @@ pub enum EventItem {
 pub struct EventStreamThreadHandle {
     node_id: NodeId,
     handle: flume::Receiver<std::thread::Result<()>>,
+    native: NativeThread,
 }
 
 impl EventStreamThreadHandle {
     fn new(node_id: NodeId, join_handle: std::thread::JoinHandle<()>) -> Self {
+        let native = NativeThread::of(&join_handle);
         let (tx, rx) = flume::bounded(1);
         std::thread::spawn(move || {
             let _ = tx.send(join_handle.join());
@@ -47,8 +49,19 @@ impl EventStreamThreadHandle {
         Self {
             node_id,
             handle: rx,
+            native,
         }
     }
+
+    /// Sets the scheduling priority of the event stream thread, see
+    /// [`DoraNode::set_realtime_priority`][crate::DoraNode::set_realtime_priority].
+    pub fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        // the thread handle is only valid until the thread is joined
+        if !self.handle.is_empty() {
+            eyre::bail!("event stream thread has already finished");
+        }
+        self.native.set_realtime_priority(priority)
+    }
 }
 
 impl Drop for EventStreamThreadHandle {
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::daemon_connection::DaemonChannel;
 use dora_core::{
@@ -63,6 +63,51 @@ impl ControlChannel {
         Ok(())
     }
 
+    pub fn rotate_log(&mut self) -> eyre::Result<()> {
+        let reply = self
+            .channel
+            .request(&Timestamped {
+                inner: DaemonRequest::RotateLog,
+                timestamp: self.clock.new_timestamp(),
+            })
+            .wrap_err("failed to send log rotation request to dora-daemon")?;
+        match reply {
+            dora_core::daemon_messages::DaemonReply::Result(result) => result
+                .map_err(|e| eyre!(e))
+                .wrap_err("dora-daemon failed to rotate log")?,
+            other => bail!("unexpected log rotation reply: {other:?}"),
+        }
+        Ok(())
+    }
+
+    /// Returns `false` if the barrier was not released within the given timeout.
+    pub fn barrier(
+        &mut self,
+        name: String,
+        expected: usize,
+        timeout: Duration,
+    ) -> eyre::Result<bool> {
+        let reply = self
+            .channel
+            .request(&Timestamped {
+                inner: DaemonRequest::Barrier {
+                    name,
+                    expected,
+                    timeout,
+                },
+                timestamp: self.clock.new_timestamp(),
+            })
+            .wrap_err("failed to send barrier request to dora-daemon")?;
+        match reply {
+            dora_core::daemon_messages::DaemonReply::Result(result) => result
+                .map_err(|e| eyre!(e))
+                .wrap_err("failed to wait on barrier")?,
+            dora_core::daemon_messages::DaemonReply::BarrierTimeout => return Ok(false),
+            other => bail!("unexpected barrier reply: {other:?}"),
+        }
+        Ok(true)
+    }
+
     pub fn report_closed_outputs(&mut self, outputs: Vec<DataId>) -> eyre::Result<()> {
         let reply = self
             .channel
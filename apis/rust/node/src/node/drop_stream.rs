@@ -1,6 +1,6 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::daemon_connection::DaemonChannel;
+use crate::{daemon_connection::DaemonChannel, priority::NativeThread};
 use dora_core::{
     config::NodeId,
     daemon_messages::{
@@ -14,7 +14,7 @@ use flume::RecvTimeoutError;
 
 pub struct DropStream {
     receiver: flume::Receiver<DropToken>,
-    _thread_handle: DropStreamThreadHandle,
+    thread_handle: DropStreamThreadHandle,
 }
 
 impl DropStream {
@@ -72,11 +72,17 @@ impl DropStream {
 
         Ok(Self {
             receiver: rx,
-            _thread_handle: DropStreamThreadHandle::new(node_id.clone(), handle),
+            thread_handle: DropStreamThreadHandle::new(node_id.clone(), handle),
         })
     }
 }
 
+impl DropStream {
+    pub(crate) fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        self.thread_handle.set_realtime_priority(priority)
+    }
+}
+
 impl std::ops::Deref for DropStream {
     type Target = flume::Receiver<DropToken>;
 
@@ -139,10 +145,12 @@ fn drop_stream_loop(
 struct DropStreamThreadHandle {
     node_id: NodeId,
     handle: flume::Receiver<std::thread::Result<()>>,
+    native: NativeThread,
 }
 
 impl DropStreamThreadHandle {
     fn new(node_id: NodeId, join_handle: std::thread::JoinHandle<()>) -> Self {
+        let native = NativeThread::of(&join_handle);
         let (tx, rx) = flume::bounded(1);
         std::thread::spawn(move || {
             let _ = tx.send(join_handle.join());
@@ -150,7 +158,18 @@ impl DropStreamThreadHandle {
         Self {
             node_id,
             handle: rx,
+            native,
+        }
+    }
+
+    /// Sets the scheduling priority of the drop stream thread, see
+    /// [`DoraNode::set_realtime_priority`][crate::DoraNode::set_realtime_priority].
+    pub fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        // the thread handle is only valid until the thread is joined
+        if !self.handle.is_empty() {
+            eyre::bail!("drop stream thread has already finished");
         }
+        self.native.set_realtime_priority(priority)
     }
 }
 
@@ -14,7 +14,7 @@ use dora_core::{
     message::{uhlc, ArrowTypeInfo, Metadata, MetadataParameters},
 };
 use eyre::{bail, WrapErr};
-use shared_memory_extended::{Shmem, ShmemConf};
+use shared_memory_extended::{Shmem, ShmemConf, ShmemError};
 use std::{
     collections::{HashMap, VecDeque},
     ops::{Deref, DerefMut},
@@ -34,6 +34,7 @@ pub const ZERO_COPY_THRESHOLD: usize = 4096;
 pub struct DoraNode {
     id: NodeId,
     dataflow_id: DataflowId,
+    machine_id: String,
     node_config: NodeRunConfig,
     control_channel: ControlChannel,
     clock: Arc<uhlc::HLC>,
@@ -55,17 +56,22 @@ impl DoraNode {
     /// ```
     ///
     pub fn init_from_env() -> eyre::Result<(Self, EventStream)> {
-        let node_config: NodeConfig = {
-            let raw = std::env::var("DORA_NODE_CONFIG")
-                .wrap_err("env variable DORA_NODE_CONFIG must be set")?;
-            serde_yaml::from_str(&raw).context("failed to deserialize operator config")?
-        };
+        let node_config = node_config_from_env()?;
         #[cfg(feature = "tracing")]
         set_up_tracing(&node_config.node_id.to_string())
             .context("failed to set up tracing subscriber")?;
         Self::init(node_config)
     }
 
+    /// Initiate a new connection to the daemon, using the same environment variables
+    /// as [`init_from_env`][Self::init_from_env].
+    ///
+    /// Unlike `init_from_env`, this function does not set up tracing again. It is
+    /// intended for nodes that want to reconnect after a daemon restart.
+    pub fn reconnect_from_env() -> eyre::Result<(Self, EventStream)> {
+        Self::init(node_config_from_env()?)
+    }
+
     #[tracing::instrument]
     pub fn init(node_config: NodeConfig) -> eyre::Result<(Self, EventStream)> {
         let NodeConfig {
@@ -76,6 +82,13 @@ impl DoraNode {
             dataflow_descriptor,
         } = node_config;
 
+        let machine_id = dataflow_descriptor
+            .resolve_aliases_and_set_defaults()
+            .into_iter()
+            .find(|node| node.id == node_id)
+            .map(|node| node.deploy.machine)
+            .unwrap_or_default();
+
         let clock = Arc::new(uhlc::HLC::default());
 
         let event_stream =
@@ -91,6 +104,7 @@ impl DoraNode {
         let node = Self {
             id: node_id,
             dataflow_id: dataflow_id,
+            machine_id,
             node_config: run_config,
             control_channel,
             clock,
@@ -149,6 +163,22 @@ impl DoraNode {
         output_id: DataId,
         parameters: MetadataParameters,
         data: impl Array,
+    ) -> eyre::Result<()> {
+        let timestamp = self.clock.new_timestamp();
+        self.send_output_with_timestamp(output_id, parameters, data, timestamp)
+    }
+
+    /// Send data from the node to the other nodes, using the given timestamp
+    /// for the output metadata instead of a new timestamp of the node's clock.
+    ///
+    /// This is useful for sending multiple outputs that belong to the same
+    /// logical step under a common timestamp.
+    pub fn send_output_with_timestamp(
+        &mut self,
+        output_id: DataId,
+        parameters: MetadataParameters,
+        data: impl Array,
+        timestamp: uhlc::Timestamp,
     ) -> eyre::Result<()> {
         let arrow_array = data.to_data();
 
@@ -157,8 +187,14 @@ impl DoraNode {
         let mut sample = self.allocate_data_sample(total_len)?;
         let type_info = copy_array_into_sample(&mut sample, &arrow_array);
 
-        self.send_output_sample(output_id, type_info, parameters, Some(sample))
-            .wrap_err("failed to send output")?;
+        self.send_output_sample_with_timestamp(
+            output_id,
+            type_info,
+            parameters,
+            Some(sample),
+            timestamp,
+        )
+        .wrap_err("failed to send output")?;
 
         Ok(())
     }
@@ -198,17 +234,25 @@ impl DoraNode {
         type_info: ArrowTypeInfo,
         parameters: MetadataParameters,
         sample: Option<DataSample>,
+    ) -> eyre::Result<()> {
+        let timestamp = self.clock.new_timestamp();
+        self.send_output_sample_with_timestamp(output_id, type_info, parameters, sample, timestamp)
+    }
+
+    pub fn send_output_sample_with_timestamp(
+        &mut self,
+        output_id: DataId,
+        type_info: ArrowTypeInfo,
+        parameters: MetadataParameters,
+        sample: Option<DataSample>,
+        timestamp: uhlc::Timestamp,
     ) -> eyre::Result<()> {
         self.handle_finished_drop_tokens()?;
 
         if !self.node_config.outputs.contains(&output_id) {
             eyre::bail!("unknown output");
         }
-        let metadata = Metadata::from_parameters(
-            self.clock.new_timestamp(),
-            type_info,
-            parameters.into_owned(),
-        );
+        let metadata = Metadata::from_parameters(timestamp, type_info, parameters.into_owned());
 
         let (data, shmem) = match sample {
             Some(sample) => sample.finalize(),
@@ -249,10 +293,69 @@ impl DoraNode {
         &self.dataflow_id
     }
 
+    /// Returns the ID of the machine that this node is deployed on.
+    ///
+    /// The ID is empty if the dataflow doesn't specify a machine for this node,
+    /// i.e. if the node runs on the default machine.
+    pub fn machine_id(&self) -> &str {
+        &self.machine_id
+    }
+
     pub fn node_config(&self) -> &NodeRunConfig {
         &self.node_config
     }
 
+    /// Asks the daemon to rotate the log files of this node.
+    ///
+    /// The daemon archives the current log output of the node into a separate file
+    /// in the dataflow's `out` directory. The following output is written to the
+    /// original, now empty, log files.
+    pub fn rotate_log(&mut self) -> eyre::Result<()> {
+        self.control_channel.rotate_log()
+    }
+
+    /// Blocks until `expected` nodes of the dataflow (including this node) reached
+    /// the barrier with the given name.
+    ///
+    /// The barrier is coordinated through the dora-coordinator, so it works across
+    /// machines. Each node counts once, so `expected` should not be larger than the
+    /// number of nodes in the dataflow. After the barrier is released, the same name
+    /// can be used again for the next synchronization round.
+    ///
+    /// Returns `false` if the barrier was not released within the given timeout. In
+    /// this case, the node no longer counts as waiting on the barrier.
+    pub fn barrier(
+        &mut self,
+        name: impl Into<String>,
+        expected: usize,
+        timeout: Duration,
+    ) -> eyre::Result<bool> {
+        self.control_channel.barrier(name.into(), expected, timeout)
+    }
+
+    /// Runs the internal thread that receives drop tokens from the daemon with the
+    /// given realtime priority.
+    ///
+    /// On Unix platforms, the thread is switched to the `SCHED_FIFO` scheduling
+    /// policy with the given priority (`1` to `99` on Linux). A priority of `0`
+    /// switches back to the default `SCHED_OTHER` policy. Realtime priorities
+    /// require elevated privileges, e.g. the `CAP_SYS_NICE` capability or a
+    /// sufficient `RLIMIT_RTPRIO` limit on Linux. An error is returned if the
+    /// privileges are missing. Other platforms are not supported.
+    ///
+    /// The event stream runs on a separate thread, which is configured through
+    /// [`EventStream::set_realtime_priority`][crate::EventStream::set_realtime_priority].
+    pub fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        self.drop_stream
+            .set_realtime_priority(priority)
+            .wrap_err("failed to set priority of drop stream thread")
+    }
+
+    /// Returns the hybrid logical clock that the node uses to timestamp its outputs.
+    pub fn clock(&self) -> &uhlc::HLC {
+        &self.clock
+    }
+
     pub fn allocate_data_sample(&mut self, data_len: usize) -> eyre::Result<DataSample> {
         let data = if data_len >= ZERO_COPY_THRESHOLD {
             // create shared memory region
@@ -290,6 +393,10 @@ impl DoraNode {
                     .size(data_len)
                     .writable(true)
                     .create()
+                    .map_err(|err| SharedMemoryFull {
+                        size: data_len,
+                        source: err,
+                    })
                     .wrap_err("failed to allocate shared memory")?,
             )),
         };
@@ -298,6 +405,24 @@ impl DoraNode {
         Ok(memory)
     }
 
+    /// Makes sure that a shared memory region for a message of the given size is
+    /// available.
+    ///
+    /// The region is kept in the cache and reused by the next send that needs it.
+    /// Returns an error that can be downcast to [`SharedMemoryFull`] if no shared
+    /// memory region can be allocated. Messages smaller than the
+    /// [`ZERO_COPY_THRESHOLD`] don't use shared memory, so this function always
+    /// succeeds for them.
+    pub fn reserve_shared_memory(&mut self, data_len: usize) -> eyre::Result<()> {
+        if data_len < ZERO_COPY_THRESHOLD {
+            return Ok(());
+        }
+        self.handle_finished_drop_tokens()?;
+        let memory = self.allocate_shared_memory(data_len)?;
+        self.add_to_cache(memory);
+        Ok(())
+    }
+
     fn handle_finished_drop_tokens(&mut self) -> eyre::Result<()> {
         loop {
             match self.drop_stream.try_recv() {
@@ -331,6 +456,12 @@ impl DoraNode {
     }
 }
 
+fn node_config_from_env() -> eyre::Result<NodeConfig> {
+    let raw =
+        std::env::var("DORA_NODE_CONFIG").wrap_err("env variable DORA_NODE_CONFIG must be set")?;
+    serde_yaml::from_str(&raw).context("failed to deserialize operator config")
+}
+
 impl Drop for DoraNode {
     #[tracing::instrument(skip(self), fields(self.id = %self.id), level = "trace")]
     fn drop(&mut self) {
@@ -455,6 +586,30 @@ enum DataSampleInner {
     Vec(AVec<u8, ConstAlign<128>>),
 }
 
+/// Error that occurs when no shared memory region can be allocated, e.g. because the
+/// shared memory file system is full.
+#[derive(Debug)]
+pub struct SharedMemoryFull {
+    size: usize,
+    source: ShmemError,
+}
+
+impl std::fmt::Display for SharedMemoryFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to allocate shared memory region of {} bytes",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for SharedMemoryFull {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
 struct ShmemHandle(Box<Shmem>);
 
 impl Deref for ShmemHandle {
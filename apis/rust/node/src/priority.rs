@@ -0,0 +1,68 @@
+//! Scheduling priority of the threads that the node API spawns internally.
+
+use std::thread::JoinHandle;
+
+/// Platform handle of an internal thread, used to change its scheduling policy.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NativeThread {
+    #[cfg(unix)]
+    pthread: libc::pthread_t,
+}
+
+impl NativeThread {
+    pub fn of<T>(handle: &JoinHandle<T>) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::thread::JoinHandleExt;
+            Self {
+                pthread: handle.as_pthread_t(),
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = handle;
+            Self {}
+        }
+    }
+
+    /// Sets the scheduling policy of the thread to `SCHED_FIFO` with the given
+    /// priority, or resets it to the default policy if `priority` is `0`.
+    ///
+    /// The thread must still be running.
+    #[cfg(unix)]
+    pub fn set_realtime_priority(&self, priority: i32) -> eyre::Result<()> {
+        let policy = if priority == 0 {
+            libc::SCHED_OTHER
+        } else {
+            libc::SCHED_FIFO
+        };
+        let (min, max) = unsafe {
+            (
+                libc::sched_get_priority_min(policy),
+                libc::sched_get_priority_max(policy),
+            )
+        };
+        if priority != 0 && !(min..=max).contains(&priority) {
+            eyre::bail!("realtime priority must be between {min} and {max}, got {priority}");
+        }
+
+        // `sched_param` has additional private fields on some platforms
+        let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+        param.sched_priority = priority;
+        let result = unsafe { libc::pthread_setschedparam(self.pthread, policy, &param) };
+        match result {
+            0 => Ok(()),
+            libc::EPERM => eyre::bail!(
+                "insufficient privileges to set realtime priority (requires the \
+                `CAP_SYS_NICE` capability or a sufficient `RLIMIT_RTPRIO` limit)"
+            ),
+            err => Err(eyre::Report::new(std::io::Error::from_raw_os_error(err))
+                .wrap_err("failed to set thread scheduling policy")),
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn set_realtime_priority(&self, _priority: i32) -> eyre::Result<()> {
+        eyre::bail!("realtime priorities are not supported on this platform")
+    }
+}
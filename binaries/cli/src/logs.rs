@@ -1,45 +1,1137 @@
+use arrow::{
+    array::{ArrayRef, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    ipc::writer::FileWriter,
+    record_batch::RecordBatch,
+};
+use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
 use communication_layer_request_reply::TcpRequestReplyConnection;
-use dora_core::topics::{ControlRequest, ControlRequestReply};
+use dora_core::{
+    message::uhlc::NTP64,
+    strip_ansi,
+    topics::{control_socket_addr, ControlRequest, ControlRequestReply, DataflowId, LogRecord},
+};
 use eyre::{bail, Context, Result};
+use std::{
+    fs::File,
+    io::{IsTerminal, Read, Write},
+    net::TcpStream,
+    path::Path,
+    sync::Arc,
+};
+use termcolor::{Buffer, Color, ColorSpec, WriteColor};
 use uuid::Uuid;
 
 use bat::{Input, PrettyPrinter};
 
-pub fn logs(
+/// Output format of the `dora logs` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Colored output, shown in a pager.
+    Plain,
+    /// Syslog messages as specified in RFC 5424, one per line.
+    Rfc5424,
+    /// Journal export format, as accepted by `systemd-journal-remote`.
+    Journald,
+    /// One JSON object per log record and line.
+    Json,
+}
+
+/// Lists the running dataflows as `(uuid, name)` pairs.
+///
+/// The name is empty for unnamed dataflows.
+pub fn list_dataflows(session: &mut TcpRequestReplyConnection) -> Result<Vec<(Uuid, String)>> {
+    let reply_raw = session
+        .request(&serde_json::to_vec(&ControlRequest::List).unwrap())
+        .wrap_err("failed to send list message")?;
+    let reply: ControlRequestReply =
+        serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
+    match reply {
+        ControlRequestReply::DataflowList { dataflows } => Ok(dataflows
+            .into_iter()
+            .map(|id| (id.uuid, id.name.unwrap_or_default()))
+            .collect()),
+        ControlRequestReply::Error(err) => bail!("{err}"),
+        other => bail!("unexpected list dataflow reply: {other:?}"),
+    }
+}
+
+/// Resolves the given dataflow UUID or name to the UUID of a running dataflow.
+///
+/// If no dataflow is given, the only running dataflow is chosen. If there are
+/// multiple running dataflows, the user is asked to choose one.
+pub fn resolve_dataflow(
     session: &mut TcpRequestReplyConnection,
-    uuid: Option<Uuid>,
-    name: Option<String>,
-    node: String,
-) -> Result<()> {
-    let logs = {
-        let reply_raw = session
-            .request(
-                &serde_json::to_vec(&ControlRequest::Logs {
+    dataflow: Option<String>,
+) -> Result<Uuid> {
+    if let Some(uuid) = dataflow.as_deref().and_then(|d| Uuid::parse_str(d).ok()) {
+        return Ok(uuid);
+    }
+    let dataflows = list_dataflows(session).wrap_err("failed to query running dataflows")?;
+    if let Some(name) = dataflow {
+        let mut matching = dataflows.iter().filter(|(_, n)| *n == name);
+        return match (matching.next(), matching.next()) {
+            (Some((uuid, _)), None) => Ok(*uuid),
+            (None, _) => bail!("no running dataflow with name `{name}`"),
+            (Some(_), Some(_)) => bail!("multiple running dataflows with name `{name}`"),
+        };
+    }
+    match &dataflows[..] {
+        [] => bail!("No dataflows are running"),
+        [(uuid, _)] => Ok(*uuid),
+        _ => {
+            let ids = dataflows
+                .into_iter()
+                .map(|(uuid, name)| DataflowId {
                     uuid,
-                    name,
-                    node: node.clone(),
+                    name: (!name.is_empty()).then_some(name),
                 })
-                .wrap_err("")?,
-            )
-            .wrap_err("failed to send Logs request message")?;
+                .collect();
+            let selection = inquire::Select::new("Choose dataflow to show logs:", ids).prompt()?;
+            Ok(selection.uuid)
+        }
+    }
+}
 
-        let reply = serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
-        match reply {
-            ControlRequestReply::Logs(logs) => logs,
-            other => bail!("unexpected reply to daemon logs: {other:?}"),
+/// Splits the positional arguments of `dora logs` into the dataflow and the nodes.
+///
+/// The first argument is only treated as dataflow if further arguments follow and
+/// it is a UUID or the name of a running dataflow.
+pub fn split_dataflow_arg(
+    session: &mut TcpRequestReplyConnection,
+    mut args: Vec<String>,
+) -> Result<(Option<String>, Vec<String>)> {
+    if args.len() < 2 {
+        return Ok((None, args));
+    }
+    let first = &args[0];
+    let is_dataflow = Uuid::parse_str(first).is_ok()
+        || list_dataflows(session)
+            .wrap_err("failed to query running dataflows")?
+            .iter()
+            .any(|(_, name)| name == first);
+    if is_dataflow {
+        let dataflow = args.remove(0);
+        Ok((Some(dataflow), args))
+    } else {
+        Ok((None, args))
+    }
+}
+
+pub fn logs(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+    sort: bool,
+    format: LogFormat,
+    strip: bool,
+    max_line_len: Option<usize>,
+) -> Result<()> {
+    if format != LogFormat::Plain {
+        let mut logs = Vec::new();
+        for node in nodes {
+            let mut records = fetch_records(session, uuid, node.clone())?;
+            if let Some(max_len) = max_line_len {
+                for record in &mut records {
+                    record.message = truncate_lines(&record.message, max_len);
+                }
+            }
+            logs.push((node, records));
         }
+        let mut stdout = std::io::stdout().lock();
+        for (node, record) in ordered_records(&logs, sort) {
+            write_record(&mut stdout, node, record, format)?;
+        }
+        return Ok(());
+    }
+
+    let mut logs = Vec::new();
+    for node in nodes {
+        let node_logs = fetch_raw_logs(session, uuid, node.clone())?;
+        let mut text = String::from_utf8_lossy(&node_logs).into_owned();
+        if strip {
+            text = strip_ansi(&text);
+        }
+        logs.push((node, text));
+    }
+
+    let colored = std::io::stdout().is_terminal();
+    let rendered: Vec<(String, String)> = if sort && logs.len() > 1 {
+        vec![("Merged logs.".to_owned(), merge_sorted(&logs))]
+    } else {
+        logs.into_iter()
+            .map(|(node, text)| (format!("Logs from {node}."), text))
+            .collect()
     };
+    let rendered = rendered
+        .into_iter()
+        .map(|(title, text)| {
+            let text = match max_line_len {
+                Some(max_len) => truncate_lines(&text, max_len),
+                None => text,
+            };
+            Ok((title, colorize_levels(&text, colored)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let inputs = rendered.iter().map(|(title, text)| {
+        Input::from_bytes(text.as_bytes())
+            .name("Logs")
+            .title(title.as_str())
+    });
 
+    // lines are already colored by log level, so we only use `bat` as pager
     PrettyPrinter::new()
         .header(false)
         .grid(false)
         .line_numbers(false)
+        .colored_output(false)
         .paging_mode(bat::PagingMode::QuitIfOneScreen)
-        .inputs(vec![Input::from_bytes(&logs)
-            .name("Logs")
-            .title(format!("Logs from {node}.").as_str())])
+        .inputs(inputs)
         .print()
         .wrap_err("Something went wrong with viewing log file")?;
 
     Ok(())
 }
+
+/// Writes the log records of the given nodes to the given file as table.
+///
+/// Files with `.parquet` extension are written as Parquet, all others as Arrow IPC
+/// file. See [`records_batch`] for the schema.
+pub fn write_table(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+    sort: bool,
+    path: &Path,
+) -> Result<()> {
+    let mut logs = Vec::new();
+    for node in nodes {
+        let records = fetch_records(session, uuid, node.clone())?;
+        logs.push((node, records));
+    }
+    let batch = records_batch(&ordered_records(&logs, sort))?;
+
+    let file = File::create(path).wrap_err_with(|| format!("failed to create {path:?}"))?;
+    if path.extension().is_some_and(|ext| ext == "parquet") {
+        let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None)
+            .wrap_err("failed to create parquet writer")?;
+        writer
+            .write(&batch)
+            .wrap_err("failed to write parquet file")?;
+        writer.close().wrap_err("failed to finish parquet file")?;
+    } else {
+        let mut writer = FileWriter::try_new(file, &batch.schema())
+            .wrap_err("failed to create arrow IPC writer")?;
+        writer
+            .write(&batch)
+            .wrap_err("failed to write arrow IPC file")?;
+        writer
+            .finish()
+            .wrap_err("failed to finish arrow IPC file")?;
+    }
+    println!(
+        "wrote {} log records to {}",
+        batch.num_rows(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Converts the given log records into a record batch.
+///
+/// The batch has the columns `node` (utf8), `timestamp` (timestamp[ns, UTC]),
+/// `logged_at` (timestamp[ns, UTC], nullable), `received_at` (timestamp[ns, UTC]),
+/// `level` (utf8, nullable), `target` (utf8, nullable), and `message` (utf8). The
+/// `timestamp` is the time at which the node logged the record if known, and the
+/// time at which the daemon received it otherwise.
+fn records_batch(records: &[(&str, &LogRecord)]) -> Result<RecordBatch> {
+    let time_type = DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()));
+    let schema = Schema::new(vec![
+        Field::new("node", DataType::Utf8, false),
+        Field::new("timestamp", time_type.clone(), false),
+        Field::new("logged_at", time_type.clone(), true),
+        Field::new("received_at", time_type, false),
+        Field::new("level", DataType::Utf8, true),
+        Field::new("target", DataType::Utf8, true),
+        Field::new("message", DataType::Utf8, false),
+    ]);
+    let nanos = |time: DateTime<Utc>| {
+        time.timestamp_nanos_opt()
+            .ok_or_else(|| eyre::eyre!("log record timestamp is out of range"))
+    };
+    let timestamps = records
+        .iter()
+        .map(|(_, r)| nanos(record_time(r)))
+        .collect::<Result<Vec<_>>>()?;
+    let logged = records
+        .iter()
+        .map(|(_, r)| logged_time(r).map(nanos).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    let received = records
+        .iter()
+        .map(|(_, r)| nanos(received_time(r)))
+        .collect::<Result<Vec<_>>>()?;
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|(node, _)| node),
+        )),
+        Arc::new(TimestampNanosecondArray::from(timestamps).with_timezone("UTC")),
+        Arc::new(TimestampNanosecondArray::from(logged).with_timezone("UTC")),
+        Arc::new(TimestampNanosecondArray::from(received).with_timezone("UTC")),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|(_, r)| r.level.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|(_, r)| r.target.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|(_, r)| &r.message),
+        )),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).wrap_err("failed to create record batch")
+}
+
+/// Shows the differences between the logs of the given nodes in two dataflow runs
+/// as unified diff.
+///
+/// Timestamps are ignored unless `with_timestamps` is set, since they differ between
+/// any two runs.
+pub fn diff_logs(
+    session: &mut TcpRequestReplyConnection,
+    old: Uuid,
+    new: Uuid,
+    nodes: Vec<String>,
+    with_timestamps: bool,
+) -> Result<()> {
+    let mut rendered = Vec::new();
+    for node in nodes {
+        let old_records = fetch_records(session, old, node.clone())?;
+        let new_records = fetch_records(session, new, node.clone())?;
+        let diff = unified_diff(
+            &format!("{old}/{node}"),
+            &old_records,
+            &format!("{new}/{node}"),
+            &new_records,
+            with_timestamps,
+        );
+        let diff = if diff.is_empty() {
+            "Logs are identical.\n".to_owned()
+        } else {
+            diff
+        };
+        rendered.push((format!("Log diff for {node}."), diff));
+    }
+    let inputs = rendered.iter().map(|(title, text)| {
+        Input::from_bytes(text.as_bytes())
+            .name("Logs.diff")
+            .title(title.as_str())
+    });
+
+    PrettyPrinter::new()
+        .header(false)
+        .grid(false)
+        .line_numbers(false)
+        .language("diff")
+        .colored_output(std::io::stdout().is_terminal())
+        .paging_mode(bat::PagingMode::QuitIfOneScreen)
+        .inputs(inputs)
+        .print()
+        .wrap_err("Something went wrong with viewing log diff")?;
+
+    Ok(())
+}
+
+/// Compares the given log records line by line and formats the differences as
+/// unified diff.
+///
+/// Returns an empty string if there are no differences.
+fn unified_diff(
+    old_name: &str,
+    old: &[LogRecord],
+    new_name: &str,
+    new: &[LogRecord],
+    with_timestamps: bool,
+) -> String {
+    let text = |records: &[LogRecord]| -> String {
+        let mut text = String::new();
+        for record in records {
+            if with_timestamps {
+                let timestamp = record_time(record).to_rfc3339_opts(SecondsFormat::Micros, true);
+                text += &format!("{timestamp} ");
+            }
+            text += &record_text(record);
+            text.push('\n');
+        }
+        text
+    };
+    let (old, new) = (text(old), text(new));
+    let diff = similar::TextDiff::from_lines(&old, &new);
+    if diff
+        .ops()
+        .iter()
+        .all(|op| op.tag() == similar::DiffTag::Equal)
+    {
+        return String::new();
+    }
+    diff.unified_diff()
+        .context_radius(3)
+        .header(old_name, new_name)
+        .to_string()
+}
+
+/// Prints the log output of the given nodes and keeps printing new output as it is
+/// written, until the process is interrupted.
+///
+/// The coordinator streams new output on a dedicated connection per node, so no
+/// polling is involved. If multiple nodes are given, each line is prefixed with
+/// the ID of the node that logged it.
+pub fn follow_logs(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+    strip: bool,
+) -> Result<()> {
+    let prefix = nodes.len() > 1;
+    let mut streams = Vec::new();
+    for node in nodes {
+        // start following before fetching the existing logs, so that no output is missed
+        let mut stream = TcpStream::connect(control_socket_addr())
+            .wrap_err("failed to connect to dora coordinator")?;
+        send_request(
+            &mut stream,
+            &ControlRequest::FollowLogs {
+                uuid,
+                node: node.clone(),
+            },
+        )?;
+        let offset = match receive_reply(&mut stream)? {
+            ControlRequestReply::LogsFollowed { offset } => offset,
+            ControlRequestReply::Error(err) => bail!("{err}"),
+            other => bail!("unexpected reply to follow logs: {other:?}"),
+        };
+        let existing = fetch_raw_logs(session, uuid, node.clone())?;
+        let existing = &existing[..existing.len().min(offset as usize)];
+        print_output(&node, existing, prefix, strip)?;
+        streams.push((node, stream, offset));
+    }
+
+    let handles: Vec<_> = streams
+        .into_iter()
+        .map(|(node, mut stream, mut position)| {
+            std::thread::spawn(move || -> Result<()> {
+                loop {
+                    match receive_reply(&mut stream)? {
+                        ControlRequestReply::LogChunk { offset, data } => {
+                            let output = new_output(&mut position, offset, &data);
+                            print_output(&node, output, prefix, strip)?;
+                        }
+                        ControlRequestReply::Error(err) => bail!("{err}"),
+                        other => bail!("unexpected reply while following logs: {other:?}"),
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        match handle.join() {
+            Ok(result) => result?,
+            Err(_) => bail!("log follow thread panicked"),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the part of the given log chunk that was not printed yet.
+///
+/// The `position` is the log file offset up to which the output was printed. It
+/// is updated accordingly. A chunk at offset `0` that ends before `position`
+/// means that the log file was truncated, so it is printed completely.
+fn new_output<'a>(position: &mut u64, offset: u64, data: &'a [u8]) -> &'a [u8] {
+    let end = offset + data.len() as u64;
+    if offset == 0 && end <= *position {
+        *position = end;
+        return data;
+    }
+    let skip = position.saturating_sub(offset).min(data.len() as u64);
+    *position = (*position).max(end);
+    &data[skip as usize..]
+}
+
+/// Prints the given raw log output.
+///
+/// If `strip` is set, ANSI escape sequences are removed from the output. Escape
+/// sequences that are split across two log chunks are not recognized.
+fn print_output(node: &str, output: &[u8], prefix: bool, strip: bool) -> Result<()> {
+    let stripped;
+    let output = if strip {
+        stripped = strip_ansi(&String::from_utf8_lossy(output));
+        stripped.as_bytes()
+    } else {
+        output
+    };
+    let mut stdout = std::io::stdout().lock();
+    if prefix {
+        for line in String::from_utf8_lossy(output).lines() {
+            writeln!(stdout, "[{node}] {line}")?;
+        }
+    } else {
+        stdout.write_all(output)?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Shortens all lines of the given text that are longer than `max_len` characters.
+///
+/// Truncated lines end with an ellipsis, which counts towards `max_len`.
+fn truncate_lines(text: &str, max_len: usize) -> String {
+    let lines: Vec<_> = text
+        .split('\n')
+        .map(|line| {
+            if line.chars().count() <= max_len {
+                return line.to_owned();
+            }
+            let mut truncated: String = line.chars().take(max_len.saturating_sub(1)).collect();
+            truncated.push('…');
+            truncated
+        })
+        .collect();
+    lines.join("\n")
+}
+
+fn send_request(stream: &mut TcpStream, request: &ControlRequest) -> Result<()> {
+    let message = serde_json::to_vec(request).wrap_err("failed to serialize request")?;
+    stream.write_all(&(message.len() as u64).to_le_bytes())?;
+    stream.write_all(&message)?;
+    Ok(())
+}
+
+fn receive_reply(stream: &mut TcpStream) -> Result<ControlRequestReply> {
+    let mut len = [0; 8];
+    stream
+        .read_exact(&mut len)
+        .wrap_err("connection to coordinator closed")?;
+    let mut raw = vec![0; u64::from_le_bytes(len) as usize];
+    stream.read_exact(&mut raw)?;
+    serde_json::from_slice(&raw).wrap_err("failed to parse reply")
+}
+
+pub fn clear_logs(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+) -> Result<()> {
+    for node in nodes {
+        let reply_raw = session
+            .request(
+                &serde_json::to_vec(&ControlRequest::ClearLogs {
+                    uuid,
+                    node: node.clone(),
+                })
+                .wrap_err("failed to serialize ClearLogs request message")?,
+            )
+            .wrap_err("failed to send ClearLogs request message")?;
+
+        let reply = serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
+        match reply {
+            ControlRequestReply::LogsCleared => println!("cleared logs for {node}"),
+            ControlRequestReply::Error(err) => bail!("{err}"),
+            other => bail!("unexpected reply to clear logs: {other:?}"),
+        }
+    }
+    Ok(())
+}
+
+pub fn print_pids(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+) -> Result<()> {
+    for node in nodes {
+        let reply_raw = session
+            .request(
+                &serde_json::to_vec(&ControlRequest::NodePid {
+                    uuid,
+                    node: node.clone(),
+                })
+                .wrap_err("failed to serialize NodePid request message")?,
+            )
+            .wrap_err("failed to send NodePid request message")?;
+
+        let reply = serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
+        match reply {
+            ControlRequestReply::NodePid(pid) => println!("{node}: {pid}"),
+            ControlRequestReply::Error(err) => bail!("{err}"),
+            other => bail!("unexpected reply to node pid: {other:?}"),
+        }
+    }
+    Ok(())
+}
+
+/// Prints the number of log lines of the given nodes.
+///
+/// If a single node is given, only the number is printed. Otherwise, each count is
+/// prefixed with the node ID. If `per_level` is set, the lines are counted per log
+/// level instead, with lines of records without level counted as `NONE`.
+pub fn print_line_counts(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    nodes: Vec<String>,
+    per_level: bool,
+) -> Result<()> {
+    let prefix = nodes.len() > 1;
+    for node in nodes {
+        let node_prefix = if prefix {
+            format!("{node}: ")
+        } else {
+            String::new()
+        };
+        if per_level {
+            let records = fetch_records(session, uuid, node.clone())?;
+            for (level, count) in line_counts_by_level(&records) {
+                println!("{node_prefix}{level} {count}");
+            }
+        } else {
+            let logs = fetch_raw_logs(session, uuid, node.clone())?;
+            println!("{node_prefix}{}", line_count(&logs));
+        }
+    }
+    Ok(())
+}
+
+/// Counts the lines of the given log output, including an unterminated last line.
+fn line_count(logs: &[u8]) -> usize {
+    let newlines = logs.iter().filter(|&&b| b == b'\n').count();
+    if logs.last().is_some_and(|&b| b != b'\n') {
+        newlines + 1
+    } else {
+        newlines
+    }
+}
+
+/// Counts the message lines of the given records per log level, ordered by
+/// decreasing severity.
+///
+/// Levels without any lines are omitted.
+fn line_counts_by_level(records: &[LogRecord]) -> Vec<(&str, usize)> {
+    let mut counts: Vec<(&str, usize)> = ["ERROR", "WARN", "INFO", "DEBUG", "TRACE", "NONE"]
+        .into_iter()
+        .map(|level| (level, 0))
+        .collect();
+    for record in records {
+        let level = record.level.as_deref().unwrap_or("NONE");
+        let lines = record.message.lines().count().max(1);
+        match counts.iter_mut().find(|(l, _)| *l == level) {
+            Some((_, count)) => *count += lines,
+            None => counts.push((level, lines)),
+        }
+    }
+    counts.retain(|(_, count)| *count > 0);
+    counts
+}
+
+fn fetch_raw_logs(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    node: String,
+) -> Result<Vec<u8>> {
+    let reply_raw = session
+        .request(
+            &serde_json::to_vec(&ControlRequest::Logs {
+                uuid: Some(uuid),
+                name: None,
+                node,
+            })
+            .wrap_err("failed to serialize Logs request message")?,
+        )
+        .wrap_err("failed to send Logs request message")?;
+
+    let reply = serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
+    match reply {
+        ControlRequestReply::Logs(logs) => Ok(logs),
+        ControlRequestReply::Error(err) => bail!("{err}"),
+        other => bail!("unexpected reply to daemon logs: {other:?}"),
+    }
+}
+
+fn fetch_records(
+    session: &mut TcpRequestReplyConnection,
+    uuid: Uuid,
+    node: String,
+) -> Result<Vec<LogRecord>> {
+    let reply_raw = session
+        .request(
+            &serde_json::to_vec(&ControlRequest::StructuredLogs { uuid, node })
+                .wrap_err("failed to serialize StructuredLogs request message")?,
+        )
+        .wrap_err("failed to send StructuredLogs request message")?;
+
+    let reply = serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
+    match reply {
+        ControlRequestReply::StructuredLogs(records) => Ok(records),
+        ControlRequestReply::Error(err) => bail!("{err}"),
+        other => bail!("unexpected reply to daemon logs: {other:?}"),
+    }
+}
+
+/// Returns the records of all nodes together with the ID of the node that logged
+/// them.
+///
+/// If `sort` is set, the records of all nodes are merged into a single timeline,
+/// ordered by the timestamps that the nodes logged. Records without such a
+/// timestamp stay attached to the preceding record of the same node, or are ordered
+/// by the time at which the daemon received them if there is none. Otherwise, the
+/// records are grouped by node.
+fn ordered_records(logs: &[(String, Vec<LogRecord>)], sort: bool) -> Vec<(&str, &LogRecord)> {
+    if !sort {
+        return logs
+            .iter()
+            .flat_map(|(node, records)| records.iter().map(move |r| (node.as_str(), r)))
+            .collect();
+    }
+    let mut records = Vec::new();
+    for (node, node_records) in logs {
+        // the last record with a logged timestamp, as (logged, received) pair
+        let mut last_logged = None;
+        for record in node_records {
+            let received = *record.timestamp.get_time();
+            let time = match (record.logged_at, last_logged) {
+                (Some(logged), _) => {
+                    last_logged = Some((logged, received));
+                    logged
+                }
+                // continuation lines and other output without timestamp are placed
+                // relative to the last logged timestamp of the node
+                (None, Some((logged, logged_received))) => NTP64(
+                    logged.as_u64() + received.as_u64().saturating_sub(logged_received.as_u64()),
+                ),
+                (None, None) => received,
+            };
+            records.push((time, node.as_str(), record));
+        }
+    }
+    // the sort is stable, so records with equal timestamps keep their order
+    records.sort_by_key(|(time, _, _)| *time);
+    records
+        .into_iter()
+        .map(|(_, node, record)| (node, record))
+        .collect()
+}
+
+/// Returns the time at which the given record was logged, falling back to the time
+/// at which the daemon received it.
+fn record_time(record: &LogRecord) -> DateTime<Utc> {
+    logged_time(record).unwrap_or_else(|| received_time(record))
+}
+
+/// Returns the time at which the node logged the given record, if known.
+fn logged_time(record: &LogRecord) -> Option<DateTime<Utc>> {
+    record.logged_at.map(|t| t.to_system_time().into())
+}
+
+/// Returns the time at which the daemon received the given record.
+fn received_time(record: &LogRecord) -> DateTime<Utc> {
+    record.timestamp.get_time().to_system_time().into()
+}
+
+/// Formats the level, target, and message of the given record as text.
+fn record_text(record: &LogRecord) -> String {
+    let mut text = String::new();
+    if let Some(level) = &record.level {
+        text += &format!("{level} ");
+    }
+    if let Some(target) = &record.target {
+        text += &format!("{target}: ");
+    }
+    text + &record.message
+}
+
+/// Writes the given log record in the given format, tagged with the node ID.
+///
+/// For the syslog formats, the severity is derived from the log level of the record.
+/// Records without a level are treated as informational.
+fn write_record(
+    out: &mut impl Write,
+    node: &str,
+    record: &LogRecord,
+    format: LogFormat,
+) -> std::io::Result<()> {
+    let severity = match record.level.as_deref() {
+        Some("ERROR") => 3,
+        Some("WARN") => 4,
+        Some("DEBUG") | Some("TRACE") => 7,
+        _ => 6,
+    };
+    let timestamp = record_time(record);
+    let text = record_text(record);
+    match format {
+        LogFormat::Rfc5424 => {
+            // facility 1 is "user-level messages"
+            let priority = 8 + severity;
+            let timestamp = timestamp.to_rfc3339_opts(SecondsFormat::Micros, true);
+            // syslog messages are line-based, so every line becomes a separate message
+            for line in text.lines() {
+                writeln!(out, "<{priority}>1 {timestamp} - {node} - - - {line}")?;
+            }
+        }
+        LogFormat::Journald => {
+            writeln!(out, "__REALTIME_TIMESTAMP={}", timestamp.timestamp_micros())?;
+            writeln!(out, "PRIORITY={severity}\nSYSLOG_IDENTIFIER={node}")?;
+            if text.contains('\n') {
+                // multi-line values use the binary field encoding of the export format
+                out.write_all(b"MESSAGE\n")?;
+                out.write_all(&(text.len() as u64).to_le_bytes())?;
+                writeln!(out, "{text}\n")?;
+            } else {
+                writeln!(out, "MESSAGE={text}\n")?;
+            }
+        }
+        LogFormat::Json => {
+            let rfc3339 = |time: DateTime<Utc>| time.to_rfc3339_opts(SecondsFormat::Micros, true);
+            let record = serde_json::json!({
+                "node": node,
+                "timestamp": rfc3339(timestamp),
+                "logged_at": logged_time(record).map(rfc3339),
+                "received_at": rfc3339(received_time(record)),
+                "level": record.level,
+                "target": record.target,
+                "message": record.message,
+            });
+            writeln!(out, "{record}")?;
+        }
+        LogFormat::Plain => writeln!(
+            out,
+            "{} {text}",
+            timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+        )?,
+    }
+    Ok(())
+}
+
+/// Merges the logs of multiple nodes into a single timeline.
+///
+/// Each line is prefixed with the ID of the node that logged it. See
+/// [`sorted_lines`] for details on the ordering.
+fn merge_sorted(logs: &[(String, String)]) -> String {
+    let mut merged = String::new();
+    for (node, line) in sorted_lines(logs) {
+        merged += &format!("[{node}] {line}\n");
+    }
+    merged
+}
+
+/// Orders the lines of multiple node logs by their leading timestamp.
+///
+/// Lines without a parseable timestamp stay attached to the preceding timestamped
+/// line of the same node. Returns the lines together with the ID of the node that
+/// logged them.
+fn sorted_lines(logs: &[(String, String)]) -> Vec<(&str, &str)> {
+    type Entry<'a> = (Option<DateTime<FixedOffset>>, Vec<(&'a str, &'a str)>);
+    let mut entries: Vec<Entry> = Vec::new();
+    for (node, logs) in logs {
+        let mut current: Option<Entry> = None;
+        for line in logs.lines() {
+            let line = (node.as_str(), line);
+            match parse_timestamp(line.1) {
+                Some(timestamp) => {
+                    entries.extend(current.take());
+                    current = Some((Some(timestamp), vec![line]));
+                }
+                None => match &mut current {
+                    Some((_, lines)) => lines.push(line),
+                    None => current = Some((None, vec![line])),
+                },
+            }
+        }
+        entries.extend(current);
+    }
+
+    // the sort is stable, so entries with equal timestamps keep their order
+    entries.sort_by_key(|(timestamp, _)| *timestamp);
+    entries.into_iter().flat_map(|(_, lines)| lines).collect()
+}
+
+/// Colors each log line according to its log level.
+///
+/// Lines without a recognizable level (e.g. backtraces) use the color of the
+/// preceding line. Lines that contain ANSI escape sequences were already colored
+/// by the node, so they are kept as they are. If `colored` is false, the text is
+/// not changed.
+fn colorize_levels(text: &str, colored: bool) -> Result<String> {
+    let mut buffer = if colored {
+        Buffer::ansi()
+    } else {
+        Buffer::no_color()
+    };
+    let mut level = None;
+    for line in text.lines() {
+        let stripped = strip_ansi(line);
+        level = parse_level(&stripped).or(level);
+        if stripped.len() != line.len() {
+            writeln!(buffer, "{line}")?;
+            continue;
+        }
+        let mut spec = ColorSpec::new();
+        match level {
+            Some("ERROR") => spec.set_fg(Some(Color::Red)).set_bold(true),
+            Some("WARN") => spec.set_fg(Some(Color::Yellow)),
+            Some("INFO") => spec.set_fg(Some(Color::Green)),
+            Some("DEBUG") => spec.set_fg(Some(Color::Blue)),
+            Some(_) => spec.set_dimmed(true),
+            None => &mut spec,
+        };
+        buffer.set_color(&spec)?;
+        write!(buffer, "{line}")?;
+        buffer.reset()?;
+        writeln!(buffer)?;
+    }
+    Ok(String::from_utf8_lossy(buffer.as_slice()).into_owned())
+}
+
+/// Finds the log level among the first words of the given log line.
+fn parse_level(line: &str) -> Option<&'static str> {
+    line.split_whitespace()
+        .take(3)
+        .flat_map(|word| word.split(|c: char| !c.is_ascii_alphabetic()))
+        .find_map(|word| match word {
+            "ERROR" => Some("ERROR"),
+            "WARN" | "WARNING" => Some("WARN"),
+            "INFO" => Some("INFO"),
+            "DEBUG" => Some("DEBUG"),
+            "TRACE" => Some("TRACE"),
+            _ => None,
+        })
+}
+
+/// Parses the RFC 3339 timestamp at the start of the given log line, if any.
+fn parse_timestamp(line: &str) -> Option<DateTime<FixedOffset>> {
+    let line = strip_ansi(line);
+    let first = line.split_whitespace().next()?;
+    DateTime::parse_from_rfc3339(first).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dora_core::message::uhlc;
+    use std::time::Duration;
+
+    fn record(secs: u64, level: Option<&str>, message: &str) -> LogRecord {
+        let id = *uhlc::HLC::default().new_timestamp().get_id();
+        let time = uhlc::NTP64::from(Duration::from_secs(1_709_287_200 + secs));
+        LogRecord {
+            timestamp: uhlc::Timestamp::new(time, id),
+            logged_at: None,
+            level: level.map(ToOwned::to_owned),
+            target: Some("node".to_owned()),
+            message: message.to_owned(),
+        }
+    }
+
+    fn format_record(node: &str, record: &LogRecord, format: LogFormat) -> String {
+        let mut out = Vec::new();
+        write_record(&mut out, node, record, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn format_rfc5424() {
+        let record = record(0, Some("WARN"), "low battery\nrecharge now");
+        assert_eq!(
+            format_record("camera", &record, LogFormat::Rfc5424),
+            "<12>1 2024-03-01T10:00:00.000000Z - camera - - - WARN node: low battery\n\
+             <12>1 2024-03-01T10:00:00.000000Z - camera - - - recharge now\n"
+        );
+    }
+
+    #[test]
+    fn format_json() {
+        let record = record(1, None, "hello");
+        let json: serde_json::Value =
+            serde_json::from_str(&format_record("camera", &record, LogFormat::Json)).unwrap();
+        assert_eq!(json["node"], "camera");
+        assert_eq!(json["timestamp"], "2024-03-01T10:00:01.000000Z");
+        assert_eq!(json["logged_at"], serde_json::Value::Null);
+        assert_eq!(json["level"], serde_json::Value::Null);
+        assert_eq!(json["message"], "hello");
+
+        // the logged timestamp takes precedence over the receive time
+        let mut record = record;
+        record.logged_at = Some(uhlc::NTP64::from(Duration::from_secs(1_709_287_200)));
+        let json: serde_json::Value =
+            serde_json::from_str(&format_record("camera", &record, LogFormat::Json)).unwrap();
+        assert_eq!(json["timestamp"], "2024-03-01T10:00:00.000000Z");
+        assert_eq!(json["logged_at"], "2024-03-01T10:00:00.000000Z");
+        assert_eq!(json["received_at"], "2024-03-01T10:00:01.000000Z");
+    }
+
+    #[test]
+    fn follow_skips_printed_output() {
+        let mut position = 10;
+        assert_eq!(new_output(&mut position, 5, b"0123456789"), b"56789");
+        assert_eq!(position, 15);
+        assert_eq!(new_output(&mut position, 15, b"ab"), b"ab");
+        assert_eq!(position, 17);
+        // the log file was truncated
+        assert_eq!(new_output(&mut position, 0, b"new"), b"new");
+        assert_eq!(position, 3);
+    }
+
+    #[test]
+    fn diff_ignores_timestamps() {
+        let old = [record(0, Some("INFO"), "start"), record(1, None, "a")];
+        let new = [record(5, Some("INFO"), "start"), record(6, None, "b")];
+        assert_eq!(
+            unified_diff("old", &old, "new", &new, false),
+            "--- old\n+++ new\n@@ -1,2 +1,2 @@\n INFO node: start\n-node: a\n+node: b\n"
+        );
+        assert_eq!(unified_diff("old", &old[..1], "new", &new[..1], false), "");
+        assert!(unified_diff("old", &old[..1], "new", &new[..1], true)
+            .contains("-2024-03-01T10:00:00.000000Z INFO node: start"));
+    }
+
+    #[test]
+    fn strip_escape_sequences() {
+        let text = "\x1b[2m2024-03-01T10:00:00Z\x1b[0m \x1b[1;31mERROR\x1b[0m failed\n";
+        assert_eq!(strip_ansi(text), "2024-03-01T10:00:00Z ERROR failed\n");
+        let link = "see \x1b]8;;https://dora-rs.ai\x1b\\docs\x1b]8;;\x07 here";
+        assert_eq!(strip_ansi(link), "see docs here");
+        assert_eq!(strip_ansi("plain\ttext"), "plain\ttext");
+    }
+
+    #[test]
+    fn colorize_keeps_node_colors() {
+        let text = "\x1b[31mERROR\x1b[0m failed\nERROR failed\n  at main.rs:12\n";
+        assert_eq!(colorize_levels(text, false).unwrap(), text);
+        assert_eq!(
+            colorize_levels(&strip_ansi(text), false).unwrap(),
+            "ERROR failed\nERROR failed\n  at main.rs:12\n"
+        );
+        let colored = colorize_levels(text, true).unwrap();
+        let lines: Vec<_> = colored.lines().collect();
+        assert_eq!(lines[0], "\x1b[31mERROR\x1b[0m failed");
+        assert!(lines[1].starts_with("\x1b[0m\x1b[1m\x1b[31m"));
+        assert!(lines[2].starts_with("\x1b[0m\x1b[1m\x1b[31m"));
+    }
+
+    #[test]
+    fn truncate_long_lines() {
+        assert_eq!(truncate_lines("short\n0123456789", 8), "short\n0123456…");
+        assert_eq!(truncate_lines("exactly8", 8), "exactly8");
+        assert_eq!(truncate_lines("äöüäöü", 4), "äöü…");
+    }
+
+    #[test]
+    fn count_lines() {
+        assert_eq!(line_count(b""), 0);
+        assert_eq!(line_count(b"a\nb\n"), 2);
+        assert_eq!(line_count(b"a\nb"), 2);
+
+        let records = [
+            record(0, Some("INFO"), "start"),
+            record(1, Some("ERROR"), "failed\n  at main.rs:12"),
+            record(2, None, ""),
+            record(3, Some("INFO"), "done"),
+        ];
+        assert_eq!(
+            line_counts_by_level(&records),
+            [("ERROR", 2), ("INFO", 2), ("NONE", 1)]
+        );
+    }
+
+    #[test]
+    fn records_as_table() {
+        let mut first = record(5, Some("INFO"), "start");
+        first.logged_at = Some(uhlc::NTP64::from(Duration::from_secs(1_709_287_200)));
+        let second = record(1, None, "done");
+        let batch = records_batch(&[("camera", &first), ("plot", &second)]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let names: Vec<_> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "node",
+                "timestamp",
+                "logged_at",
+                "received_at",
+                "level",
+                "target",
+                "message"
+            ]
+        );
+        let timestamps = |i: usize| {
+            batch
+                .column(i)
+                .as_any()
+                .downcast_ref::<TimestampNanosecondArray>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(timestamps(1).value(0), 1_709_287_200_000_000_000);
+        assert_eq!(timestamps(1).value(1), 1_709_287_201_000_000_000);
+        assert!(batch.column(2).is_null(1));
+        assert_eq!(timestamps(3).value(0), 1_709_287_205_000_000_000);
+        assert!(batch.column(4).is_null(1));
+    }
+
+    #[test]
+    fn parse_levels() {
+        let line = "\x1b[2m2024-03-01T10:00:00Z\x1b[0m \x1b[31mERROR\x1b[0m failed";
+        assert_eq!(parse_level(&strip_ansi(line)), Some("ERROR"));
+        assert_eq!(parse_level("WARNING:root:careful"), Some("WARN"));
+        assert_eq!(parse_level("[INFO] started"), Some("INFO"));
+        assert_eq!(parse_level("  at main.rs:12"), None);
+    }
+
+    #[test]
+    fn merge_keeps_continuation_lines() {
+        let logs = vec![
+            (
+                "a".to_owned(),
+                "\x1b[2m2024-03-01T10:00:00Z\x1b[0m first\ncontinued\n2024-03-01T10:00:02Z third\n"
+                    .to_owned(),
+            ),
+            ("b".to_owned(), "2024-03-01T10:00:01Z second\n".to_owned()),
+        ];
+        assert_eq!(
+            merge_sorted(&logs),
+            "[a] \x1b[2m2024-03-01T10:00:00Z\x1b[0m first\n[a] continued\n\
+             [b] 2024-03-01T10:00:01Z second\n\
+             [a] 2024-03-01T10:00:02Z third\n"
+        );
+    }
+
+    #[test]
+    fn sort_records_by_timestamp() {
+        let logs = vec![
+            (
+                "a".to_owned(),
+                vec![record(0, None, "first"), record(2, None, "third")],
+            ),
+            ("b".to_owned(), vec![record(1, None, "second")]),
+        ];
+        let sorted: Vec<_> = ordered_records(&logs, true)
+            .into_iter()
+            .map(|(node, r)| (node, r.message.as_str()))
+            .collect();
+        assert_eq!(sorted, [("a", "first"), ("b", "second"), ("a", "third")]);
+
+        // logged timestamps take precedence over the receive time
+        let mut late = record(10, None, "logged early");
+        late.logged_at = Some(uhlc::NTP64::from(Duration::from_secs(1_709_287_200)));
+        let logs = vec![
+            ("a".to_owned(), vec![record(5, None, "received")]),
+            ("b".to_owned(), vec![late, record(11, None, "continued")]),
+        ];
+        let sorted: Vec<_> = ordered_records(&logs, true)
+            .into_iter()
+            .map(|(_, r)| r.message.as_str())
+            .collect();
+        assert_eq!(sorted, ["logged early", "continued", "received"]);
+    }
+}
@@ -14,6 +14,7 @@ use dora_daemon::Daemon;
 use dora_tracing::set_up_tracing;
 use duration_str::parse;
 use eyre::{bail, Context};
+use logs::LogFormat;
 use std::net::SocketAddr;
 use std::{
     net::{IpAddr, Ipv4Addr},
@@ -96,11 +97,82 @@ enum Command {
     List,
     // Planned for future releases:
     // Dashboard,
-    /// Show logs of a given dataflow and node.
-    #[command(allow_missing_positional = true)]
+    /// Show logs of the given nodes.
     Logs {
-        dataflow: Option<String>,
-        node: String,
+        /// Dataflow UUID or name, followed by the nodes. The dataflow can be omitted
+        /// if only one dataflow is running.
+        #[clap(required = true, value_name = "[DATAFLOW] NODE")]
+        nodes: Vec<String>,
+        /// Merge the logs of all given nodes into a single timeline, sorted by timestamp.
+        #[clap(long, alias = "merge-timestamp-sort", action)]
+        sort: bool,
+        /// Clear the stored logs of the given nodes instead of showing them.
+        #[clap(long, action)]
+        clear: bool,
+        /// Print the operating system process IDs of the given nodes instead of their
+        /// logs, e.g. for use with `perf` or `htop`.
+        #[clap(long, action, conflicts_with_all = ["clear", "sort", "format", "json"])]
+        pid: bool,
+        /// Output format of the logs.
+        #[clap(long, value_enum, default_value_t = LogFormat::Plain)]
+        format: LogFormat,
+        /// Print the log records as JSON, one object per line. Shorthand for `--format json`.
+        #[clap(long, action, conflicts_with = "format")]
+        json: bool,
+        /// Write the log records to the given file as table instead of showing them.
+        ///
+        /// Files with `.parquet` extension are written as Parquet, all others as Arrow
+        /// IPC file. The table has the columns `node` (utf8), `timestamp`, `logged_at`
+        /// (nullable), and `received_at` (all timestamp[ns, UTC]), `level` (utf8,
+        /// nullable), `target` (utf8, nullable), and `message` (utf8). The `timestamp`
+        /// is the logged time if known, and the receive time otherwise.
+        #[clap(long, value_name = "PATH", conflicts_with_all = ["clear", "pid", "format", "json"])]
+        arrow: Option<PathBuf>,
+        /// Keep printing new log output as it is written.
+        #[clap(long, short, action, conflicts_with_all = ["clear", "pid", "arrow", "sort", "format", "json"])]
+        follow: bool,
+        /// Compare the logs of two dataflow runs, given by UUID or name, as unified diff.
+        #[clap(
+            long,
+            num_args = 2,
+            value_names = ["DATAFLOW1", "DATAFLOW2"],
+            conflicts_with_all = ["clear", "pid", "arrow", "sort", "format", "json", "follow"]
+        )]
+        diff: Option<Vec<String>>,
+        /// Include the timestamps of the log records in the `--diff` comparison.
+        #[clap(long, action, requires = "diff")]
+        with_timestamps: bool,
+        /// Remove ANSI escape sequences (e.g. colors) that the nodes wrote to their
+        /// output, e.g. for attaching the logs to a bug report.
+        ///
+        /// The log levels are still colored when printing to a terminal. The JSON,
+        /// syslog, and journal formats never contain escape sequences.
+        #[clap(long, action, conflicts_with_all = ["clear", "pid", "arrow", "diff"])]
+        strip_ansi: bool,
+        /// Truncate log message lines that are longer than the given number of
+        /// characters, marking the truncation with an ellipsis.
+        #[clap(
+            long,
+            value_name = "N",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+            conflicts_with_all = ["clear", "pid", "arrow", "diff", "follow"]
+        )]
+        truncate_long_lines: Option<usize>,
+        /// Show long lines completely. Overrides a preceding `--truncate-long-lines`,
+        /// e.g. one that is set in a shell alias.
+        #[clap(long, action, overrides_with = "truncate_long_lines")]
+        full: bool,
+        /// Print only the number of log lines of the given nodes, e.g. for health
+        /// checks in scripts.
+        #[clap(
+            long,
+            action,
+            conflicts_with_all = ["clear", "pid", "arrow", "follow", "diff", "sort", "format", "json"]
+        )]
+        count: bool,
+        /// Break the `--count` output down by log level.
+        #[clap(long, action, requires = "count")]
+        level: bool,
     },
     // Metrics,
     // Stats,
@@ -203,22 +275,55 @@ fn run() -> eyre::Result<()> {
         } => template::create(args, internal_create_with_path_dependencies)?,
         Command::Up { config } => up::up(config.as_deref())?,
 
-        Command::Logs { dataflow, node } => {
+        Command::Logs {
+            nodes,
+            sort,
+            clear,
+            pid,
+            format,
+            json,
+            arrow,
+            follow,
+            diff,
+            with_timestamps,
+            strip_ansi,
+            truncate_long_lines,
+            full,
+            count,
+            level,
+        } => {
             let mut session =
                 connect_to_coordinator().wrap_err("failed to connect to dora coordinator")?;
-            let uuids = query_running_dataflows(&mut *session)
-                .wrap_err("failed to query running dataflows")?;
-            if let Some(dataflow) = dataflow {
-                let uuid = Uuid::parse_str(&dataflow).ok();
-                let name = if uuid.is_some() { None } else { Some(dataflow) };
-                logs::logs(&mut *session, uuid, name, node)?
+            if let Some([old, new]) = diff.as_deref() {
+                let old = logs::resolve_dataflow(&mut *session, Some(old.clone()))?;
+                let new = logs::resolve_dataflow(&mut *session, Some(new.clone()))?;
+                logs::diff_logs(&mut *session, old, new, nodes, with_timestamps)?
             } else {
-                let uuid = match &uuids[..] {
-                    [] => bail!("No dataflows are running"),
-                    [uuid] => uuid.clone(),
-                    _ => inquire::Select::new("Choose dataflow to show logs:", uuids).prompt()?,
-                };
-                logs::logs(&mut *session, Some(uuid.uuid), None, node)?
+                let (dataflow, nodes) = logs::split_dataflow_arg(&mut *session, nodes)?;
+                let uuid = logs::resolve_dataflow(&mut *session, dataflow)?;
+                if clear {
+                    logs::clear_logs(&mut *session, uuid, nodes)?
+                } else if pid {
+                    logs::print_pids(&mut *session, uuid, nodes)?
+                } else if count {
+                    logs::print_line_counts(&mut *session, uuid, nodes, level)?
+                } else if let Some(path) = arrow {
+                    logs::write_table(&mut *session, uuid, nodes, sort, &path)?
+                } else if follow {
+                    logs::follow_logs(&mut *session, uuid, nodes, strip_ansi)?
+                } else {
+                    let format = if json { LogFormat::Json } else { format };
+                    let max_line_len = truncate_long_lines.filter(|_| !full);
+                    logs::logs(
+                        &mut *session,
+                        uuid,
+                        nodes,
+                        sort,
+                        format,
+                        strip_ansi,
+                        max_line_len,
+                    )?
+                }
             }
         }
         Command::Start {
@@ -441,16 +546,13 @@ fn list(session: &mut TcpRequestReplyConnection) -> Result<(), eyre::ErrReport>
 fn query_running_dataflows(
     session: &mut TcpRequestReplyConnection,
 ) -> Result<Vec<DataflowId>, eyre::ErrReport> {
-    let reply_raw = session
-        .request(&serde_json::to_vec(&ControlRequest::List).unwrap())
-        .wrap_err("failed to send list message")?;
-    let reply: ControlRequestReply =
-        serde_json::from_slice(&reply_raw).wrap_err("failed to parse reply")?;
-    let ids = match reply {
-        ControlRequestReply::DataflowList { dataflows } => dataflows,
-        ControlRequestReply::Error(err) => bail!("{err}"),
-        other => bail!("unexpected list dataflow reply: {other:?}"),
-    };
+    let ids = logs::list_dataflows(session)?
+        .into_iter()
+        .map(|(uuid, name)| DataflowId {
+            uuid,
+            name: (!name.is_empty()).then_some(name),
+        })
+        .collect();
 
     Ok(ids)
 }
@@ -2,7 +2,10 @@ use crate::{
     tcp_utils::{tcp_receive, tcp_send},
     Event,
 };
-use dora_core::topics::{ControlRequest, ControlRequestReply};
+use dora_core::{
+    config::NodeId,
+    topics::{ControlRequest, ControlRequestReply},
+};
 use eyre::{eyre, Context};
 use futures::{
     future::{self, Either},
@@ -17,6 +20,7 @@ use tokio::{
     task::JoinHandle,
 };
 use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
 
 pub(crate) async fn control_events(
     control_listen_addr: SocketAddr,
@@ -99,11 +103,17 @@ async fn handle_requests(
             },
         };
 
-        let result =
-            match serde_json::from_slice(&raw).wrap_err("failed to deserialize incoming message") {
-                Ok(request) => handle_request(request, &tx).await,
-                Err(err) => Err(err),
-            };
+        let request: eyre::Result<ControlRequest> =
+            serde_json::from_slice(&raw).wrap_err("failed to deserialize incoming message");
+        if let Ok(ControlRequest::FollowLogs { uuid, node }) = request {
+            // the connection is used for streaming the logs from now on
+            stream_logs(&mut connection, uuid, node, &tx).await;
+            break;
+        }
+        let result = match request {
+            Ok(request) => handle_request(request, &tx).await,
+            Err(err) => Err(err),
+        };
 
         let reply = result.unwrap_or_else(|err| ControlRequestReply::Error(format!("{err}")));
         let serialized =
@@ -135,6 +145,60 @@ async fn handle_requests(
     }
 }
 
+/// Forwards the log output of the given node to the given connection, until either
+/// the connection or the coordinator is closed.
+async fn stream_logs(
+    connection: &mut TcpStream,
+    dataflow_id: Uuid,
+    node: String,
+    tx: &mpsc::Sender<ControlEvent>,
+) {
+    let node_id: NodeId = node.into();
+    let (reply_tx, mut reply_rx) = mpsc::unbounded_channel();
+    let event = ControlEvent::FollowLogs {
+        dataflow_id,
+        node_id: node_id.clone(),
+        reply_sender: reply_tx,
+    };
+    if tx.send(event).await.is_err() {
+        return;
+    }
+
+    loop {
+        let next_reply = reply_rx.recv().map(Either::Left);
+        // followers don't send any messages, so this only finishes when the
+        // connection is closed
+        let connection_closed = tcp_receive(connection).map(Either::Right);
+        let reply = match (next_reply, connection_closed).race().await {
+            Either::Left(Some(reply)) => reply,
+            Either::Left(None) | Either::Right(_) => break,
+        };
+        let serialized = match serde_json::to_vec(&reply) {
+            Ok(s) => s,
+            Err(err) => {
+                tracing::error!("failed to serialize log reply: {err}");
+                break;
+            }
+        };
+        if let Err(err) = tcp_send(connection, &serialized).await {
+            tracing::debug!("log stream closed: {err}");
+            break;
+        }
+        if matches!(reply, ControlRequestReply::Error(_)) {
+            break;
+        }
+    }
+
+    // the coordinator stops following the logs if this was the last follower
+    drop(reply_rx);
+    let _ = tx
+        .send(ControlEvent::LogsUnfollowed {
+            dataflow_id,
+            node_id,
+        })
+        .await;
+}
+
 async fn handle_request(
     request: ControlRequest,
     tx: &mpsc::Sender<ControlEvent>,
@@ -160,6 +224,18 @@ pub enum ControlEvent {
         request: ControlRequest,
         reply_sender: oneshot::Sender<eyre::Result<ControlRequestReply>>,
     },
+    /// Request to stream the log output of the given node, see
+    /// [`ControlRequest::FollowLogs`].
+    FollowLogs {
+        dataflow_id: Uuid,
+        node_id: NodeId,
+        reply_sender: mpsc::UnboundedSender<ControlRequestReply>,
+    },
+    /// A log stream started through [`ControlEvent::FollowLogs`] was closed.
+    LogsUnfollowed {
+        dataflow_id: Uuid,
+        node_id: NodeId,
+    },
     Error(eyre::Report),
 }
 
@@ -10,7 +10,7 @@ use dora_core::{
     descriptor::{Descriptor, ResolvedNode},
     message::uhlc::{self, HLC},
     topics::{
-        control_socket_addr, ControlRequest, ControlRequestReply, DataflowId,
+        control_socket_addr, ControlRequest, ControlRequestReply, DataflowId, LogRecord,
         DORA_COORDINATOR_PORT_DEFAULT,
     },
 };
@@ -145,6 +145,8 @@ async fn start_inner(
     let mut dataflow_results: HashMap<Uuid, BTreeMap<String, Result<(), String>>> = HashMap::new();
     let mut archived_dataflows: HashMap<Uuid, ArchivedDataflow> = HashMap::new();
     let mut daemon_connections: HashMap<_, DaemonConnection> = HashMap::new();
+    let mut log_subscribers: HashMap<(Uuid, NodeId), Vec<mpsc::UnboundedSender<_>>> =
+        HashMap::new();
 
     while let Some(event) = events.next().await {
         if event.log() {
@@ -261,6 +263,57 @@ async fn start_inner(
                         }
                     }
                 }
+                DataflowEvent::BarrierReached {
+                    name,
+                    node_id,
+                    expected,
+                } => {
+                    let Some(dataflow) = running_dataflows.get_mut(&uuid) else {
+                        tracing::warn!("dataflow not running on BarrierReached");
+                        continue;
+                    };
+                    let waiting = dataflow.barriers.entry(name.clone()).or_default();
+                    waiting.insert(node_id);
+                    if waiting.len() >= expected {
+                        dataflow.barriers.remove(&name);
+                        let message = serde_json::to_vec(&Timestamped {
+                            inner: DaemonCoordinatorEvent::BarrierReleased {
+                                dataflow_id: uuid,
+                                name: name.clone(),
+                            },
+                            timestamp: clock.new_timestamp(),
+                        })
+                        .wrap_err("failed to serialize BarrierReleased message")?;
+
+                        // nodes might wait on the barrier on all machines
+                        for machine_id in &dataflow.machines {
+                            let Some(connection) = daemon_connections.get_mut(machine_id) else {
+                                tracing::warn!(
+                                    "no daemon connection found for machine `{machine_id}`"
+                                );
+                                continue;
+                            };
+                            tcp_send(&mut connection.stream, &message)
+                                .await
+                                .wrap_err_with(|| {
+                                    format!(
+                                        "failed to send BarrierReleased({uuid}, {name}) message \
+                                        to machine {machine_id}"
+                                    )
+                                })?;
+                        }
+                    }
+                }
+                DataflowEvent::BarrierLeft { name, node_id } => {
+                    if let Some(dataflow) = running_dataflows.get_mut(&uuid) {
+                        if let Some(waiting) = dataflow.barriers.get_mut(&name) {
+                            waiting.remove(&node_id);
+                            if waiting.is_empty() {
+                                dataflow.barriers.remove(&name);
+                            }
+                        }
+                    }
+                }
                 DataflowEvent::DataflowFinishedOnMachine { machine_id, result } => {
                     match running_dataflows.entry(uuid) {
                         std::collections::hash_map::Entry::Occupied(mut entry) => {
@@ -439,6 +492,51 @@ async fn start_inner(
                             .map(ControlRequestReply::Logs);
                             let _ = reply_sender.send(reply);
                         }
+                        ControlRequest::ClearLogs { uuid, node } => {
+                            let reply = clear_logs(
+                                &running_dataflows,
+                                &archived_dataflows,
+                                uuid,
+                                node.into(),
+                                &mut daemon_connections,
+                                clock.new_timestamp(),
+                            )
+                            .await
+                            .map(|()| ControlRequestReply::LogsCleared);
+                            let _ = reply_sender.send(reply);
+                        }
+                        ControlRequest::StructuredLogs { uuid, node } => {
+                            let reply = retrieve_structured_logs(
+                                &running_dataflows,
+                                &archived_dataflows,
+                                uuid,
+                                node.into(),
+                                &mut daemon_connections,
+                                clock.new_timestamp(),
+                            )
+                            .await
+                            .map(ControlRequestReply::StructuredLogs);
+                            let _ = reply_sender.send(reply);
+                        }
+                        ControlRequest::NodePid { uuid, node } => {
+                            let reply = retrieve_node_pid(
+                                &running_dataflows,
+                                &archived_dataflows,
+                                uuid,
+                                node.into(),
+                                &mut daemon_connections,
+                                clock.new_timestamp(),
+                            )
+                            .await
+                            .map(ControlRequestReply::NodePid);
+                            let _ = reply_sender.send(reply);
+                        }
+                        ControlRequest::FollowLogs { .. } => {
+                            // handled by the control connection, which streams the replies
+                            let _ = reply_sender.send(Err(eyre!(
+                                "FollowLogs requests are not supported on this connection"
+                            )));
+                        }
                         ControlRequest::Destroy => {
                             tracing::info!("Received destroy command");
 
@@ -481,6 +579,61 @@ async fn start_inner(
                         }
                     }
                 }
+                ControlEvent::FollowLogs {
+                    dataflow_id,
+                    node_id,
+                    reply_sender,
+                } => {
+                    let result = follow_logs(
+                        &running_dataflows,
+                        &archived_dataflows,
+                        dataflow_id,
+                        node_id.clone(),
+                        &mut daemon_connections,
+                        clock.new_timestamp(),
+                    )
+                    .await;
+                    match result {
+                        Ok(offset) => {
+                            if reply_sender
+                                .send(ControlRequestReply::LogsFollowed { offset })
+                                .is_ok()
+                            {
+                                log_subscribers
+                                    .entry((dataflow_id, node_id))
+                                    .or_default()
+                                    .push(reply_sender);
+                            }
+                        }
+                        Err(err) => {
+                            let _ = reply_sender.send(ControlRequestReply::Error(format!("{err}")));
+                        }
+                    }
+                }
+                ControlEvent::LogsUnfollowed {
+                    dataflow_id,
+                    node_id,
+                } => {
+                    let key = (dataflow_id, node_id);
+                    if let Some(subscribers) = log_subscribers.get_mut(&key) {
+                        subscribers.retain(|subscriber| !subscriber.is_closed());
+                        if subscribers.is_empty() {
+                            log_subscribers.remove(&key);
+                            let result = unfollow_logs(
+                                &running_dataflows,
+                                &archived_dataflows,
+                                dataflow_id,
+                                &key.1,
+                                &mut daemon_connections,
+                                clock.new_timestamp(),
+                            )
+                            .await;
+                            if let Err(err) = result {
+                                tracing::warn!("{err:?}");
+                            }
+                        }
+                    }
+                }
                 ControlEvent::Error(err) => tracing::error!("{err:?}"),
             },
             Event::DaemonHeartbeatInterval => {
@@ -528,6 +681,39 @@ async fn start_inner(
                     connection.last_heartbeat = Instant::now();
                 }
             }
+            Event::Logs {
+                dataflow_id,
+                node_id,
+                offset,
+                data,
+            } => {
+                let key = (dataflow_id, node_id);
+                if let Some(subscribers) = log_subscribers.get_mut(&key) {
+                    // remove subscribers whose connection was closed
+                    subscribers.retain(|subscriber| {
+                        let chunk = ControlRequestReply::LogChunk {
+                            offset,
+                            data: data.clone(),
+                        };
+                        subscriber.send(chunk).is_ok()
+                    });
+                    if subscribers.is_empty() {
+                        log_subscribers.remove(&key);
+                        let result = unfollow_logs(
+                            &running_dataflows,
+                            &archived_dataflows,
+                            dataflow_id,
+                            &key.1,
+                            &mut daemon_connections,
+                            clock.new_timestamp(),
+                        )
+                        .await;
+                        if let Err(err) = result {
+                            tracing::warn!("{err:?}");
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -652,6 +838,8 @@ struct RunningDataflow {
     pending_machines: BTreeSet<String>,
     init_success: bool,
     nodes: Vec<ResolvedNode>,
+    /// Nodes that wait on a barrier, by barrier name.
+    barriers: BTreeMap<String, BTreeSet<NodeId>>,
 
     reply_senders: Vec<tokio::sync::oneshot::Sender<eyre::Result<ControlRequestReply>>>,
 }
@@ -765,38 +953,29 @@ async fn reload_dataflow(
     Ok(())
 }
 
-async fn retrieve_logs(
+/// Returns the ID of the machine that runs the given node of the given dataflow.
+fn node_machine_id(
     running_dataflows: &HashMap<Uuid, RunningDataflow>,
     archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
     dataflow_id: Uuid,
-    node_id: NodeId,
-    daemon_connections: &mut HashMap<String, DaemonConnection>,
-    timestamp: uhlc::Timestamp,
-) -> eyre::Result<Vec<u8>> {
+    node_id: &NodeId,
+) -> eyre::Result<String> {
     let nodes = if let Some(dataflow) = archived_dataflows.get(&dataflow_id) {
-        dataflow.nodes.clone()
+        &dataflow.nodes
     } else if let Some(dataflow) = running_dataflows.get(&dataflow_id) {
-        dataflow.nodes.clone()
+        &dataflow.nodes
     } else {
         bail!("No dataflow found with UUID `{dataflow_id}`")
     };
 
-    let message = serde_json::to_vec(&Timestamped {
-        inner: DaemonCoordinatorEvent::Logs {
-            dataflow_id,
-            node_id: node_id.clone(),
-        },
-        timestamp,
-    })?;
-
-    let machine_ids: Vec<String> = nodes
+    let machine_ids: Vec<&String> = nodes
         .iter()
-        .filter(|node| node.id == node_id)
-        .map(|node| node.deploy.machine.clone())
+        .filter(|node| &node.id == node_id)
+        .map(|node| &node.deploy.machine)
         .collect();
 
-    let machine_id = if let [machine_id] = &machine_ids[..] {
-        machine_id
+    if let [machine_id] = &machine_ids[..] {
+        Ok((*machine_id).clone())
     } else if machine_ids.is_empty() {
         bail!("No machine contains {}/{}", dataflow_id, node_id)
     } else {
@@ -805,7 +984,26 @@ async fn retrieve_logs(
             dataflow_id,
             node_id
         )
-    };
+    }
+}
+
+async fn retrieve_logs(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<Vec<u8>> {
+    let machine_id = node_machine_id(running_dataflows, archived_dataflows, dataflow_id, &node_id)?;
+
+    let message = serde_json::to_vec(&Timestamped {
+        inner: DaemonCoordinatorEvent::Logs {
+            dataflow_id,
+            node_id: node_id.clone(),
+        },
+        timestamp,
+    })?;
 
     let daemon_connection = daemon_connections
         .get_mut(machine_id.as_str())
@@ -829,6 +1027,200 @@ async fn retrieve_logs(
     reply_logs.map_err(|err| eyre!(err))
 }
 
+/// Sends the given event to the daemon that runs the given node and waits for
+/// its reply.
+async fn node_daemon_request(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: &NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    event: DaemonCoordinatorEvent,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<DaemonCoordinatorReply> {
+    let machine_id = node_machine_id(running_dataflows, archived_dataflows, dataflow_id, node_id)?;
+
+    let message = serde_json::to_vec(&Timestamped {
+        inner: event,
+        timestamp,
+    })?;
+
+    let daemon_connection = daemon_connections
+        .get_mut(machine_id.as_str())
+        .wrap_err("no daemon connection")?;
+    tcp_send(&mut daemon_connection.stream, &message)
+        .await
+        .wrap_err("failed to send message to daemon")?;
+
+    // wait for reply
+    let reply_raw = tcp_receive(&mut daemon_connection.stream)
+        .await
+        .wrap_err("failed to receive reply from daemon")?;
+    serde_json::from_slice(&reply_raw).wrap_err("failed to deserialize reply from daemon")
+}
+
+/// Asks the daemon of the given node to report new log output of the node.
+///
+/// Returns the length of the log file at the time following started.
+async fn follow_logs(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<u64> {
+    let event = DaemonCoordinatorEvent::FollowLogs {
+        dataflow_id,
+        node_id: node_id.clone(),
+    };
+    let reply = node_daemon_request(
+        running_dataflows,
+        archived_dataflows,
+        dataflow_id,
+        &node_id,
+        daemon_connections,
+        event,
+        timestamp,
+    )
+    .await
+    .wrap_err("failed to follow logs")?;
+    let offset = match reply {
+        DaemonCoordinatorReply::FollowLogsResult(result) => result,
+        other => bail!("unexpected reply after sending follow logs: {other:?}"),
+    };
+    tracing::info!("following logs of `{dataflow_id}/{node_id}`");
+
+    offset.map_err(|err| eyre!(err))
+}
+
+/// Asks the daemon of the given node to stop reporting new log output of the node.
+async fn unfollow_logs(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: &NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<()> {
+    let machine_id = node_machine_id(running_dataflows, archived_dataflows, dataflow_id, node_id)?;
+
+    let message = serde_json::to_vec(&Timestamped {
+        inner: DaemonCoordinatorEvent::UnfollowLogs {
+            dataflow_id,
+            node_id: node_id.clone(),
+        },
+        timestamp,
+    })?;
+
+    let daemon_connection = daemon_connections
+        .get_mut(machine_id.as_str())
+        .wrap_err("no daemon connection")?;
+    tcp_send(&mut daemon_connection.stream, &message)
+        .await
+        .wrap_err("failed to send unfollow logs message to daemon")?;
+    tracing::info!("stopped following logs of `{dataflow_id}/{node_id}`");
+
+    Ok(())
+}
+
+async fn retrieve_structured_logs(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<Vec<LogRecord>> {
+    let event = DaemonCoordinatorEvent::StructuredLogs {
+        dataflow_id,
+        node_id: node_id.clone(),
+    };
+    let reply = node_daemon_request(
+        running_dataflows,
+        archived_dataflows,
+        dataflow_id,
+        &node_id,
+        daemon_connections,
+        event,
+        timestamp,
+    )
+    .await
+    .wrap_err("failed to retrieve structured logs")?;
+    let records = match reply {
+        DaemonCoordinatorReply::StructuredLogs(records) => records,
+        other => bail!("unexpected reply after sending structured logs: {other:?}"),
+    };
+    tracing::info!("successfully retrieved structured logs for `{dataflow_id}/{node_id}`");
+
+    records.map_err(|err| eyre!(err))
+}
+
+async fn clear_logs(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<()> {
+    let event = DaemonCoordinatorEvent::ClearLogs {
+        dataflow_id,
+        node_id: node_id.clone(),
+    };
+    let reply = node_daemon_request(
+        running_dataflows,
+        archived_dataflows,
+        dataflow_id,
+        &node_id,
+        daemon_connections,
+        event,
+        timestamp,
+    )
+    .await
+    .wrap_err("failed to clear logs")?;
+    match reply {
+        DaemonCoordinatorReply::ClearLogsResult(result) => result
+            .map_err(|err| eyre!(err))
+            .wrap_err("failed to clear logs")?,
+        other => bail!("unexpected reply after sending clear logs: {other:?}"),
+    }
+    tracing::info!("successfully cleared logs for `{dataflow_id}/{node_id}`");
+
+    Ok(())
+}
+
+async fn retrieve_node_pid(
+    running_dataflows: &HashMap<Uuid, RunningDataflow>,
+    archived_dataflows: &HashMap<Uuid, ArchivedDataflow>,
+    dataflow_id: Uuid,
+    node_id: NodeId,
+    daemon_connections: &mut HashMap<String, DaemonConnection>,
+    timestamp: uhlc::Timestamp,
+) -> eyre::Result<u32> {
+    let event = DaemonCoordinatorEvent::NodePid {
+        dataflow_id,
+        node_id: node_id.clone(),
+    };
+    let reply = node_daemon_request(
+        running_dataflows,
+        archived_dataflows,
+        dataflow_id,
+        &node_id,
+        daemon_connections,
+        event,
+        timestamp,
+    )
+    .await
+    .wrap_err("failed to retrieve node pid")?;
+    let pid = match reply {
+        DaemonCoordinatorReply::NodePid(pid) => pid,
+        other => bail!("unexpected reply after sending node pid: {other:?}"),
+    };
+
+    pid.map_err(|err| eyre!(err))
+}
+
 async fn start_dataflow(
     dataflow: Descriptor,
     working_dir: PathBuf,
@@ -852,6 +1244,7 @@ async fn start_dataflow(
         init_success: true,
         machines,
         nodes,
+        barriers: BTreeMap::new(),
         reply_senders: Vec::new(),
     })
 }
@@ -893,8 +1286,19 @@ async fn destroy_daemons(
 pub enum Event {
     NewDaemonConnection(TcpStream),
     DaemonConnectError(eyre::Report),
-    DaemonHeartbeat { machine_id: String },
-    Dataflow { uuid: Uuid, event: DataflowEvent },
+    DaemonHeartbeat {
+        machine_id: String,
+    },
+    Logs {
+        dataflow_id: Uuid,
+        node_id: NodeId,
+        offset: u64,
+        data: Vec<u8>,
+    },
+    Dataflow {
+        uuid: Uuid,
+        event: DataflowEvent,
+    },
     Control(ControlEvent),
     Daemon(DaemonEvent),
     DaemonHeartbeatInterval,
@@ -907,6 +1311,7 @@ impl Event {
     pub fn log(&self) -> bool {
         match self {
             Event::DaemonHeartbeatInterval => false,
+            Event::Logs { .. } => false,
             _ => true,
         }
     }
@@ -922,6 +1327,15 @@ pub enum DataflowEvent {
         machine_id: String,
         success: bool,
     },
+    BarrierReached {
+        name: String,
+        node_id: NodeId,
+        expected: usize,
+    },
+    BarrierLeft {
+        name: String,
+        node_id: NodeId,
+    },
 }
 
 #[derive(Debug)]
@@ -101,6 +101,53 @@ pub async fn handle_connection(
                         break;
                     }
                 }
+                coordinator_messages::DaemonEvent::Logs {
+                    dataflow_id,
+                    node_id,
+                    offset,
+                    data,
+                } => {
+                    let event = Event::Logs {
+                        dataflow_id,
+                        node_id,
+                        offset,
+                        data,
+                    };
+                    if events_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                coordinator_messages::DaemonEvent::BarrierReached {
+                    dataflow_id,
+                    name,
+                    node_id,
+                    expected,
+                } => {
+                    let event = Event::Dataflow {
+                        uuid: dataflow_id,
+                        event: DataflowEvent::BarrierReached {
+                            name,
+                            node_id,
+                            expected,
+                        },
+                    };
+                    if events_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                coordinator_messages::DaemonEvent::BarrierLeft {
+                    dataflow_id,
+                    name,
+                    node_id,
+                } => {
+                    let event = Event::Dataflow {
+                        uuid: dataflow_id,
+                        event: DataflowEvent::BarrierLeft { name, node_id },
+                    };
+                    if events_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
             },
         };
     }
@@ -4,7 +4,7 @@ use dora_core::config::{Input, OperatorId};
 use dora_core::coordinator_messages::CoordinatorRequest;
 use dora_core::daemon_messages::{DataMessage, InterDaemonEvent, Timestamped};
 use dora_core::message::uhlc::{self, HLC};
-use dora_core::message::{ArrowTypeInfo, Metadata, MetadataParameters};
+use dora_core::message::{ArrowTypeInfo, Metadata, MetadataParameters, TARGET_PARAMETER_PREFIX};
 use dora_core::{
     config::{DataId, InputMapping, NodeId},
     coordinator_messages::DaemonEvent,
@@ -73,6 +73,8 @@ pub struct Daemon {
     exit_when_done: Option<BTreeSet<(Uuid, NodeId)>>,
     /// used to record dataflow results when `exit_when_done` is used
     dataflow_errors: BTreeMap<Uuid, BTreeMap<NodeId, eyre::Report>>,
+    /// log files that are followed by the coordinator, see [`log::follow`]
+    log_watchers: HashMap<(DataflowId, NodeId), notify::RecommendedWatcher>,
 
     clock: Arc<uhlc::HLC>,
 }
@@ -222,6 +224,7 @@ impl Daemon {
             machine_id,
             exit_when_done,
             dataflow_errors: BTreeMap::new(),
+            log_watchers: HashMap::new(),
             clock,
         };
 
@@ -412,6 +415,118 @@ impl Daemon {
                 }
                 RunStatus::Continue
             }
+            DaemonCoordinatorEvent::ClearLogs {
+                dataflow_id,
+                node_id,
+            } => {
+                let result = match self.working_dir.get(&dataflow_id) {
+                    Some(working_dir) => {
+                        let paths = [
+                            log::log_path(working_dir, &dataflow_id, &node_id),
+                            log::structured_log_path(working_dir, &dataflow_id, &node_id),
+                        ];
+                        // the log writer opens the files in append mode, so truncating
+                        // them here makes the following log lines start at the beginning
+                        let mut result = Ok(());
+                        for path in paths {
+                            if let Err(err) = File::create(&path).await {
+                                result =
+                                    Err(format!("Could not truncate log file {path:?}: {err}"));
+                                break;
+                            }
+                        }
+                        result
+                    }
+                    None => Err(format!("no known dataflow with ID `{dataflow_id}`")),
+                };
+                let _ = reply_tx
+                    .send(Some(DaemonCoordinatorReply::ClearLogsResult(result)))
+                    .map_err(|_| {
+                        error!("could not send `ClearLogsResult` reply from daemon to coordinator")
+                    });
+                RunStatus::Continue
+            }
+            DaemonCoordinatorEvent::StructuredLogs {
+                dataflow_id,
+                node_id,
+            } => {
+                match self.working_dir.get(&dataflow_id) {
+                    Some(working_dir) => {
+                        let path = log::structured_log_path(working_dir, &dataflow_id, &node_id);
+                        tokio::spawn(async move {
+                            let records = log::read_records(&path)
+                                .await
+                                .map_err(|err| format!("{err:?}"));
+                            let _ = reply_tx
+                                .send(Some(DaemonCoordinatorReply::StructuredLogs(records)))
+                                .map_err(|_| {
+                                    error!("could not send structured logs reply from daemon to coordinator")
+                                });
+                        });
+                    }
+                    None => {
+                        tracing::warn!(
+                            "received StructuredLogs for unknown dataflow (ID `{dataflow_id}`)"
+                        );
+                        let _ = reply_tx
+                            .send(Some(DaemonCoordinatorReply::StructuredLogs(Err(format!(
+                                "no known dataflow with ID `{dataflow_id}`"
+                            )))))
+                            .map_err(|_| {
+                                error!("could not send structured logs reply from daemon to coordinator")
+                            });
+                    }
+                }
+                RunStatus::Continue
+            }
+            DaemonCoordinatorEvent::FollowLogs {
+                dataflow_id,
+                node_id,
+            } => {
+                let result = match self.working_dir.get(&dataflow_id) {
+                    Some(working_dir) => {
+                        let path = log::log_path(working_dir, &dataflow_id, &node_id);
+                        self.follow_logs(path, dataflow_id, node_id)
+                            .await
+                            .map_err(|err| format!("{err:?}"))
+                    }
+                    None => Err(format!("no known dataflow with ID `{dataflow_id}`")),
+                };
+                let _ = reply_tx
+                    .send(Some(DaemonCoordinatorReply::FollowLogsResult(result)))
+                    .map_err(|_| {
+                        error!("could not send `FollowLogsResult` reply from daemon to coordinator")
+                    });
+                RunStatus::Continue
+            }
+            DaemonCoordinatorEvent::UnfollowLogs {
+                dataflow_id,
+                node_id,
+            } => {
+                // dropping the watcher stops it
+                self.log_watchers.remove(&(dataflow_id, node_id));
+                let _ = reply_tx.send(None);
+                RunStatus::Continue
+            }
+            DaemonCoordinatorEvent::NodePid {
+                dataflow_id,
+                node_id,
+            } => {
+                let result = match self.running.get(&dataflow_id) {
+                    Some(dataflow) => dataflow
+                        .running_nodes
+                        .get(&node_id)
+                        .map(|node| node.pid)
+                        .ok_or_else(|| format!("node `{node_id}` is not running")),
+                    None => Err(format!("no running dataflow with ID `{dataflow_id}`")),
+                };
+                let _ = reply_tx
+                    .send(Some(DaemonCoordinatorReply::NodePid(result)))
+                    .map_err(|_| {
+                        error!("could not send `NodePid` reply from daemon to coordinator")
+                    });
+                RunStatus::Continue
+            }
             DaemonCoordinatorEvent::ReloadDataflow {
                 dataflow_id,
                 node_id,
@@ -459,6 +574,16 @@ impl Daemon {
                 }
                 RunStatus::Exit
             }
+            DaemonCoordinatorEvent::BarrierReleased { dataflow_id, name } => {
+                match self.running.get_mut(&dataflow_id) {
+                    Some(dataflow) => dataflow.release_barrier(&name),
+                    None => tracing::warn!(
+                        "received BarrierReleased for unknown dataflow (ID `{dataflow_id}`)"
+                    ),
+                }
+                let _ = reply_tx.send(None);
+                RunStatus::Continue
+            }
             DaemonCoordinatorEvent::Heartbeat => {
                 self.last_coordinator_heartbeat = Instant::now();
                 let _ = reply_tx.send(None);
@@ -584,6 +709,7 @@ impl Daemon {
                 dataflow.pending_nodes.insert(node.id.clone());
 
                 let node_id = node.id.clone();
+                let log_lock = Arc::new(tokio::sync::Mutex::new(()));
                 match spawn::spawn_node(
                     dataflow_id,
                     &working_dir,
@@ -591,6 +717,7 @@ impl Daemon {
                     self.events_tx.clone(),
                     dataflow_descriptor.clone(),
                     self.clock.clone(),
+                    log_lock.clone(),
                 )
                 .await
                 .wrap_err_with(|| format!("failed to spawn node `{node_id}`"))
@@ -598,7 +725,7 @@ impl Daemon {
                     Ok(pid) => {
                         dataflow
                             .running_nodes
-                            .insert(node_id.clone(), RunningNode { pid });
+                            .insert(node_id.clone(), RunningNode { pid, log_lock });
                     }
                     Err(err) => {
                         tracing::error!("{err:?}");
@@ -755,6 +882,104 @@ impl Daemon {
                 let reply = inner.await.map_err(|err| format!("{err:?}"));
                 let _ = reply_sender.send(DaemonReply::Result(reply));
             }
+            DaemonNodeEvent::RotateLog { reply_sender } => {
+                let reply = match self.working_dir.get(&dataflow_id) {
+                    Some(working_dir) => {
+                        let working_dir = working_dir.clone();
+                        let log_lock = self
+                            .running
+                            .get(&dataflow_id)
+                            .and_then(|dataflow| dataflow.running_nodes.get(&node_id))
+                            .map(|node| node.log_lock.clone())
+                            .unwrap_or_default();
+                        // copying large log files might take a while, so don't block
+                        // the daemon's event loop
+                        tokio::spawn(async move {
+                            let reply = log::rotate(&working_dir, &dataflow_id, &node_id, log_lock)
+                                .await
+                                .map_err(|err| format!("{err:?}"));
+                            let _ = reply_sender.send(DaemonReply::Result(reply));
+                        });
+                        return Ok(());
+                    }
+                    None => Err(format!("no known dataflow with ID `{dataflow_id}`")),
+                };
+                let _ = reply_sender.send(DaemonReply::Result(reply));
+            }
+            DaemonNodeEvent::Barrier {
+                name,
+                expected,
+                reply_sender,
+            } => {
+                let Some(dataflow) = self.running.get_mut(&dataflow_id) else {
+                    let _ = reply_sender.send(DaemonReply::Result(Err(format!(
+                        "no running dataflow with ID `{dataflow_id}`"
+                    ))));
+                    return Ok(());
+                };
+                let waiting = dataflow.barriers.entry(name.clone()).or_default();
+                waiting.insert(node_id.clone(), reply_sender);
+
+                match &mut self.coordinator_connection {
+                    Some(connection) => {
+                        // the coordinator counts the nodes of all machines
+                        let msg = serde_json::to_vec(&Timestamped {
+                            inner: CoordinatorRequest::Event {
+                                machine_id: self.machine_id.clone(),
+                                event: DaemonEvent::BarrierReached {
+                                    dataflow_id,
+                                    name,
+                                    node_id,
+                                    expected,
+                                },
+                            },
+                            timestamp: self.clock.new_timestamp(),
+                        })?;
+                        tcp_send(connection, &msg)
+                            .await
+                            .wrap_err("failed to report barrier to dora-coordinator")?;
+                    }
+                    None => {
+                        // without coordinator, all nodes run on this machine
+                        if waiting.len() >= expected {
+                            dataflow.release_barrier(&name);
+                        }
+                    }
+                }
+            }
+            DaemonNodeEvent::BarrierTimeout { name } => {
+                let Some(dataflow) = self.running.get_mut(&dataflow_id) else {
+                    return Ok(());
+                };
+                let removed = match dataflow.barriers.get_mut(&name) {
+                    Some(waiting) => {
+                        let removed = waiting.remove(&node_id).is_some();
+                        if waiting.is_empty() {
+                            dataflow.barriers.remove(&name);
+                        }
+                        removed
+                    }
+                    None => false,
+                };
+                // the barrier might have been released in the meantime
+                let connection = self.coordinator_connection.as_mut().filter(|_| removed);
+                if let Some(connection) = connection {
+                    let msg = serde_json::to_vec(&Timestamped {
+                        inner: CoordinatorRequest::Event {
+                            machine_id: self.machine_id.clone(),
+                            event: DaemonEvent::BarrierLeft {
+                                dataflow_id,
+                                name,
+                                node_id,
+                            },
+                        },
+                        timestamp: self.clock.new_timestamp(),
+                    })?;
+                    tcp_send(connection, &msg)
+                        .await
+                        .wrap_err("failed to report barrier timeout to dora-coordinator")?;
+                }
+            }
         }
         Ok(())
     }
@@ -948,12 +1173,65 @@ impl Daemon {
                     .wrap_err("failed to report dataflow finish to dora-coordinator")?;
             }
             self.running.remove(&dataflow_id);
+            self.log_watchers.retain(|(id, _), _| *id != dataflow_id);
         }
         Ok(())
     }
 
+    /// Starts watching the given log file, if it isn't watched already.
+    ///
+    /// Returns the current length of the log file.
+    async fn follow_logs(
+        &mut self,
+        path: PathBuf,
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    ) -> eyre::Result<u64> {
+        let offset = tokio::fs::metadata(&path)
+            .await
+            .wrap_err_with(|| format!("Could not read metadata of log file {path:?}"))?
+            .len();
+        let key = (dataflow_id, node_id.clone());
+        if let std::collections::hash_map::Entry::Vacant(entry) = self.log_watchers.entry(key) {
+            let watcher = log::follow(
+                path,
+                offset,
+                dataflow_id,
+                node_id,
+                self.events_tx.clone(),
+                self.clock.clone(),
+            )?;
+            entry.insert(watcher);
+        }
+        Ok(offset)
+    }
+
     async fn handle_dora_event(&mut self, event: DoraEvent) -> eyre::Result<RunStatus> {
         match event {
+            DoraEvent::LogChunk {
+                dataflow_id,
+                node_id,
+                offset,
+                data,
+            } => {
+                if let Some(connection) = &mut self.coordinator_connection {
+                    let msg = serde_json::to_vec(&Timestamped {
+                        inner: CoordinatorRequest::Event {
+                            machine_id: self.machine_id.clone(),
+                            event: DaemonEvent::Logs {
+                                dataflow_id,
+                                node_id,
+                                offset,
+                                data,
+                            },
+                        },
+                        timestamp: self.clock.new_timestamp(),
+                    })?;
+                    tcp_send(connection, &msg)
+                        .await
+                        .wrap_err("failed to send log output to dora-coordinator")?;
+                }
+            }
             DoraEvent::Timer {
                 dataflow_id,
                 interval,
@@ -1151,8 +1429,19 @@ async fn send_output_to_local_receivers(
     let output_id = OutputId(node_id, output_id);
     let local_receivers = dataflow.mappings.get(&output_id).unwrap_or(&empty_set);
     let OutputId(node_id, _) = output_id;
+    // messages with `target.<node_id>` parameters are only delivered to the listed nodes
+    let targets: BTreeSet<String> = metadata
+        .parameters
+        .custom
+        .keys()
+        .filter_map(|key| key.strip_prefix(TARGET_PARAMETER_PREFIX))
+        .map(ToOwned::to_owned)
+        .collect();
     let mut closed = Vec::new();
     for (receiver_id, input_id) in local_receivers {
+        if !targets.is_empty() && !targets.contains(&receiver_id.to_string()) {
+            continue;
+        }
         if let Some(channel) = dataflow.subscribe_channels.get(receiver_id) {
             let item = daemon_messages::NodeEvent::Input {
                 id: input_id.clone(),
@@ -1327,6 +1616,8 @@ fn close_input(
 #[derive(Debug, Clone)]
 struct RunningNode {
     pid: u32,
+    /// Held by the log writer of the node while writing, see [`log::rotate`].
+    log_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 pub struct RunningDataflow {
@@ -1349,6 +1640,9 @@ pub struct RunningDataflow {
     _timer_handles: Vec<futures::future::RemoteHandle<()>>,
     stop_sent: bool,
 
+    /// Local nodes that wait on a barrier, by barrier name.
+    barriers: HashMap<String, BTreeMap<NodeId, oneshot::Sender<DaemonReply>>>,
+
     /// Used in `open_inputs`.
     ///
     /// TODO: replace this with a constant once `BTreeSet::new` is `const` on stable.
@@ -1370,10 +1664,18 @@ impl RunningDataflow {
             pending_drop_tokens: HashMap::new(),
             _timer_handles: Vec::new(),
             stop_sent: false,
+            barriers: HashMap::new(),
             empty_set: BTreeSet::new(),
         }
     }
 
+    /// Lets all local nodes that wait on the given barrier continue.
+    fn release_barrier(&mut self, name: &str) {
+        for (_, reply_sender) in self.barriers.remove(name).unwrap_or_default() {
+            let _ = reply_sender.send(DaemonReply::Result(Ok(())));
+        }
+    }
+
     async fn start(
         &mut self,
         events_tx: &mpsc::Sender<Timestamped<Event>>,
@@ -1402,6 +1704,7 @@ impl RunningDataflow {
                             open_telemetry_context: serialize_context(&span.context()),
                             #[cfg(not(feature = "telemetry"))]
                             open_telemetry_context: "".into(),
+                            ..Default::default()
                         },
                     );
 
@@ -1549,6 +1852,17 @@ pub enum DaemonNodeEvent {
     EventStreamDropped {
         reply_sender: oneshot::Sender<DaemonReply>,
     },
+    RotateLog {
+        reply_sender: oneshot::Sender<DaemonReply>,
+    },
+    Barrier {
+        name: String,
+        expected: usize,
+        reply_sender: oneshot::Sender<DaemonReply>,
+    },
+    BarrierTimeout {
+        name: String,
+    },
 }
 
 #[derive(Debug)]
@@ -1569,6 +1883,13 @@ pub enum DoraEvent {
         node_id: NodeId,
         exit_status: NodeExitStatus,
     },
+    /// New output in a followed log file, see [`log::follow`].
+    LogChunk {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+        offset: u64,
+        data: Vec<u8>,
+    },
 }
 
 #[derive(Debug)]
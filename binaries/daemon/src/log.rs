@@ -1,9 +1,338 @@
-use std::path::{Path, PathBuf};
+use std::{
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use dora_core::config::NodeId;
+use chrono::{DateTime, FixedOffset};
+use dora_core::{
+    config::NodeId,
+    daemon_messages::{DataflowId, Timestamped},
+    message::uhlc,
+    strip_ansi,
+    topics::LogRecord,
+};
+use eyre::Context;
+use notify::Watcher;
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt},
+    sync::mpsc,
+};
 use uuid::Uuid;
 
+use crate::{DoraEvent, Event};
+
 pub fn log_path(working_dir: &Path, dataflow_id: &Uuid, node_id: &NodeId) -> PathBuf {
     let dataflow_dir = working_dir.join("out").join(dataflow_id.to_string());
     dataflow_dir.join(format!("log_{node_id}.txt"))
 }
+
+/// Path of the file that stores the [`LogRecord`]s of a node, one JSON object per line.
+pub fn structured_log_path(working_dir: &Path, dataflow_id: &Uuid, node_id: &NodeId) -> PathBuf {
+    let dataflow_dir = working_dir.join("out").join(dataflow_id.to_string());
+    dataflow_dir.join(format!("log_{node_id}.jsonl"))
+}
+
+/// Archives the log files of the given node and truncates them afterwards.
+///
+/// The archived files are placed next to the original log files, with the current
+/// time as additional suffix (e.g. `log_camera.20240301T100000.123Z.txt`). Like the
+/// `copytruncate` option of `logrotate`, the files are copied instead of renamed
+/// because the log writer keeps them open. This works because the writer opens the
+/// files in append mode, so that new output starts at the beginning of the truncated
+/// files.
+///
+/// The given `log_lock` is held by the log writer of the node while it writes a log
+/// message, so holding it during the rotation ensures that no output is lost between
+/// copying and truncating. Log files that don't exist are skipped. The files are only
+/// truncated after all of them were copied successfully, so that a failed rotation
+/// leaves them untouched.
+pub async fn rotate(
+    working_dir: &Path,
+    dataflow_id: &Uuid,
+    node_id: &NodeId,
+    log_lock: Arc<tokio::sync::Mutex<()>>,
+) -> eyre::Result<()> {
+    let suffix = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string();
+    let paths = [
+        log_path(working_dir, dataflow_id, node_id),
+        structured_log_path(working_dir, dataflow_id, node_id),
+    ];
+    let _guard = log_lock.lock().await;
+
+    let mut archived = Vec::new();
+    for path in paths {
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            continue;
+        }
+        let archive = archive_path(&path, &suffix);
+        if let Err(err) = tokio::fs::copy(&path, &archive).await {
+            for (_, archive) in archived {
+                let _ = tokio::fs::remove_file(archive).await;
+            }
+            return Err(err)
+                .wrap_err_with(|| format!("Could not archive log file {path:?} to {archive:?}"));
+        }
+        archived.push((path, archive));
+    }
+    for (path, _) in archived {
+        tokio::fs::File::create(&path)
+            .await
+            .wrap_err_with(|| format!("Could not truncate log file {path:?}"))?;
+    }
+    Ok(())
+}
+
+/// Inserts the given suffix before the extension of the given log file path.
+fn archive_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{stem}.{suffix}");
+    if let Some(extension) = path.extension() {
+        file_name += &format!(".{}", extension.to_string_lossy());
+    }
+    path.with_file_name(file_name)
+}
+
+/// Reads the log records stored at the given path.
+pub async fn read_records(path: &Path) -> eyre::Result<Vec<LogRecord>> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .wrap_err_with(|| format!("Could not read structured log file {path:?}"))?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).wrap_err("Could not parse log record"))
+        .collect()
+}
+
+/// Watches the given log file and reports new output as [`DoraEvent::LogChunk`].
+///
+/// Uses the file change notifications of the operating system (e.g. inotify or
+/// kqueue), so the file is only read when it was modified. Reporting starts at the
+/// given byte offset. If the file is truncated (e.g. through `dora logs --clear`),
+/// reporting restarts at the beginning of the file.
+///
+/// The file is watched until the returned watcher is dropped.
+pub fn follow(
+    path: PathBuf,
+    offset: u64,
+    dataflow_id: DataflowId,
+    node_id: NodeId,
+    events_tx: mpsc::Sender<Timestamped<Event>>,
+    clock: Arc<uhlc::HLC>,
+) -> eyre::Result<notify::RecommendedWatcher> {
+    let (changed_tx, mut changed_rx) = mpsc::unbounded_channel();
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if event.kind.is_modify() => {
+                let _ = changed_tx.send(());
+            }
+            Ok(_) => {}
+            Err(err) => tracing::warn!("failed to watch log file: {err}"),
+        })
+        .wrap_err("failed to create log file watcher")?;
+    watcher
+        .watch(&path, notify::RecursiveMode::NonRecursive)
+        .wrap_err_with(|| format!("failed to watch log file {path:?}"))?;
+
+    tokio::spawn(async move {
+        let mut position = offset;
+        // the channel is closed when the watcher is dropped
+        while changed_rx.recv().await.is_some() {
+            // handle multiple notifications with a single read
+            while changed_rx.try_recv().is_ok() {}
+
+            let data = match read_from(&path, &mut position).await {
+                Ok(data) => data,
+                Err(err) => {
+                    tracing::warn!("{err:?}");
+                    continue;
+                }
+            };
+            if data.is_empty() {
+                continue;
+            }
+            let event = Timestamped {
+                inner: DoraEvent::LogChunk {
+                    dataflow_id,
+                    node_id: node_id.clone(),
+                    offset: position - data.len() as u64,
+                    data,
+                }
+                .into(),
+                timestamp: clock.new_timestamp(),
+            };
+            if events_tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+/// Reads the contents of the given file starting at `position` and advances
+/// `position` accordingly.
+async fn read_from(path: &Path, position: &mut u64) -> eyre::Result<Vec<u8>> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .wrap_err_with(|| format!("Could not open log file {path:?}"))?;
+    let len = file.metadata().await?.len();
+    if len < *position {
+        // the file was truncated
+        *position = 0;
+    }
+    file.seek(SeekFrom::Start(*position)).await?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .await
+        .wrap_err("Could not read log file")?;
+    *position += data.len() as u64;
+    Ok(data)
+}
+
+/// Parses a message that a node wrote to stdout or stderr into a [`LogRecord`].
+///
+/// The level and target are taken from the first line of the message, if it has
+/// the format of the `tracing` crate (`<timestamp> <LEVEL> <target>: <text>`). A
+/// leading timestamp is removed from the message and stored separately, in addition
+/// to the time at which the daemon received the message.
+pub fn parse_record(message: &str, timestamp: uhlc::Timestamp) -> LogRecord {
+    let text = strip_ansi(message.trim_end());
+    let level = parse_level(&text);
+
+    let mut rest = text.trim_start();
+    let mut logged_at = None;
+    if let Some((first, tail)) = rest.split_once(char::is_whitespace) {
+        if let Some(time) = parse_timestamp(first) {
+            logged_at = SystemTime::from(time)
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(uhlc::NTP64::from);
+            rest = tail.trim_start();
+        }
+    }
+    let mut target = None;
+    if let Some(tail) = level.and_then(|level| rest.strip_prefix(level)) {
+        if tail.starts_with(char::is_whitespace) {
+            rest = tail.trim_start();
+            if let Some((first, tail)) = rest.split_once(char::is_whitespace) {
+                if let Some(name) = first.strip_suffix(':').filter(|n| !n.is_empty()) {
+                    target = Some(name.to_owned());
+                    rest = tail.trim_start();
+                }
+            }
+        }
+    }
+
+    LogRecord {
+        timestamp,
+        logged_at,
+        level: level.map(ToOwned::to_owned),
+        target,
+        message: rest.to_owned(),
+    }
+}
+
+/// Finds the log level among the first words of the given log line.
+fn parse_level(line: &str) -> Option<&'static str> {
+    line.split_whitespace()
+        .take(3)
+        .flat_map(|word| word.split(|c: char| !c.is_ascii_alphabetic()))
+        .find_map(|word| match word {
+            "ERROR" => Some("ERROR"),
+            "WARN" | "WARNING" => Some("WARN"),
+            "INFO" => Some("INFO"),
+            "DEBUG" => Some("DEBUG"),
+            "TRACE" => Some("TRACE"),
+            _ => None,
+        })
+}
+
+/// Parses the RFC 3339 timestamp at the start of the given log line, if any.
+fn parse_timestamp(line: &str) -> Option<DateTime<FixedOffset>> {
+    let line = strip_ansi(line);
+    let first = line.split_whitespace().next()?;
+    DateTime::parse_from_rfc3339(first).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_paths() {
+        let suffix = "20240301T100000.000Z";
+        assert_eq!(
+            archive_path(Path::new("out/log_camera.txt"), suffix),
+            Path::new("out/log_camera.20240301T100000.000Z.txt")
+        );
+        assert_eq!(
+            archive_path(Path::new("out/log_camera.jsonl"), suffix),
+            Path::new("out/log_camera.20240301T100000.000Z.jsonl")
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_skips_missing_files() {
+        let working_dir = std::env::temp_dir().join(format!("dora-rotate-{}", Uuid::new_v4()));
+        let dataflow_id = Uuid::new_v4();
+        let node_id: NodeId = "camera".to_owned().into();
+        let path = log_path(&working_dir, &dataflow_id, &node_id);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "first\nsecond\n").unwrap();
+
+        rotate(&working_dir, &dataflow_id, &node_id, Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        let archives: Vec<_> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|p| *p != path)
+            .collect();
+        assert_eq!(archives.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&archives[0]).unwrap(),
+            "first\nsecond\n"
+        );
+        std::fs::remove_dir_all(working_dir).unwrap();
+    }
+
+    #[test]
+    fn parse_timestamp_with_ansi() {
+        let line = "\x1b[2m2024-03-01T10:00:00.123456Z\x1b[0m \x1b[32m INFO\x1b[0m hello";
+        assert!(parse_timestamp(line).is_some());
+        assert!(parse_timestamp("hello world").is_none());
+    }
+
+    #[test]
+    fn parse_levels() {
+        let line = "\x1b[2m2024-03-01T10:00:00Z\x1b[0m \x1b[31mERROR\x1b[0m failed";
+        assert_eq!(parse_level(&strip_ansi(line)), Some("ERROR"));
+        assert_eq!(parse_level("WARNING:root:careful"), Some("WARN"));
+        assert_eq!(parse_level("[INFO] started"), Some("INFO"));
+        assert_eq!(parse_level("  at main.rs:12"), None);
+    }
+
+    #[test]
+    fn parse_tracing_record() {
+        let timestamp = uhlc::HLC::default().new_timestamp();
+        let message = "\x1b[2m2024-03-01T10:00:00Z\x1b[0m \x1b[32m INFO\x1b[0m \
+                       \x1b[2mdora_node_api::node\x1b[0m\x1b[2m:\x1b[0m ready\n  details\n\n";
+        let record = parse_record(message, timestamp);
+        assert_eq!(record.level.as_deref(), Some("INFO"));
+        assert_eq!(record.target.as_deref(), Some("dora_node_api::node"));
+        assert_eq!(record.message, "ready\n  details");
+        let logged_at = record.logged_at.unwrap().to_duration();
+        assert_eq!(logged_at.as_secs(), 1_709_287_200);
+
+        let record = parse_record("plain output\n", timestamp);
+        assert_eq!(record.level, None);
+        assert_eq!(record.target, None);
+        assert_eq!(record.message, "plain output");
+        assert_eq!(record.logged_at, None);
+    }
+}
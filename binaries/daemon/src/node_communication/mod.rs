@@ -471,6 +471,41 @@ impl Listener {
                 )
                 .await?;
             }
+            DaemonRequest::RotateLog => {
+                let (reply_sender, reply) = oneshot::channel();
+                self.process_daemon_event(
+                    DaemonNodeEvent::RotateLog { reply_sender },
+                    Some(reply),
+                    connection,
+                )
+                .await?;
+            }
+            DaemonRequest::Barrier {
+                name,
+                expected,
+                timeout,
+            } => {
+                let (reply_sender, reply) = oneshot::channel();
+                self.send_daemon_event(DaemonNodeEvent::Barrier {
+                    name: name.clone(),
+                    expected,
+                    reply_sender,
+                })
+                .await?;
+                let reply = match tokio::time::timeout(timeout, reply).await {
+                    Ok(reply) => reply.map_err(|_| eyre!("failed to receive reply from daemon"))?,
+                    Err(_) => {
+                        // the daemon handles events in order, so this event arrives
+                        // before any later barrier request of this node
+                        self.send_daemon_event(DaemonNodeEvent::BarrierTimeout { name })
+                            .await?;
+                        DaemonReply::BarrierTimeout
+                    }
+                };
+                self.send_reply(reply, connection)
+                    .await
+                    .wrap_err("failed to send Barrier reply")?;
+            }
         }
         Ok(())
     }
@@ -505,7 +540,20 @@ impl Listener {
         reply: Option<oneshot::Receiver<DaemonReply>>,
         connection: &mut C,
     ) -> eyre::Result<()> {
-        // send NodeEvent to daemon main loop
+        self.send_daemon_event(event).await?;
+        let reply = if let Some(reply) = reply {
+            reply
+                .await
+                .map_err(|_| eyre!("failed to receive reply from daemon"))?
+        } else {
+            DaemonReply::Empty
+        };
+        self.send_reply(reply, connection).await?;
+        Ok(())
+    }
+
+    /// Sends the given event to the daemon main loop.
+    async fn send_daemon_event(&mut self, event: DaemonNodeEvent) -> eyre::Result<()> {
         let event = Event::Node {
             dataflow_id: self.dataflow_id,
             node_id: self.node_id.clone(),
@@ -519,14 +567,6 @@ impl Listener {
             .send(event)
             .await
             .map_err(|_| eyre!("failed to send event to daemon"))?;
-        let reply = if let Some(reply) = reply {
-            reply
-                .await
-                .map_err(|_| eyre!("failed to receive reply from daemon"))?
-        } else {
-            DaemonReply::Empty
-        };
-        self.send_reply(reply, connection).await?;
         Ok(())
     }
 
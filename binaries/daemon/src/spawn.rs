@@ -28,7 +28,7 @@ use std::{
     sync::Arc,
 };
 use tokio::{
-    fs::File,
+    fs::OpenOptions,
     io::{AsyncBufReadExt, AsyncWriteExt},
     sync::{mpsc, oneshot},
 };
@@ -42,6 +42,7 @@ pub async fn spawn_node(
     daemon_tx: mpsc::Sender<Timestamped<Event>>,
     dataflow_descriptor: Descriptor,
     clock: Arc<HLC>,
+    log_lock: Arc<tokio::sync::Mutex<()>>,
 ) -> eyre::Result<u32> {
     let node_id = node.id.clone();
     tracing::debug!("Spawning node `{dataflow_id}/{node_id}`");
@@ -223,6 +224,8 @@ pub async fn spawn_node(
                     run_config: NodeRunConfig {
                         inputs: runtime_node_inputs(&n),
                         outputs: runtime_node_outputs(&n),
+                        types: Default::default(),
+                        config: Default::default(),
                     },
                     daemon_communication,
                     dataflow_descriptor,
@@ -259,9 +262,29 @@ pub async fn spawn_node(
         std::fs::create_dir_all(&dataflow_dir).context("could not create dataflow_dir")?;
     }
     let (tx, mut rx) = mpsc::channel(10);
-    let mut file = File::create(log::log_path(working_dir, &dataflow_id, &node_id))
+    let log_path = log::log_path(working_dir, &dataflow_id, &node_id);
+    // open in append mode so that the log file can be truncated while the node is running
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
         .await
         .expect("Failed to create log file");
+    file.set_len(0).await.expect("Failed to truncate log file");
+    let mut records_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log::structured_log_path(
+            working_dir,
+            &dataflow_id,
+            &node_id,
+        ))
+        .await
+        .expect("Failed to create structured log file");
+    records_file
+        .set_len(0)
+        .await
+        .expect("Failed to truncate structured log file");
     let mut child_stdout =
         tokio::io::BufReader::new(child.stdout.take().expect("failed to take stdout"));
     let pid = child.id().unwrap();
@@ -401,10 +424,24 @@ pub async fn spawn_node(
                 let _ = daemon_tx_log.send(event).await;
             }
 
+            // don't write while the log files are rotated
+            let guard = log_lock.lock().await;
             let _ = file
                 .write_all(message.as_bytes())
                 .await
                 .map_err(|err| error!("Could not log {message} to file due to {err}"));
+            let record = log::parse_record(&message, uhlc.new_timestamp());
+            match serde_json::to_string(&record) {
+                Ok(mut line) => {
+                    line.push('\n');
+                    let _ = records_file
+                        .write_all(line.as_bytes())
+                        .await
+                        .map_err(|err| error!("Could not write structured log record: {err}"));
+                }
+                Err(err) => error!("Could not serialize log record: {err}"),
+            }
+            drop(guard);
             let formatted: String = message.lines().map(|l| format!("      {l}\n")).collect();
             debug!("{dataflow_id}/{} logged:\n{formatted}", node.id.clone());
             // Make sure that all data has been synced to disk.
@@ -242,6 +242,17 @@ pub struct NodeRunConfig {
     pub inputs: BTreeMap<DataId, Input>,
     #[serde(default)]
     pub outputs: BTreeSet<DataId>,
+    /// Declared arrow data types of inputs and outputs, e.g. `float32`.
+    ///
+    /// The types are not enforced by dora. They allow nodes to allocate buffers
+    /// before the first input arrives.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<DataId, String>,
+    /// Custom configuration values of the node, e.g. `gain: 0.5`.
+    ///
+    /// Dora passes them to the node without interpreting them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub config: BTreeMap<String, serde_yaml::Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
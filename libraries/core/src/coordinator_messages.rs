@@ -1,4 +1,4 @@
-use crate::daemon_messages::DataflowId;
+use crate::{config::NodeId, daemon_messages::DataflowId};
 use eyre::eyre;
 use std::net::SocketAddr;
 
@@ -26,6 +26,27 @@ pub enum DaemonEvent {
         result: Result<(), String>,
     },
     Heartbeat,
+    /// New output in the log file of a followed node, starting at the given byte
+    /// offset.
+    Logs {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+        offset: u64,
+        data: Vec<u8>,
+    },
+    /// A node started to wait on the given barrier.
+    BarrierReached {
+        dataflow_id: DataflowId,
+        name: String,
+        node_id: NodeId,
+        expected: usize,
+    },
+    /// A node stopped waiting on the given barrier because its timeout expired.
+    BarrierLeft {
+        dataflow_id: DataflowId,
+        name: String,
+        node_id: NodeId,
+    },
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
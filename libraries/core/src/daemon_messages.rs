@@ -9,6 +9,7 @@ use std::{
 use crate::{
     config::{DataId, NodeId, NodeRunConfig, OperatorId},
     descriptor::{Descriptor, OperatorDefinition, ResolvedNode},
+    topics::LogRecord,
 };
 use aligned_vec::{AVec, ConstAlign};
 use dora_message::{uhlc, Metadata};
@@ -68,6 +69,20 @@ pub enum DaemonRequest {
     SubscribeDrop,
     NextFinishedDropTokens,
     EventStreamDropped,
+    /// Archives the current log files of the node and continues logging into empty
+    /// files.
+    RotateLog,
+    /// Waits until `expected` nodes of the dataflow reached the barrier with the
+    /// given name.
+    ///
+    /// The daemon replies with [`DaemonReply::Result`] once the barrier is released
+    /// or with [`DaemonReply::BarrierTimeout`] if it was not released within the
+    /// given timeout.
+    Barrier {
+        name: String,
+        expected: usize,
+        timeout: Duration,
+    },
 }
 
 impl DaemonRequest {
@@ -82,7 +97,9 @@ impl DaemonRequest {
             | DaemonRequest::NextEvent { .. }
             | DaemonRequest::SubscribeDrop
             | DaemonRequest::NextFinishedDropTokens
-            | DaemonRequest::EventStreamDropped => true,
+            | DaemonRequest::EventStreamDropped
+            | DaemonRequest::RotateLog
+            | DaemonRequest::Barrier { .. } => true,
         }
     }
 }
@@ -136,6 +153,7 @@ pub enum DaemonReply {
     PreparedMessage { shared_memory_id: SharedMemoryId },
     NextEvents(Vec<Timestamped<NodeEvent>>),
     NextDropEvents(Vec<Timestamped<NodeDropEvent>>),
+    BarrierTimeout,
     Empty,
 }
 
@@ -225,6 +243,36 @@ pub enum DaemonCoordinatorEvent {
         dataflow_id: DataflowId,
         node_id: NodeId,
     },
+    ClearLogs {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    },
+    StructuredLogs {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    },
+    /// Starts reporting new log output of the given node through
+    /// [`DaemonEvent::Logs`][crate::coordinator_messages::DaemonEvent::Logs] events.
+    FollowLogs {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    },
+    /// Stops reporting new log output of the given node, see [`Self::FollowLogs`].
+    UnfollowLogs {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    },
+    /// Queries the operating system process ID of the given running node.
+    NodePid {
+        dataflow_id: DataflowId,
+        node_id: NodeId,
+    },
+    /// All expected nodes reached the given barrier, so the local nodes that wait
+    /// on it can continue.
+    BarrierReleased {
+        dataflow_id: DataflowId,
+        name: String,
+    },
     Destroy,
     Heartbeat,
 }
@@ -255,6 +303,11 @@ pub enum DaemonCoordinatorReply {
         notify: Option<tokio::sync::oneshot::Sender<()>>,
     },
     Logs(Result<Vec<u8>, String>),
+    ClearLogsResult(Result<(), String>),
+    StructuredLogs(Result<Vec<LogRecord>, String>),
+    /// The current length of the log file on success.
+    FollowLogsResult(Result<u64, String>),
+    NodePid(Result<u32, String>),
 }
 
 pub type DataflowId = Uuid;
@@ -45,6 +45,40 @@ pub fn get_python_path() -> Result<std::path::PathBuf, eyre::ErrReport> {
     Ok(python)
 }
 
+/// Removes ANSI escape sequences (e.g. color codes) from the given text.
+///
+/// Handles control sequences (`ESC [ ... <final byte>`), operating system commands
+/// (`ESC ] ... BEL` or `ESC ] ... ESC \`), and two-byte escape sequences.
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                // skip parameter and intermediate bytes up to the final byte
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    stripped
+}
+
 // Search for pip binary.
 // First search for `pip3` as for ubuntu <20, `pip` can resolves to `python2,7 -m pip`
 // Then search for `pip`, this will resolve for windows to python3 -m pip.
@@ -1,3 +1,4 @@
+use dora_message::uhlc;
 use std::{
     collections::BTreeSet,
     fmt::Display,
@@ -50,6 +51,30 @@ pub enum ControlRequest {
         name: Option<String>,
         node: String,
     },
+    ClearLogs {
+        uuid: Uuid,
+        node: String,
+    },
+    StructuredLogs {
+        uuid: Uuid,
+        node: String,
+    },
+    /// Queries the operating system process ID of the given running node.
+    NodePid {
+        uuid: Uuid,
+        node: String,
+    },
+    /// Streams new log output of the given node.
+    ///
+    /// Unlike other requests, this request is answered by a stream of replies on
+    /// the same connection: first [`ControlRequestReply::LogsFollowed`] (or
+    /// [`ControlRequestReply::Error`]), then a [`ControlRequestReply::LogChunk`]
+    /// whenever the log file grows. The connection can't be used for other requests
+    /// afterwards.
+    FollowLogs {
+        uuid: Uuid,
+        node: String,
+    },
     Destroy,
     List,
     DaemonConnected,
@@ -78,6 +103,36 @@ pub enum ControlRequestReply {
     DaemonConnected(bool),
     ConnectedMachines(BTreeSet<String>),
     Logs(Vec<u8>),
+    LogsCleared,
+    StructuredLogs(Vec<LogRecord>),
+    NodePid(u32),
+    /// The log file had the given length in bytes when following started.
+    LogsFollowed {
+        offset: u64,
+    },
+    /// New log output, starting at the given byte offset of the log file.
+    LogChunk {
+        offset: u64,
+        data: Vec<u8>,
+    },
+}
+
+/// A log message of a node, as recorded by the daemon.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogRecord {
+    /// Time at which the daemon received the message.
+    pub timestamp: uhlc::Timestamp,
+    /// Time at which the node logged the message, if the message started with a
+    /// timestamp.
+    #[serde(default)]
+    pub logged_at: Option<uhlc::NTP64>,
+    /// Log level of the message (e.g. `INFO`), if it has one.
+    pub level: Option<String>,
+    /// Target of the message (e.g. the Rust module that logged it), if it has one.
+    pub target: Option<String>,
+    /// The message text, without timestamp, level, and target. Might span
+    /// multiple lines.
+    pub message: String,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
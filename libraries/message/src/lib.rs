@@ -1,6 +1,8 @@
 //! Enable serialisation and deserialisation of capnproto messages
 //!
 
+use std::collections::BTreeMap;
+
 use arrow_data::ArrayData;
 use arrow_schema::DataType;
 use eyre::Context;
@@ -103,11 +105,28 @@ pub struct BufferOffset {
     pub len: usize,
 }
 
+/// Prefix of the custom metadata parameters that restrict the receivers of a message.
+///
+/// A message with one or more `target.<node_id>` parameters is only delivered to
+/// the listed nodes. Messages without such parameters go to all receivers.
+pub const TARGET_PARAMETER_PREFIX: &str = "target.";
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub struct MetadataParameters {
     pub watermark: u64,
     pub deadline: u64,
     pub open_telemetry_context: String,
+    /// Additional named parameters that are passed along with the message.
+    pub custom: BTreeMap<String, Parameter>,
+}
+
+/// Value of a custom metadata parameter.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Parameter {
+    Bool(bool),
+    Integer(i64),
+    String(String),
+    ListInt(Vec<i64>),
 }
 
 impl MetadataParameters {